@@ -0,0 +1,221 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"reflect"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// abiValueError is a type mismatch between JSON value and ABI type, path points to the value (e.g. stakes[1].amount)
+type abiValueError struct {
+	Path    string
+	Type    string
+	Message string
+}
+
+func (e *abiValueError) Error() string {
+	return fmt.Sprintf("%s (%s): %s", e.Path, e.Type, e.Message)
+}
+
+func newAbiValueError(path string, t abi.Type, format string, args ...interface{}) error {
+	return &abiValueError{Path: path, Type: t.String(), Message: fmt.Sprintf(format, args...)}
+}
+
+// encodeAbiArguments encodes JSON array (or object with argument names) of function or constructor arguments
+func encodeAbiArguments(args abi.Arguments, rawValues json.RawMessage) ([]byte, error) {
+	var value interface{}
+	decoder := json.NewDecoder(strings.NewReader(string(rawValues)))
+	decoder.UseNumber()
+	if err := decoder.Decode(&value); err != nil {
+		return nil, err
+	}
+	var items []json.RawMessage
+	switch v := value.(type) {
+	case []interface{}:
+		if err := json.Unmarshal(rawValues, &items); err != nil {
+			return nil, err
+		}
+	case map[string]interface{}:
+		named := make(map[string]json.RawMessage)
+		if err := json.Unmarshal(rawValues, &named); err != nil {
+			return nil, err
+		}
+		for _, arg := range args {
+			item, ok := named[arg.Name]
+			if !ok {
+				return nil, fmt.Errorf("argument %s is missing", arg.Name)
+			}
+			items = append(items, item)
+		}
+		if len(v) != len(args) {
+			return nil, fmt.Errorf("expected %d argument(s), got %d", len(args), len(v))
+		}
+	default:
+		return nil, fmt.Errorf("arguments must be JSON array or object, got %v", value)
+	}
+	values, err := decodeAbiArguments(args, items)
+	if err != nil {
+		return nil, err
+	}
+	return args.Pack(values...)
+}
+
+// decodeAbiArguments converts JSON values (numbers can be strings, bytes are hex, tuples are objects or arrays)
+// into Go values accepted by abi.Pack
+func decodeAbiArguments(args abi.Arguments, rawValues []json.RawMessage) ([]interface{}, error) {
+	if len(args) != len(rawValues) {
+		return nil, fmt.Errorf("expected %d argument(s), got %d", len(args), len(rawValues))
+	}
+	var result []interface{}
+	for i, arg := range args {
+		var value interface{}
+		decoder := json.NewDecoder(strings.NewReader(string(rawValues[i])))
+		decoder.UseNumber()
+		if err := decoder.Decode(&value); err != nil {
+			return nil, fmt.Errorf("argument %d: %w", i, err)
+		}
+		path := arg.Name
+		if path == "" {
+			path = fmt.Sprintf("args[%d]", i)
+		}
+		converted, err := toAbiValue(path, arg.Type, value)
+		if err != nil {
+			return nil, err
+		}
+		result = append(result, converted.Interface())
+	}
+	return result, nil
+}
+
+func toAbiValue(path string, t abi.Type, value interface{}) (reflect.Value, error) {
+	switch t.T {
+	case abi.AddressTy:
+		s, ok := value.(string)
+		if !ok || !common.IsHexAddress(s) {
+			return reflect.Value{}, newAbiValueError(path, t, "expected address, got %v", value)
+		}
+		return reflect.ValueOf(common.HexToAddress(s)), nil
+	case abi.UintTy, abi.IntTy:
+		var text string
+		switch v := value.(type) {
+		case json.Number:
+			text = v.String()
+		case string:
+			text = v
+		default:
+			return reflect.Value{}, newAbiValueError(path, t, "expected integer, got %v", value)
+		}
+		number, ok := new(big.Int).SetString(text, 0)
+		if !ok {
+			return reflect.Value{}, newAbiValueError(path, t, "invalid integer %q", text)
+		}
+		if t.T == abi.UintTy && (number.Sign() < 0 || number.BitLen() > t.Size) {
+			return reflect.Value{}, newAbiValueError(path, t, "%s doesn't fit into uint%d", number, t.Size)
+		}
+		// magnitude of negative value can be one more than positive one (-2^(n-1))
+		magnitude := new(big.Int).Abs(number)
+		if number.Sign() < 0 {
+			magnitude.Sub(magnitude, big.NewInt(1))
+		}
+		if t.T == abi.IntTy && magnitude.BitLen() > t.Size-1 {
+			return reflect.Value{}, newAbiValueError(path, t, "%s doesn't fit into int%d", number, t.Size)
+		}
+		goType := t.GetType()
+		if goType == reflect.TypeOf(&big.Int{}) {
+			return reflect.ValueOf(number), nil
+		}
+		if t.T == abi.UintTy {
+			return reflect.ValueOf(number.Uint64()).Convert(goType), nil
+		}
+		return reflect.ValueOf(number.Int64()).Convert(goType), nil
+	case abi.BoolTy:
+		b, ok := value.(bool)
+		if !ok {
+			return reflect.Value{}, newAbiValueError(path, t, "expected bool, got %v", value)
+		}
+		return reflect.ValueOf(b), nil
+	case abi.StringTy:
+		s, ok := value.(string)
+		if !ok {
+			return reflect.Value{}, newAbiValueError(path, t, "expected string, got %v", value)
+		}
+		return reflect.ValueOf(s), nil
+	case abi.BytesTy, abi.FixedBytesTy:
+		s, ok := value.(string)
+		if !ok {
+			return reflect.Value{}, newAbiValueError(path, t, "expected hex string, got %v", value)
+		}
+		data, err := hexutil.Decode(s)
+		if err != nil {
+			return reflect.Value{}, newAbiValueError(path, t, "%s", err)
+		}
+		if t.T == abi.BytesTy {
+			return reflect.ValueOf(data), nil
+		}
+		if len(data) != t.Size {
+			return reflect.Value{}, newAbiValueError(path, t, "expected %d byte(s), got %d", t.Size, len(data))
+		}
+		result := reflect.New(t.GetType()).Elem()
+		reflect.Copy(result, reflect.ValueOf(data))
+		return result, nil
+	case abi.SliceTy, abi.ArrayTy:
+		items, ok := value.([]interface{})
+		if !ok {
+			return reflect.Value{}, newAbiValueError(path, t, "expected array, got %v", value)
+		}
+		var result reflect.Value
+		if t.T == abi.SliceTy {
+			result = reflect.MakeSlice(t.GetType(), len(items), len(items))
+		} else if len(items) != t.Size {
+			return reflect.Value{}, newAbiValueError(path, t, "expected %d element(s), got %d", t.Size, len(items))
+		} else {
+			result = reflect.New(t.GetType()).Elem()
+		}
+		for i, item := range items {
+			element, err := toAbiValue(fmt.Sprintf("%s[%d]", path, i), *t.Elem, item)
+			if err != nil {
+				return reflect.Value{}, err
+			}
+			result.Index(i).Set(element)
+		}
+		return result, nil
+	case abi.TupleTy:
+		var fields []interface{}
+		switch v := value.(type) {
+		case []interface{}:
+			fields = v
+		case map[string]interface{}:
+			for _, name := range t.TupleRawNames {
+				field, ok := v[name]
+				if !ok {
+					return reflect.Value{}, newAbiValueError(path, t, "field %s is missing", name)
+				}
+				fields = append(fields, field)
+			}
+			if len(v) != len(t.TupleRawNames) {
+				return reflect.Value{}, newAbiValueError(path, t, "expected fields %s", strings.Join(t.TupleRawNames, ", "))
+			}
+		default:
+			return reflect.Value{}, newAbiValueError(path, t, "expected object or array, got %v", value)
+		}
+		if len(fields) != len(t.TupleElems) {
+			return reflect.Value{}, newAbiValueError(path, t, "expected %d field(s), got %d", len(t.TupleElems), len(fields))
+		}
+		result := reflect.New(t.GetType()).Elem()
+		for i, elem := range t.TupleElems {
+			field, err := toAbiValue(path+"."+t.TupleRawNames[i], *elem, fields[i])
+			if err != nil {
+				return reflect.Value{}, err
+			}
+			result.Field(i).Set(field)
+		}
+		return result, nil
+	}
+	return reflect.Value{}, newAbiValueError(path, t, "unsupported type")
+}
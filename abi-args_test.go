@@ -0,0 +1,66 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"math/big"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+)
+
+const testAbi = `[{"type":"constructor","inputs":[
+	{"name":"owner","type":"address"},
+	{"name":"limits","type":"uint16[]"},
+	{"name":"stake","type":"tuple","components":[{"name":"validator","type":"address"},{"name":"amount","type":"uint256"}]}
+]}]`
+
+func TestEncodeAbiArguments(t *testing.T) {
+	parsed, err := abi.JSON(strings.NewReader(testAbi))
+	if err != nil {
+		t.Fatal(err)
+	}
+	inputs := parsed.Constructor.Inputs
+	positional, err := encodeAbiArguments(inputs, json.RawMessage(`["0x00a601f45688dba8a070722073b015277cf36725", [1, "0x2"], {"validator": "0x08fae3885e299c24ff9841478eb946f41023ac69", "amount": "1000000000000000000000"}]`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := encodeAbiArguments(inputs, json.RawMessage(`{"owner": "0x00a601f45688dba8a070722073b015277cf36725", "limits": [1, 2], "stake": ["0x08fae3885e299c24ff9841478eb946f41023ac69", 1e21]}`)); err == nil {
+		t.Fatal("float literal must be rejected for uint256")
+	}
+	named, err := encodeAbiArguments(inputs, json.RawMessage(`{"owner": "0x00a601f45688dba8a070722073b015277cf36725", "limits": [1, 2], "stake": ["0x08fae3885e299c24ff9841478eb946f41023ac69", "0x3635c9adc5dea00000"]}`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if string(positional) != string(named) {
+		t.Fatal("positional and named arguments must be encoded the same way")
+	}
+	values, err := inputs.Unpack(positional)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if values[0].(common.Address) != common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725") {
+		t.Fatalf("wrong owner: %v", values[0])
+	}
+	if amount := reflect.ValueOf(values[2]).Field(1).Interface().(*big.Int); amount.String() != "1000000000000000000000" {
+		t.Fatalf("wrong stake amount: %s", amount)
+	}
+}
+
+func TestEncodeAbiArgumentsErrors(t *testing.T) {
+	parsed, err := abi.JSON(strings.NewReader(testAbi))
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, err = encodeAbiArguments(parsed.Constructor.Inputs, json.RawMessage(`["0x00a601f45688dba8a070722073b015277cf36725", [1, 65536], {"validator": "0x08fae3885e299c24ff9841478eb946f41023ac69", "amount": 1}]`))
+	var valueErr *abiValueError
+	if !errors.As(err, &valueErr) {
+		t.Fatalf("expected abiValueError, got %v", err)
+	}
+	if valueErr.Path != "limits[1]" || valueErr.Type != "uint16" {
+		t.Fatalf("wrong error location: %s", valueErr)
+	}
+}
@@ -0,0 +1,57 @@
+package main
+
+import (
+	"bytes"
+	"math/big"
+	"sort"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// accountConfig is a genesis account written as is (e.g. CREATE2 deployer with nonce and code copied from
+// mainnet), it's in state before system contracts are simulated, so their constructors can call it
+type accountConfig struct {
+	Balance *amountValue                `json:"balance,omitempty"`
+	Nonce   uint64                      `json:"nonce,omitempty"`
+	Code    hexutil.Bytes               `json:"code,omitempty"`
+	Storage map[common.Hash]common.Hash `json:"storage,omitempty"`
+}
+
+func (a accountConfig) genesisAccount() core.GenesisAccount {
+	balance := big.NewInt(0)
+	if a.Balance != nil {
+		balance = new(big.Int).Set((*big.Int)(a.Balance))
+	}
+	result := core.GenesisAccount{Balance: balance, Nonce: a.Nonce, Code: a.Code}
+	if len(a.Storage) > 0 {
+		result.Storage = make(map[common.Hash]common.Hash, len(a.Storage))
+		for key, value := range a.Storage {
+			result.Storage[key] = value
+		}
+	}
+	return result
+}
+
+// applyAccountConfigs writes configured accounts into genesis alloc and returns sum of their balances
+func applyAccountConfigs(genesis *core.Genesis, accounts map[common.Address]accountConfig) *big.Int {
+	total := big.NewInt(0)
+	for address, account := range accounts {
+		genesisAccount := account.genesisAccount()
+		genesis.Alloc[address] = genesisAccount
+		total.Add(total, genesisAccount.Balance)
+	}
+	return total
+}
+
+func sortedAccountAddresses(accounts map[common.Address]accountConfig) []common.Address {
+	var result []common.Address
+	for address := range accounts {
+		result = append(result, address)
+	}
+	sort.Slice(result, func(i, j int) bool {
+		return bytes.Compare(result[i][:], result[j][:]) < 0
+	})
+	return result
+}
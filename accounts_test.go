@@ -0,0 +1,58 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"math/big"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+func TestAccountConfigs(t *testing.T) {
+	// deterministic deployment proxy (CREATE2 factory) as it is on mainnet
+	factory := common.HexToAddress("0x4e59b44847b379578588920cA78FbF26c0B4956C")
+	code := common.FromHex("0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3")
+	config := devNetConfig
+	config.Accounts = map[common.Address]accountConfig{
+		factory: {
+			Balance: (*amountValue)(big.NewInt(1)),
+			Nonce:   1,
+			Code:    code,
+			Storage: map[common.Hash]common.Hash{common.HexToHash("0x01"): common.HexToHash("0x2a")},
+		},
+	}
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+	genesis, err := bgenesis.Load(outputFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	account, ok := genesis.Alloc[factory]
+	if !ok {
+		t.Fatal("configured account is missing in genesis")
+	}
+	if account.Nonce != 1 || account.Balance.Int64() != 1 || !bytes.Equal(account.Code, code) || account.Storage[common.HexToHash("0x01")] != common.HexToHash("0x2a") {
+		t.Fatalf("unexpected account: %+v", account)
+	}
+}
+
+func TestAccountConfigCollisions(t *testing.T) {
+	config := devNetConfig
+	funded := common.HexToAddress("0x00000000000000000000000000000000000000aa")
+	config.Faucet = map[common.Address]*amountValue{funded: (*amountValue)(big.NewInt(1e18))}
+	config.Accounts = map[common.Address]accountConfig{
+		funded:                    {Nonce: 1},
+		stakingAddress:            {Nonce: 1},
+		intermediarySystemAddress: {Nonce: 1},
+	}
+	var violations configViolations
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 3 {
+		t.Fatalf("expected 3 violations, got %v", err)
+	}
+}
@@ -0,0 +1,113 @@
+package main
+
+import (
+	"encoding/csv"
+	"errors"
+	"fmt"
+	"io"
+	"math/big"
+	"os"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// max number of broken rows printed in the error, the rest is only counted
+const maxReportedAirdropErrors = 20
+
+type airdropErrors struct {
+	file   string
+	errors []string
+	total  int
+}
+
+func (e *airdropErrors) add(format string, args ...interface{}) {
+	e.total++
+	if len(e.errors) < maxReportedAirdropErrors {
+		e.errors = append(e.errors, fmt.Sprintf(format, args...))
+	}
+}
+
+func (e *airdropErrors) Error() string {
+	message := fmt.Sprintf("airdrop file %s has %d invalid row(s):\n  %s", e.file, e.total, strings.Join(e.errors, "\n  "))
+	if e.total > len(e.errors) {
+		message += fmt.Sprintf("\n  ... and %d more", e.total-len(e.errors))
+	}
+	return message
+}
+
+// readAirdrop streams "address,amount" rows (header row is optional) from CSV file, amounts are in any
+// format supported by parseAmount, all broken rows are reported at once
+func readAirdrop(path string, progress func(done, total int)) (map[common.Address]*big.Int, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+	reader := csv.NewReader(file)
+	reader.FieldsPerRecord = 2
+	reader.TrimLeadingSpace = true
+	reader.ReuseRecord = true
+	result := make(map[common.Address]*big.Int)
+	firstLine := make(map[common.Address]int)
+	failures := &airdropErrors{file: path}
+	for line := 1; ; line++ {
+		progress(line-1, 0)
+		record, err := reader.Read()
+		if errors.Is(err, io.EOF) {
+			break
+		}
+		var parseErr *csv.ParseError
+		if errors.As(err, &parseErr) {
+			failures.add("line %d: %s", parseErr.Line, parseErr.Err)
+			continue
+		} else if err != nil {
+			return nil, err
+		}
+		rawAddress, rawAmount := strings.TrimSpace(record[0]), strings.TrimSpace(record[1])
+		if !addressPattern.MatchString(rawAddress) {
+			// header row
+			if line == 1 {
+				continue
+			}
+			failures.add("line %d: invalid address %q", line, rawAddress)
+			continue
+		}
+		address := common.HexToAddress(rawAddress)
+		if rawAddress[2:] != strings.ToLower(rawAddress[2:]) && address.Hex() != rawAddress {
+			failures.add("line %d: invalid EIP-55 checksum of %s", line, rawAddress)
+			continue
+		}
+		amount, err := parseAmount(rawAmount)
+		if err != nil {
+			failures.add("line %d: %s", line, err)
+			continue
+		}
+		if prev, ok := firstLine[address]; ok {
+			failures.add("line %d: %s duplicates line %d", line, address.Hex(), prev)
+			continue
+		}
+		firstLine[address] = line
+		result[address] = amount
+	}
+	if failures.total > 0 {
+		return nil, failures
+	}
+	return result, nil
+}
+
+// applyAirdrop adds airdrop balances to genesis alloc (existing accounts keep their code and storage)
+func applyAirdrop(genesis *core.Genesis, balances map[common.Address]*big.Int) *big.Int {
+	total := big.NewInt(0)
+	for address, amount := range balances {
+		account := genesis.Alloc[address]
+		if account.Balance == nil {
+			account.Balance = new(big.Int)
+		}
+		account.Balance = new(big.Int).Add(account.Balance, amount)
+		genesis.Alloc[address] = account
+		total.Add(total, amount)
+	}
+	return total
+}
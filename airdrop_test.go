@@ -0,0 +1,69 @@
+package main
+
+import (
+	"errors"
+	"math/big"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func writeAirdropFile(t *testing.T, content string) string {
+	path := filepath.Join(t.TempDir(), "airdrop.csv")
+	if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+	return path
+}
+
+func TestReadAirdrop(t *testing.T) {
+	first, second := common.HexToAddress("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"), common.HexToAddress("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359")
+	path := writeAirdropFile(t, "address,amount\n0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed,1.5 ether\n0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359, 30 gwei\n")
+	balances, err := readAirdrop(path, func(done, total int) {})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(balances) != 2 || balances[first].String() != "1500000000000000000" || balances[second].Int64() != 30_000_000_000 {
+		t.Fatalf("unexpected balances %v", balances)
+	}
+	genesis := &core.Genesis{Alloc: core.GenesisAlloc{first: {Balance: big.NewInt(1), Code: []byte{0x60, 0x00}}}}
+	total := applyAirdrop(genesis, balances)
+	if total.String() != "1500000030000000000" || genesis.Alloc[first].Balance.String() != "1500000000000000001" || len(genesis.Alloc[first].Code) != 2 {
+		t.Fatalf("unexpected airdrop result %s %+v", total, genesis.Alloc)
+	}
+}
+
+func TestReadAirdropErrors(t *testing.T) {
+	path := writeAirdropFile(t, strings.Join([]string{
+		"address,amount",
+		"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed,1 ether",
+		"not-an-address,1",
+		"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD,1",
+		"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359,1 btc",
+		"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed,2 ether",
+		"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+	}, "\n"))
+	_, err := readAirdrop(path, func(done, total int) {})
+	var failures *airdropErrors
+	if !errors.As(err, &failures) || failures.total != 5 {
+		t.Fatalf("expected 5 broken rows, got %v", err)
+	}
+	for i, expected := range []string{"line 3: invalid address", "line 4: invalid EIP-55 checksum", "line 5:", "line 6: 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed duplicates line 2", "line 7:"} {
+		if !strings.HasPrefix(failures.errors[i], expected) {
+			t.Errorf("expected %q, got %q", expected, failures.errors[i])
+		}
+	}
+	// only the first errors are printed
+	rows := []string{"address,amount"}
+	for i := 0; i < maxReportedAirdropErrors+5; i++ {
+		rows = append(rows, "0x1,1")
+	}
+	_, err = readAirdrop(writeAirdropFile(t, strings.Join(rows, "\n")), func(done, total int) {})
+	if !errors.As(err, &failures) || len(failures.errors) != maxReportedAirdropErrors || !strings.Contains(err.Error(), "... and 5 more") {
+		t.Fatalf("unexpected error for many broken rows: %v", err)
+	}
+}
@@ -0,0 +1,92 @@
+package main
+
+import (
+	"fmt"
+	"math/big"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// amountUnits maps unit names to decimals, BAS is the native token of the chain (18 decimals like ether)
+var amountUnits = map[string]int{
+	"wei":   0,
+	"gwei":  9,
+	"ether": 18,
+	"eth":   18,
+	"bas":   18,
+}
+
+// parseAmount parses balance given as 0x-prefixed hex wei, decimal wei or decimal number with unit
+// (e.g. "1000000 ether", "1.5 BAS", "30 gwei")
+func parseAmount(value string) (*big.Int, error) {
+	value = strings.TrimSpace(value)
+	if strings.HasPrefix(value, "0x") || strings.HasPrefix(value, "0X") {
+		result, ok := new(big.Int).SetString(value[2:], 16)
+		if !ok || value[2:] == "" || strings.ContainsAny(value[2:], "+-") {
+			return nil, fmt.Errorf("invalid hex amount: %s", value)
+		}
+		if result.BitLen() > 256 {
+			return nil, fmt.Errorf("amount doesn't fit into uint256: %s", value)
+		}
+		return result, nil
+	}
+	number, decimals := value, 0
+	if fields := strings.Fields(value); len(fields) == 2 {
+		unitDecimals, ok := amountUnits[strings.ToLower(fields[1])]
+		if !ok {
+			return nil, fmt.Errorf("unknown unit (%s) in amount: %s", fields[1], value)
+		}
+		number, decimals = fields[0], unitDecimals
+	} else if len(fields) != 1 {
+		return nil, fmt.Errorf("invalid amount: %s", value)
+	}
+	integer, fraction := number, ""
+	if i := strings.IndexByte(number, '.'); i >= 0 {
+		integer, fraction = number[:i], number[i+1:]
+	}
+	if len(fraction) > decimals {
+		return nil, fmt.Errorf("amount has more than %d fractional digits: %s", decimals, value)
+	}
+	digits := integer + fraction + strings.Repeat("0", decimals-len(fraction))
+	if integer == "" || strings.ContainsAny(digits, "+-") {
+		return nil, fmt.Errorf("invalid amount: %s", value)
+	}
+	result, ok := new(big.Int).SetString(digits, 10)
+	if !ok {
+		return nil, fmt.Errorf("invalid amount: %s", value)
+	}
+	if result.BitLen() > 256 {
+		return nil, fmt.Errorf("amount doesn't fit into uint256: %s", value)
+	}
+	return result, nil
+}
+
+// formatAmount renders wei in ether units without precision loss, result can be parsed by parseAmount
+func formatAmount(wei *big.Int) string {
+	ether := new(big.Int).Exp(big.NewInt(10), big.NewInt(18), nil)
+	integer, fraction := new(big.Int).QuoRem(wei, ether, new(big.Int))
+	if fraction.Sign() == 0 {
+		return fmt.Sprintf("%s ether", integer)
+	}
+	digits := fraction.String()
+	digits = strings.Repeat("0", 18-len(digits)) + digits
+	return fmt.Sprintf("%s.%s ether", integer, strings.TrimRight(digits, "0"))
+}
+
+// amountValue is wei amount accepting the same formats as parseAmount, it's encoded as geth quantity (minimal
+// 0x-prefixed hex), so configs and reports can be fed back without conversion
+type amountValue big.Int
+
+func (a *amountValue) UnmarshalText(input []byte) error {
+	result, err := parseAmount(string(input))
+	if err != nil {
+		return err
+	}
+	*a = amountValue(*result)
+	return nil
+}
+
+func (a *amountValue) MarshalText() ([]byte, error) {
+	return []byte(hexutil.EncodeBig((*big.Int)(a))), nil
+}
@@ -0,0 +1,70 @@
+package main
+
+import (
+	"encoding/json"
+	"math/big"
+	"strings"
+	"testing"
+)
+
+func TestParseAmount(t *testing.T) {
+	ether := new(big.Int).Exp(big.NewInt(10), big.NewInt(18), nil)
+	cases := map[string]*big.Int{
+		"0xde0b6b3a7640000":   ether,
+		"1000000000000000000": ether,
+		"1 ether":              ether,
+		"1.5 BAS":              new(big.Int).Div(new(big.Int).Mul(ether, big.NewInt(3)), big.NewInt(2)),
+		"1000000 ether":        new(big.Int).Mul(ether, big.NewInt(1000000)),
+		"30 gwei":              big.NewInt(30_000_000_000),
+		"7 wei":                big.NewInt(7),
+	}
+	for input, expected := range cases {
+		result, err := parseAmount(input)
+		if err != nil {
+			t.Fatalf("failed to parse %q: %s", input, err)
+		}
+		if result.Cmp(expected) != 0 {
+			t.Fatalf("wrong amount for %q: have %s, want %s", input, result, expected)
+		}
+	}
+}
+
+func TestParseAmountErrors(t *testing.T) {
+	for _, input := range []string{"", "0x", "1.5", "1 btc", "0.0000000000000000001 ether", "-1 ether", "1 2 ether", ".5 ether", "0x-5", "0x+5", "0x1" + strings.Repeat("0", 64)} {
+		if _, err := parseAmount(input); err == nil {
+			t.Fatalf("expected error for %q", input)
+		}
+	}
+}
+
+func TestFormatAmountRoundTrip(t *testing.T) {
+	for _, input := range []string{"0", "1", "1000000000000000000", "1500000000000000000", "123456789012345678901234567890"} {
+		wei, _ := new(big.Int).SetString(input, 10)
+		formatted := formatAmount(wei)
+		result, err := parseAmount(formatted)
+		if err != nil {
+			t.Fatalf("failed to parse formatted amount %q: %s", formatted, err)
+		}
+		if result.Cmp(wei) != 0 {
+			t.Fatalf("round trip mismatch for %s: %q parsed as %s", input, formatted, result)
+		}
+	}
+}
+
+func TestAmountValueJson(t *testing.T) {
+	var values map[string]*amountValue
+	if err := json.Unmarshal([]byte(`{"a": "1.5 ether", "b": "0x0", "c": "1000"}`), &values); err != nil {
+		t.Fatal(err)
+	}
+	encoded, err := json.Marshal(values)
+	if err != nil {
+		t.Fatal(err)
+	}
+	// geth style quantities, minimal hex without leading zeros
+	if expected := `{"a":"0x14d1120d7b160000","b":"0x0","c":"0x3e8"}`; string(encoded) != expected {
+		t.Fatalf("unexpected encoding: have %s, want %s", encoded, expected)
+	}
+	if err := json.Unmarshal([]byte(`{"a": "1 btc"}`), &values); err == nil {
+		t.Fatal("unknown unit is accepted")
+	}
+}
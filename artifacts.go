@@ -0,0 +1,118 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// artifactSet maps system contract names to raw truffle artifacts
+type artifactSet map[string][]byte
+
+func defaultArtifactSet() artifactSet {
+	return artifactSet{
+		"Staking":           stakingRawArtifact,
+		"StakingPool":       stakingPoolRawArtifact,
+		"ChainConfig":       chainConfigRawArtifact,
+		"SlashingIndicator": slashingIndicatorRawArtifact,
+		"SystemReward":      systemRewardRawArtifact,
+		"Governance":        governanceRawArtifact,
+		"RuntimeUpgrade":    runtimeUpgradeRawArtifact,
+		"DeployerProxy":     deployerProxyRawArtifact,
+	}
+}
+
+// loadArtifactSet reads artifacts from the directory (truffle's build/contracts layout), missing
+// artifacts fall back to the embedded ones
+func loadArtifactSet(dir string) (artifactSet, error) {
+	result := defaultArtifactSet()
+	if dir == "" {
+		return result, nil
+	}
+	// don't stop on the first broken artifact, report all of them at once
+	var failures artifactFailures
+	for _, name := range result.names() {
+		fileName := filepath.Join(dir, name+".json")
+		rawArtifact, err := os.ReadFile(fileName)
+		if errors.Is(err, fs.ErrNotExist) {
+			continue
+		} else if err != nil {
+			failures = append(failures, fmt.Sprintf("%s: %s", fileName, err))
+			continue
+		}
+		if err := checkArtifact(rawArtifact); err != nil {
+			failures = append(failures, fmt.Sprintf("%s: %s", fileName, err))
+			continue
+		}
+		result[name] = rawArtifact
+	}
+	if len(failures) > 0 {
+		return nil, failures
+	}
+	return result, nil
+}
+
+func (s artifactSet) names() []string {
+	var result []string
+	for name := range s {
+		result = append(result, name)
+	}
+	sort.Strings(result)
+	return result
+}
+
+type artifactFailures []string
+
+func (f artifactFailures) Error() string {
+	return fmt.Sprintf("failed to load %d artifact(s):\n  %s", len(f), strings.Join(f, "\n  "))
+}
+
+// checkArtifact makes sure that artifact is a valid JSON with bytecode and ABI, syntax errors are
+// reported with line and column
+func checkArtifact(rawArtifact []byte) error {
+	artifact := &artifactData{}
+	if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+		var syntaxError *json.SyntaxError
+		var typeError *json.UnmarshalTypeError
+		if errors.As(err, &syntaxError) {
+			line, column := jsonPosition(rawArtifact, syntaxError.Offset)
+			return fmt.Errorf("%d:%d: %s", line, column, err)
+		} else if errors.As(err, &typeError) {
+			line, column := jsonPosition(rawArtifact, typeError.Offset)
+			return fmt.Errorf("%d:%d: %s", line, column, err)
+		}
+		return err
+	}
+	if _, err := hexutil.Decode(artifact.Bytecode); err != nil {
+		return fmt.Errorf("invalid bytecode: %s", err)
+	}
+	if _, err := abi.JSON(bytes.NewReader(artifact.Abi)); err != nil {
+		return fmt.Errorf("invalid abi: %s", err)
+	}
+	return nil
+}
+
+func jsonPosition(data []byte, offset int64) (line int, column int) {
+	if offset > int64(len(data)) {
+		offset = int64(len(data))
+	}
+	line, column = 1, 1
+	for _, c := range data[:offset] {
+		if c == '\n' {
+			line++
+			column = 1
+		} else {
+			column++
+		}
+	}
+	return line, column
+}
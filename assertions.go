@@ -0,0 +1,127 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/core/vm"
+)
+
+// assertionConfig is a view call executed against generated genesis, its result must match expected values
+type assertionConfig struct {
+	// name of the system contract (e.g. Staking)
+	Contract string `json:"contract"`
+	// function signature as in ABI (e.g. getValidators() or getValidatorStatus(address))
+	Call string `json:"call"`
+	// call arguments, JSON array or object with argument names
+	Args json.RawMessage `json:"args,omitempty"`
+	// expected return values, JSON array or object with output names
+	Expect json.RawMessage `json:"expect"`
+}
+
+// assertionFailures are assertions that don't hold for generated genesis
+type assertionFailures []string
+
+func (f assertionFailures) Error() string {
+	return fmt.Sprintf("%d assertion(s) failed:\n  %s", len(f), strings.Join(f, "\n  "))
+}
+
+// findAbiMethod looks up the function by signature (e.g. transfer(address,uint256)) or by name if it isn't overloaded
+func findAbiMethod(contractAbi *abi.ABI, signature string) (*abi.Method, error) {
+	var found []abi.Method
+	for _, method := range contractAbi.Methods {
+		if method.Sig == signature || method.RawName == signature {
+			found = append(found, method)
+		}
+	}
+	switch len(found) {
+	case 0:
+		return nil, fmt.Errorf("there is no function %s in ABI", signature)
+	case 1:
+		return &found[0], nil
+	}
+	return nil, fmt.Errorf("function %s is overloaded, specify argument types", signature)
+}
+
+// verifyAssertions executes configured view calls against genesis state after init of system contracts, all
+// failed assertions are reported at once
+func verifyAssertions(genesis *core.Genesis, assertions []assertionConfig, deployments []*systemContractDeployment, artifacts artifactSet) error {
+	if len(assertions) == 0 {
+		return nil
+	}
+	statedb, err := newStateFromGenesis(genesis)
+	if err != nil {
+		return err
+	}
+	evm := newGenesisEVM(genesis, statedb)
+	// system contracts are initialized by consensus engine in the first block, lets do the same
+	if err := initSystemContracts(evm); err != nil {
+		return err
+	}
+	var failures assertionFailures
+	for i, assertion := range assertions {
+		if err := checkAssertion(evm, assertion, deployments, artifacts); err != nil {
+			failures = append(failures, fmt.Sprintf("assertions[%d] %s.%s: %s", i, assertion.Contract, assertion.Call, err))
+		}
+	}
+	if len(failures) > 0 {
+		return failures
+	}
+	return nil
+}
+
+func checkAssertion(evm *vm.EVM, assertion assertionConfig, deployments []*systemContractDeployment, artifacts artifactSet) error {
+	var deployment *systemContractDeployment
+	for _, d := range deployments {
+		if strings.EqualFold(d.name, assertion.Contract) {
+			deployment = d
+		}
+	}
+	if deployment == nil {
+		return fmt.Errorf("unknown system contract")
+	}
+	artifact := &artifactData{}
+	if err := json.Unmarshal(artifacts[deployment.name], artifact); err != nil {
+		return err
+	}
+	contractAbi := parseArtifactAbi(artifact)
+	if contractAbi == nil {
+		return fmt.Errorf("artifact of %s doesn't contain valid ABI", deployment.name)
+	}
+	method, err := findAbiMethod(contractAbi, assertion.Call)
+	if err != nil {
+		return err
+	}
+	args := assertion.Args
+	if len(args) == 0 {
+		args = json.RawMessage("[]")
+	}
+	input, err := encodeAbiArguments(method.Inputs, args)
+	if err != nil {
+		return fmt.Errorf("invalid args: %w", err)
+	}
+	expected, err := encodeAbiArguments(method.Outputs, assertion.Expect)
+	if err != nil {
+		return fmt.Errorf("invalid expect: %w", err)
+	}
+	result, _, err := evm.StaticCall(vm.AccountRef(common.Address{}), deployment.address, append(method.ID[:4:4], input...), 10_000_000)
+	if err != nil {
+		return fmt.Errorf("call failed: %w (%s)", err, decodeRevert(result, contractAbi))
+	}
+	// ABI encoding is canonical, so equal values have equal encoding
+	if bytes.Equal(result, expected) {
+		return nil
+	}
+	actual, err := method.Outputs.Unpack(result)
+	if err != nil {
+		return fmt.Errorf("unexpected result %s: %w", hexutil.Encode(result), err)
+	}
+	wanted, _ := method.Outputs.Unpack(expected)
+	return fmt.Errorf("expected %v, got %v", wanted, actual)
+}
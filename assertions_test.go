@@ -0,0 +1,26 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"path/filepath"
+	"testing"
+)
+
+func TestGenesisAssertions(t *testing.T) {
+	config := devNetConfig
+	config.Assertions = []assertionConfig{
+		{Contract: "ChainConfig", Call: "getEpochBlockInterval()", Expect: json.RawMessage(`[1200]`)},
+		{Contract: "chainconfig", Call: "getActiveValidatorsLength", Expect: json.RawMessage(`["25"]`)},
+	}
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+	config.Assertions = append(config.Assertions, assertionConfig{Contract: "ChainConfig", Call: "getEpochBlockInterval()", Expect: json.RawMessage(`[28800]`)})
+	err := createGenesisConfig(config, outputFile, defaultGenerateOptions())
+	var failures assertionFailures
+	if !errors.As(err, &failures) || len(failures) != 1 {
+		t.Fatalf("expected one failed assertion, got %v", err)
+	}
+}
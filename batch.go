@@ -0,0 +1,207 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/log"
+	"gopkg.in/yaml.v2"
+)
+
+// batchManifest lists genesis configs generated in one invocation, relative paths are resolved against the
+// directory of the manifest
+type batchManifest struct {
+	// artifacts shared by all chains (default is embedded)
+	Artifacts string `yaml:"artifacts"`
+	// simulation cache shared by all chains (optional)
+	CacheDir string `yaml:"cacheDir"`
+	// defaults for chains that don't set their own
+	Format       string `yaml:"format"`
+	OutputFormat string `yaml:"outputFormat"`
+	// chains generated in the listed order
+	Chains []batchChain `yaml:"chains"`
+}
+
+type batchChain struct {
+	Name   string `yaml:"name"`
+	Config string `yaml:"config"`
+	Output string `yaml:"output"`
+	// JSON file with values of config placeholders (optional)
+	Vars string `yaml:"vars"`
+	// optional outputs and per-chain layout
+	Report       string `yaml:"report"`
+	Spec         string `yaml:"spec"`
+	Format       string `yaml:"format"`
+	OutputFormat string `yaml:"outputFormat"`
+	Patch        string `yaml:"patch"`
+	Airdrop      string `yaml:"airdrop"`
+}
+
+// batchFailures are chains that failed to generate, the rest of the manifest is still generated
+type batchFailures []string
+
+func (f batchFailures) Error() string {
+	return fmt.Sprintf("failed to generate %d chain(s):\n  %s", len(f), strings.Join(f, "\n  "))
+}
+
+func readBatchManifest(path string) (*batchManifest, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	manifest := &batchManifest{}
+	if err := yaml.UnmarshalStrict(data, manifest); err != nil {
+		return nil, fmt.Errorf("failed to parse manifest %s: %w", path, err)
+	}
+	baseDir := filepath.Dir(path)
+	resolve := func(path *string) {
+		if *path != "" && *path != "stdout" && *path != "stderr" && !filepath.IsAbs(*path) {
+			*path = filepath.Join(baseDir, *path)
+		}
+	}
+	resolve(&manifest.Artifacts)
+	resolve(&manifest.CacheDir)
+	var violations configViolations
+	seen := make(map[string]bool)
+	for i := range manifest.Chains {
+		chain := &manifest.Chains[i]
+		path := fmt.Sprintf("chains[%d]", i)
+		if chain.Name == "" {
+			violations.add(path+".name", "is required")
+		} else if seen[chain.Name] {
+			violations.add(path+".name", "duplicate chain %q", chain.Name)
+		}
+		seen[chain.Name] = true
+		if chain.Config == "" {
+			violations.add(path+".config", "is required")
+		}
+		if chain.Output == "" {
+			violations.add(path+".output", "is required")
+		}
+		for _, p := range []*string{&chain.Config, &chain.Output, &chain.Vars, &chain.Report, &chain.Spec, &chain.Patch, &chain.Airdrop} {
+			resolve(p)
+		}
+	}
+	if len(manifest.Chains) == 0 {
+		violations.add("chains", "manifest doesn't list any chains")
+	}
+	if len(violations) > 0 {
+		return nil, violations
+	}
+	return manifest, nil
+}
+
+// runGenBatchCommand generates all chains listed in the manifest, artifacts and simulation cache are loaded
+// once and shared across them
+func runGenBatchCommand(args []string) error {
+	flags := flag.NewFlagSet("gen batch", flag.ContinueOnError)
+	only := flags.String("only", "", "comma separated names of chains to generate (default is all)")
+	allowUnchecksummed := flags.Bool("allow-unchecksummed", false, "accept all lower case addresses in configs without EIP-55 checksum")
+	dryRun := flags.Bool("dry-run", false, "validate and simulate, print deployments and funded accounts without writing files")
+	parallel := flags.Int("parallel", 0, "max number of independent system contracts simulated concurrently (0 is number of CPUs, 1 disables it)")
+	applyLoggingFlags := registerLoggingFlags(flags)
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if err := applyLoggingFlags(); err != nil {
+		return err
+	}
+	if flags.NArg() != 1 {
+		return fmt.Errorf("usage: gen batch [--only <names>] [--dry-run] <manifest.yaml>")
+	}
+	manifest, err := readBatchManifest(flags.Arg(0))
+	if err != nil {
+		return err
+	}
+	selected := make(map[string]bool)
+	for _, name := range strings.Split(*only, ",") {
+		if name = strings.TrimSpace(name); name != "" {
+			selected[name] = true
+		}
+	}
+	artifacts, err := loadArtifactSet(manifest.Artifacts)
+	if err != nil {
+		return err
+	}
+	storageLayouts, err := loadStorageLayouts(artifacts, manifest.Artifacts)
+	if err != nil {
+		return err
+	}
+	var failures batchFailures
+	for _, chain := range manifest.Chains {
+		if len(selected) > 0 && !selected[chain.Name] {
+			continue
+		}
+		delete(selected, chain.Name)
+		log.Info("Generating chain", "name", chain.Name, "config", chain.Config, "output", chain.Output)
+		options := &generateOptions{
+			Artifacts:      artifacts,
+			SpecFile:       chain.Spec,
+			ReportFile:     chain.Report,
+			Format:         firstNonEmpty(chain.Format, manifest.Format),
+			OutputFormat:   firstNonEmpty(chain.OutputFormat, manifest.OutputFormat),
+			PatchFile:      chain.Patch,
+			AirdropFile:    chain.Airdrop,
+			DryRun:         *dryRun,
+			Parallelism:    *parallel,
+			CacheDir:       manifest.CacheDir,
+			StorageLayouts: storageLayouts,
+		}
+		if err := generateBatchChain(chain, *allowUnchecksummed, options); err != nil {
+			failures = append(failures, fmt.Sprintf("%s: %s", chain.Name, err))
+		}
+	}
+	var unknown []string
+	for name := range selected {
+		unknown = append(unknown, name)
+	}
+	sort.Strings(unknown)
+	for _, name := range unknown {
+		failures = append(failures, fmt.Sprintf("%s: chain is not listed in the manifest", name))
+	}
+	if len(failures) > 0 {
+		return failures
+	}
+	return nil
+}
+
+func generateBatchChain(chain batchChain, allowUnchecksummed bool, options *generateOptions) error {
+	vars, err := readConfigVariables(chain.Vars)
+	if err != nil {
+		return err
+	}
+	config, err := readGenesisConfig(chain.Config, allowUnchecksummed, vars)
+	if err != nil {
+		return err
+	}
+	if chain.Output != "stdout" && chain.Output != "stderr" && !options.DryRun {
+		if err := os.MkdirAll(filepath.Dir(chain.Output), 0755); err != nil {
+			return err
+		}
+	}
+	if err := createGenesisConfig(*config, chain.Output, options); err != nil || options.DryRun {
+		return err
+	}
+	template, err := os.ReadFile(chain.Config)
+	if err != nil {
+		return err
+	}
+	rawConfig, err := expandConfigVariables(template, vars)
+	if err != nil {
+		return err
+	}
+	return writeGenesisAttestation(chain.Output, newGenesisLock(rawConfig, options.Artifacts, map[string]string{"batch": chain.Name}))
+}
+
+func firstNonEmpty(values ...string) string {
+	for _, value := range values {
+		if value != "" {
+			return value
+		}
+	}
+	return ""
+}
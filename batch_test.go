@@ -0,0 +1,43 @@
+package main
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestReadBatchManifest(t *testing.T) {
+	dir := t.TempDir()
+	path := filepath.Join(dir, "manifest.yaml")
+	manifest := `artifacts: build/contracts
+chains:
+  - name: devnet
+    config: configs/devnet.json
+    output: out/devnet.json
+  - name: mainnet
+    config: /etc/bas/mainnet.json
+    output: stdout
+`
+	if err := os.WriteFile(path, []byte(manifest), 0644); err != nil {
+		t.Fatal(err)
+	}
+	result, err := readBatchManifest(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if result.Artifacts != filepath.Join(dir, "build/contracts") || result.Chains[0].Output != filepath.Join(dir, "out/devnet.json") {
+		t.Fatalf("relative paths aren't resolved against the manifest: %+v", result)
+	}
+	if result.Chains[1].Config != "/etc/bas/mainnet.json" || result.Chains[1].Output != "stdout" {
+		t.Fatalf("absolute paths are changed: %+v", result.Chains[1])
+	}
+	invalid := "chains:\n  - name: devnet\n    config: a.json\n  - name: devnet\n    config: b.json\n    output: b-genesis.json\n"
+	if err := os.WriteFile(path, []byte(invalid), 0644); err != nil {
+		t.Fatal(err)
+	}
+	var violations configViolations
+	if _, err := readBatchManifest(path); !errors.As(err, &violations) || len(violations) != 2 {
+		t.Fatalf("expected missing output and duplicate name, got %v", err)
+	}
+}
@@ -0,0 +1,73 @@
+package main
+
+import (
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/rlp"
+)
+
+// mix hash besu expects in blocks of IBFT 2.0 and QBFT chains ("ctical byzantine fault tolerance")
+var bftMixHash = common.HexToHash("0x63746963616c2062797a616e74696e65206661756c7420746f6c6572616e6365")
+
+// bftExtraData is RLP encoded extra data of besu BFT engines, vote, round and seals are empty in genesis, but
+// IBFT 2.0 and QBFT encode them differently
+type bftExtraData struct {
+	Vanity     []byte
+	Validators []common.Address
+	Vote       rlp.RawValue
+	Round      rlp.RawValue
+	Seals      [][]byte
+}
+
+func encodeBftExtraData(encoding string, vanityLength int, vanity []byte, validators []validatorConfig) ([]byte, error) {
+	extra := &bftExtraData{Vanity: make([]byte, vanityLength), Validators: []common.Address{}, Seals: [][]byte{}}
+	copy(extra.Vanity, vanity)
+	switch encoding {
+	case consensusIbft2:
+		// no vote is empty string, round is 4 bytes integer
+		extra.Vote, extra.Round = rlp.RawValue{0x80}, rlp.RawValue{0x84, 0, 0, 0, 0}
+	case consensusQbft:
+		// no vote is empty list, round is scalar
+		extra.Vote, extra.Round = rlp.RawValue{0xc0}, rlp.RawValue{0x80}
+	default:
+		return nil, fmt.Errorf("unknown extra data encoding: %s", encoding)
+	}
+	for _, v := range validators {
+		extra.Validators = append(extra.Validators, v.Address)
+	}
+	return rlp.EncodeToBytes(extra)
+}
+
+// decodeBftValidators returns validator set of RLP encoded extra data
+func decodeBftValidators(extra []byte) ([]common.Address, error) {
+	result := &bftExtraData{}
+	if err := rlp.DecodeBytes(extra, result); err != nil {
+		return nil, fmt.Errorf("invalid BFT extra data: %w", err)
+	}
+	return result.Validators, nil
+}
+
+// besuBftConfig is the engine section of besu genesis config
+func besuBftConfig(config *consensusConfig, period, epoch uint64) orderedObject {
+	result := orderedObject{
+		{Key: "blockperiodseconds", Value: period},
+		{Key: "epochlength", Value: epoch},
+	}
+	if config.RequestTimeoutSeconds > 0 {
+		result = append(result, orderedField{Key: "requesttimeoutseconds", Value: config.RequestTimeoutSeconds})
+	}
+	return result
+}
+
+// withBesuBftConfig adds engine section into config of the besu genesis, geth chain config doesn't have fields for
+// besu engines, so it's done after formatting
+func withBesuBftConfig(formatted interface{}, config *consensusConfig, period, epoch uint64) interface{} {
+	result := formatted.(orderedObject)
+	for i, field := range result {
+		if field.Key == "config" {
+			result[i].Value = append(field.Value.(orderedObject), orderedField{Key: config.engine(), Value: besuBftConfig(config, period, epoch)})
+		}
+	}
+	return result
+}
@@ -0,0 +1,70 @@
+package main
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+func TestBftExtraData(t *testing.T) {
+	validators := []validatorConfig{{Address: common.HexToAddress("0x9811ebc35d7b06b3fa8dc5809a1f9c52751e1deb")}}
+	// extra data of single validator genesis as encoded by besu rlp encode
+	expected := map[string]string{
+		consensusIbft2: "0xf83ea00000000000000000000000000000000000000000000000000000000000000000d5949811ebc35d7b06b3fa8dc5809a1f9c52751e1deb808400000000c0",
+		consensusQbft:  "0xf83aa00000000000000000000000000000000000000000000000000000000000000000d5949811ebc35d7b06b3fa8dc5809a1f9c52751e1debc080c0",
+	}
+	for layout, extra := range expected {
+		result, err := createExtraData(&extraDataConfig{Layout: layout}, validators)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if hexutil.Encode(result) != extra {
+			t.Fatalf("unexpected %s extra data: have %x, want %s", layout, result, extra)
+		}
+		if err := verifyExtraData(&extraDataConfig{Layout: layout}, result, validators); err != nil {
+			t.Fatal(err)
+		}
+	}
+}
+
+func TestBesuBftGenesis(t *testing.T) {
+	config := devNetConfig
+	config.Consensus = &consensusConfig{Engine: consensusQbft, RequestTimeoutSeconds: 4}
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err == nil {
+		t.Fatal("qbft genesis is written in geth format")
+	}
+	options := defaultGenerateOptions()
+	options.Format = "besu"
+	if err := createGenesisConfig(config, outputFile, options); err != nil {
+		t.Fatal(err)
+	}
+	data, err := os.ReadFile(outputFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	genesis := &struct {
+		Config struct {
+			Qbft map[string]uint64 `json:"qbft"`
+		} `json:"config"`
+		ExtraData hexutil.Bytes `json:"extraData"`
+		MixHash   common.Hash   `json:"mixHash"`
+	}{}
+	if err := json.Unmarshal(data, genesis); err != nil {
+		t.Fatal(err)
+	}
+	if qbft := genesis.Config.Qbft; qbft["blockperiodseconds"] != 3 || qbft["epochlength"] != 1200 || qbft["requesttimeoutseconds"] != 4 {
+		t.Fatalf("unexpected qbft config: %v", qbft)
+	}
+	validators, err := decodeBftValidators(genesis.ExtraData)
+	if err != nil || len(validators) != len(config.Validators) {
+		t.Fatalf("unexpected validators in extra data: %v (%v)", validators, err)
+	}
+	if genesis.MixHash != bftMixHash {
+		t.Fatalf("unexpected mix hash: %s", genesis.MixHash.Hex())
+	}
+}
@@ -0,0 +1,121 @@
+package main
+
+import (
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/crypto/bls12381"
+	"golang.org/x/crypto/scrypt"
+)
+
+// blsPublicKeyLength is the length of compressed BLS12-381 G1 point (min-pk scheme used for fast finality votes)
+const blsPublicKeyLength = 48
+
+// verifyBlsPublicKey checks that the key is a compressed G1 point of the prime order subgroup, keys outside of
+// the subgroup allow rogue key attacks on aggregated vote signatures
+func verifyBlsPublicKey(key []byte) error {
+	if len(key) != blsPublicKeyLength {
+		return fmt.Errorf("BLS public key must be %d bytes, got %d", blsPublicKeyLength, len(key))
+	}
+	g1 := bls12381.NewG1()
+	point, err := g1.FromCompressed(key)
+	if err != nil {
+		return fmt.Errorf("invalid BLS public key: %w", err)
+	}
+	if g1.IsZero(point) {
+		return fmt.Errorf("BLS public key is the point at infinity")
+	}
+	if !g1.InCorrectSubgroup(point) {
+		return fmt.Errorf("BLS public key is not in the G1 subgroup")
+	}
+	return nil
+}
+
+// generateBlsKey returns random secret key and its compressed public key, 48 random bytes are reduced modulo
+// group order, so the bias of the reduction is negligible
+func generateBlsKey() (*big.Int, []byte, error) {
+	g1 := bls12381.NewG1()
+	seed := make([]byte, 48)
+	secret := new(big.Int)
+	for secret.Sign() == 0 {
+		if _, err := rand.Read(seed); err != nil {
+			return nil, nil, err
+		}
+		secret.Mod(new(big.Int).SetBytes(seed), g1.Q())
+	}
+	return secret, g1.ToCompressed(g1.MulScalar(g1.New(), g1.One(), secret)), nil
+}
+
+// blsKeystore is EIP-2335 keystore with scrypt KDF, the format of BLS vote key wallets used by parlia clients
+type blsKeystore struct {
+	Crypto      blsKeystoreCrypto `json:"crypto"`
+	Description string            `json:"description"`
+	Pubkey      string            `json:"pubkey"`
+	Path        string            `json:"path"`
+	Uuid        string            `json:"uuid"`
+	Version     int               `json:"version"`
+}
+
+type blsKeystoreCrypto struct {
+	Kdf      blsKeystoreModule `json:"kdf"`
+	Checksum blsKeystoreModule `json:"checksum"`
+	Cipher   blsKeystoreModule `json:"cipher"`
+}
+
+type blsKeystoreModule struct {
+	Function string                 `json:"function"`
+	Params   map[string]interface{} `json:"params"`
+	Message  string                 `json:"message"`
+}
+
+// encryptBlsKey encrypts 32 bytes of the secret key with the password, password isn't NFKD normalized, so
+// it must be ASCII to be compatible with other EIP-2335 implementations
+func encryptBlsKey(secret *big.Int, publicKey []byte, password string, scryptN int) (*blsKeystore, error) {
+	salt, iv := make([]byte, 32), make([]byte, 16)
+	if _, err := rand.Read(salt); err != nil {
+		return nil, err
+	}
+	if _, err := rand.Read(iv); err != nil {
+		return nil, err
+	}
+	decryptionKey, err := scrypt.Key([]byte(password), salt, scryptN, 8, 1, 32)
+	if err != nil {
+		return nil, err
+	}
+	block, err := aes.NewCipher(decryptionKey[:16])
+	if err != nil {
+		return nil, err
+	}
+	cipherText := make([]byte, 32)
+	cipher.NewCTR(block, iv).XORKeyStream(cipherText, common.LeftPadBytes(secret.Bytes(), 32))
+	checksum := sha256.Sum256(append(append([]byte{}, decryptionKey[16:]...), cipherText...))
+	uuid := make([]byte, 16)
+	if _, err := rand.Read(uuid); err != nil {
+		return nil, err
+	}
+	// random (version 4) uuid
+	uuid[6], uuid[8] = uuid[6]&0x0f|0x40, uuid[8]&0x3f|0x80
+	return &blsKeystore{
+		Crypto: blsKeystoreCrypto{
+			Kdf: blsKeystoreModule{
+				Function: "scrypt",
+				Params:   map[string]interface{}{"dklen": 32, "n": scryptN, "r": 8, "p": 1, "salt": hex.EncodeToString(salt)},
+			},
+			Checksum: blsKeystoreModule{Function: "sha256", Params: map[string]interface{}{}, Message: hex.EncodeToString(checksum[:])},
+			Cipher: blsKeystoreModule{
+				Function: "aes-128-ctr",
+				Params:   map[string]interface{}{"iv": hex.EncodeToString(iv)},
+				Message:  hex.EncodeToString(cipherText),
+			},
+		},
+		Pubkey:  hex.EncodeToString(publicKey),
+		Uuid:    fmt.Sprintf("%x-%x-%x-%x-%x", uuid[0:4], uuid[4:6], uuid[6:8], uuid[8:10], uuid[10:]),
+		Version: 4,
+	}, nil
+}
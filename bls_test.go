@@ -0,0 +1,47 @@
+package main
+
+import (
+	"bytes"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// compressed generator of G1
+var testBlsPublicKey = hexutil.MustDecode("0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb")
+
+func TestVerifyBlsPublicKey(t *testing.T) {
+	if err := verifyBlsPublicKey(testBlsPublicKey); err != nil {
+		t.Fatalf("generator is rejected: %s", err)
+	}
+	infinity := make([]byte, blsPublicKeyLength)
+	infinity[0] = 0xc0
+	uncompressed := append([]byte{testBlsPublicKey[0] & 0x7f}, testBlsPublicKey[1:]...)
+	for name, key := range map[string][]byte{
+		"short":        testBlsPublicKey[:47],
+		"infinity":     infinity,
+		"uncompressed": uncompressed,
+	} {
+		if err := verifyBlsPublicKey(key); err == nil {
+			t.Fatalf("%s key is accepted", name)
+		}
+	}
+}
+
+func TestCreateExtraDataWithBlsKeys(t *testing.T) {
+	validator := validatorConfig{Address: common.HexToAddress("0x08fae3885e299c24ff9841478eb946f41023ac69"), BlsPublicKey: testBlsPublicKey}
+	extra, err := createExtraData(&extraDataConfig{Layout: "luban"}, []validatorConfig{validator})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(extra) != 32+1+20+48+65 {
+		t.Fatalf("wrong extra data length %d", len(extra))
+	}
+	if extra[32] != 1 || !bytes.Equal(extra[33:53], validator.Address.Bytes()) || !bytes.Equal(extra[53:101], testBlsPublicKey) {
+		t.Fatalf("wrong validator encoding %x", extra[32:101])
+	}
+	if _, err := createExtraData(&extraDataConfig{Layout: "luban"}, []validatorConfig{{Address: validator.Address}}); err == nil {
+		t.Fatalf("missing BLS key is accepted")
+	}
+}
@@ -0,0 +1,54 @@
+//go:build cshared
+// +build cshared
+
+package main
+
+/*
+#include <stdlib.h>
+*/
+import "C"
+
+import "unsafe"
+
+// C ABI of the c-shared build (make cshared), go build writes libbasgenesis.h with these declarations, so node
+// tooling and other languages can generate and inspect genesis without reimplementing the simulation
+
+// bas_genesis_generate builds genesis from NUL-terminated config JSON and stores malloc'ed NUL-terminated output
+// in out_buf: genesis JSON if 0 is returned, otherwise JSON error object and the result is its exit code
+//
+//export bas_genesis_generate
+func bas_genesis_generate(config_json *C.char, out_buf **C.char) C.int {
+	result, err := generateGenesisJson([]byte(C.GoString(config_json)))
+	return writeForeignResult(out_buf, result, err)
+}
+
+// bas_genesis_load_config validates config JSON and stores it as the generator sees it (migrated, durations
+// converted to blocks) in out_buf, the result is like of bas_genesis_generate
+//
+//export bas_genesis_load_config
+func bas_genesis_load_config(config_json *C.char, out_buf **C.char) C.int {
+	result, err := loadConfigJson([]byte(C.GoString(config_json)))
+	return writeForeignResult(out_buf, result, err)
+}
+
+// bas_genesis_inspect decodes storage of the system contract (name or address) in genesis JSON into a JSON array
+// of named variables, init of system contracts is executed first unless raw isn't 0
+//
+//export bas_genesis_inspect
+func bas_genesis_inspect(genesis_json *C.char, contract *C.char, raw C.int, out_buf **C.char) C.int {
+	result, err := inspectGenesisJson([]byte(C.GoString(genesis_json)), C.GoString(contract), raw != 0)
+	return writeForeignResult(out_buf, result, err)
+}
+
+// bas_genesis_free releases output of other functions
+//
+//export bas_genesis_free
+func bas_genesis_free(buf *C.char) {
+	C.free(unsafe.Pointer(buf))
+}
+
+func writeForeignResult(out_buf **C.char, result []byte, err error) C.int {
+	output, code := forForeignCaller(result, err)
+	*out_buf = C.CString(string(output))
+	return C.int(code)
+}
@@ -0,0 +1,187 @@
+package main
+
+import (
+	"encoding/json"
+	"flag"
+	"fmt"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core/vm"
+	"github.com/ethereum/go-ethereum/crypto"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+// runGenCallCommand executes a view call against alloc of the existing genesis file, so published genesis can
+// be inspected without running a node
+func runGenCallCommand(args []string) error {
+	flags := flag.NewFlagSet("gen call", flag.ContinueOnError)
+	genesisPath := flags.String("genesis", "", "path to genesis file")
+	to := flags.String("to", "", "address of the called contract or system contract name (e.g. staking)")
+	from := flags.String("from", "0x0000000000000000000000000000000000000000", "caller address")
+	sig := flags.String("sig", "", "function signature (e.g. getValidatorStatus(address))")
+	callArgs := flags.String("args", "", "comma separated arguments or JSON array (numbers can be decimal or hex)")
+	returns := flags.String("returns", "", "comma separated return types (default is taken from the artifact of the system contract)")
+	artifactsPath := flags.String("artifacts", "", "path to directory with contract artifacts (default is embedded)")
+	raw := flags.Bool("raw", false, "call the state as it is in genesis, without running init of system contracts")
+	jsonOutput := flags.Bool("json", false, "print result as JSON")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if *genesisPath == "" || *to == "" || *sig == "" || flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen call --genesis <genesis.json> --to <address|name> --sig <signature> [--args <values>] [--returns <types>] [--raw] [--json]")
+	}
+	if !common.IsHexAddress(*from) {
+		return fmt.Errorf("invalid caller address: %s", *from)
+	}
+	genesis, err := bgenesis.Load(*genesisPath)
+	if err != nil {
+		return err
+	}
+	name, address := "", common.Address{}
+	if common.IsHexAddress(*to) {
+		address = common.HexToAddress(*to)
+	}
+	if systemName, systemAddress, err := resolveSystemContract(*to); err == nil {
+		name, address = systemName, systemAddress
+	} else if !common.IsHexAddress(*to) {
+		return err
+	}
+	if _, ok := genesis.Alloc[address]; !ok {
+		return fmt.Errorf("there is no account %s in genesis", address.Hex())
+	}
+	methodName, inputs, err := parseFunctionSignature(*sig)
+	if err != nil {
+		return err
+	}
+	input, err := encodeAbiArguments(inputs, callArgumentsJson(*callArgs))
+	if err != nil {
+		return fmt.Errorf("invalid args: %w", err)
+	}
+	outputs, contractAbi, err := callOutputs(name, methodName, *returns, *artifactsPath)
+	if err != nil {
+		return err
+	}
+	statedb, err := newStateFromGenesis(genesis.Genesis)
+	if err != nil {
+		return err
+	}
+	evm := newGenesisEVM(genesis.Genesis, statedb)
+	// system contracts are initialized by consensus engine in the first block
+	if !*raw {
+		if err := initSystemContracts(evm); err != nil {
+			return err
+		}
+	}
+	selector := crypto.Keccak256([]byte(methodName))[:4:4]
+	result, _, err := evm.StaticCall(vm.AccountRef(common.HexToAddress(*from)), address, append(selector, input...), 10_000_000)
+	if err != nil {
+		return fmt.Errorf("call failed: %w (%s)", err, decodeRevert(result, contractAbi))
+	}
+	if outputs == nil {
+		fmt.Println(hexutil.Encode(result))
+		return nil
+	}
+	values, err := outputs.Unpack(result)
+	if err != nil {
+		return fmt.Errorf("failed to decode result %s: %w", hexutil.Encode(result), err)
+	}
+	if *jsonOutput {
+		data, _ := json.MarshalIndent(values, "", "  ")
+		fmt.Println(string(data))
+		return nil
+	}
+	for i, value := range values {
+		fmt.Printf("%s: %v\n", outputs[i].Type, value)
+	}
+	return nil
+}
+
+// parseFunctionSignature returns canonical signature and input arguments of the function, tuples aren't supported
+func parseFunctionSignature(signature string) (string, abi.Arguments, error) {
+	signature = strings.ReplaceAll(signature, " ", "")
+	open := strings.Index(signature, "(")
+	if open <= 0 || !strings.HasSuffix(signature, ")") {
+		return "", nil, fmt.Errorf("invalid function signature %q, expected name(type1,type2)", signature)
+	}
+	typeNames := splitTypeList(signature[open+1 : len(signature)-1])
+	inputs, err := parseTypeList(typeNames)
+	if err != nil {
+		return "", nil, err
+	}
+	return signature, inputs, nil
+}
+
+func splitTypeList(list string) []string {
+	if list == "" {
+		return nil
+	}
+	return strings.Split(list, ",")
+}
+
+func parseTypeList(typeNames []string) (abi.Arguments, error) {
+	var result abi.Arguments
+	for i, typeName := range typeNames {
+		if strings.Contains(typeName, "(") {
+			return nil, fmt.Errorf("tuple type %s isn't supported", typeName)
+		}
+		abiType, err := abi.NewType(typeName, "", nil)
+		if err != nil {
+			return nil, fmt.Errorf("invalid type %s: %w", typeName, err)
+		}
+		result = append(result, abi.Argument{Name: fmt.Sprintf("%d", i), Type: abiType})
+	}
+	return result, nil
+}
+
+// callArgumentsJson turns command line arguments into JSON array accepted by encodeAbiArguments, values that
+// aren't valid JSON (addresses, hex numbers) are passed as strings
+func callArgumentsJson(args string) json.RawMessage {
+	args = strings.TrimSpace(args)
+	if strings.HasPrefix(args, "[") {
+		return json.RawMessage(args)
+	}
+	var items []json.RawMessage
+	for _, arg := range splitTypeList(args) {
+		if arg = strings.TrimSpace(arg); json.Valid([]byte(arg)) {
+			items = append(items, json.RawMessage(arg))
+			continue
+		}
+		item, _ := json.Marshal(arg)
+		items = append(items, item)
+	}
+	result, _ := json.Marshal(items)
+	return result
+}
+
+// callOutputs returns return types of the call, they are taken from the flag or from the ABI of the system contract
+func callOutputs(contractName, signature, returns, artifactsPath string) (abi.Arguments, *abi.ABI, error) {
+	var contractAbi *abi.ABI
+	if contractName != "" {
+		artifacts, err := loadArtifactSet(artifactsPath)
+		if err != nil {
+			return nil, nil, err
+		}
+		artifact := &artifactData{}
+		if err := json.Unmarshal(artifacts[contractName], artifact); err != nil {
+			return nil, nil, err
+		}
+		contractAbi = parseArtifactAbi(artifact)
+	}
+	if returns != "" {
+		outputs, err := parseTypeList(splitTypeList(strings.ReplaceAll(returns, " ", "")))
+		return outputs, contractAbi, err
+	}
+	if contractAbi == nil {
+		// result is printed as raw hex
+		return nil, nil, nil
+	}
+	method, err := findAbiMethod(contractAbi, signature)
+	if err != nil {
+		return nil, nil, err
+	}
+	return method.Outputs, contractAbi, nil
+}
@@ -0,0 +1,29 @@
+package main
+
+import (
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestEncodeCallArguments(t *testing.T) {
+	signature, inputs, err := parseFunctionSignature("getDelegatedAmount(address, uint256)")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if signature != "getDelegatedAmount(address,uint256)" || len(inputs) != 2 {
+		t.Fatalf("unexpected signature %s (%d inputs)", signature, len(inputs))
+	}
+	input, err := encodeAbiArguments(inputs, callArgumentsJson("0x08fae3885e299c24ff9841478eb946f41023ac69, 0x10"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	expected, _ := newArguments("address", "uint256").Pack(common.HexToAddress("0x08fae3885e299c24ff9841478eb946f41023ac69"), big.NewInt(16))
+	if string(input) != string(expected) {
+		t.Fatalf("unexpected encoding %x", input)
+	}
+	if _, _, err := parseFunctionSignature("getValidators"); err == nil {
+		t.Fatal("signature without argument list is accepted")
+	}
+}
@@ -0,0 +1,32 @@
+package main
+
+import (
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/core/vm"
+)
+
+// max integer that JS numbers represent exactly, bigger chain ids break web3 tooling and wallets
+const maxSafeChainId = 1<<53 - 1
+
+// verifyChainId makes sure that chain id from the config is the one used by chain config and simulated EVM
+func verifyChainId(config genesisConfig, genesis *core.Genesis, evm *vm.EVM) error {
+	expected := big.NewInt(config.ChainId)
+	if genesis.Config.ChainID == nil || genesis.Config.ChainID.Cmp(expected) != 0 {
+		return fmt.Errorf("chain config has chain id %s, but %s is expected", genesis.Config.ChainID, expected)
+	}
+	if chainId := evm.ChainConfig().ChainID; chainId == nil || chainId.Cmp(expected) != 0 {
+		return fmt.Errorf("simulated EVM uses chain id %s, but %s is expected", chainId, expected)
+	}
+	return nil
+}
+
+// chainIdWarnings returns non-fatal problems of the chain id
+func chainIdWarnings(chainId int64) []string {
+	if chainId > maxSafeChainId {
+		return []string{fmt.Sprintf("chain id %d exceeds 2^53-1, JS tooling can't represent it exactly", chainId)}
+	}
+	return nil
+}
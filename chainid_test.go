@@ -0,0 +1,46 @@
+package main
+
+import (
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core/vm"
+)
+
+func TestSimulatedConstructorChainId(t *testing.T) {
+	config := localNetConfig
+	config.ChainId = 4321
+	genesis := defaultGenesisConfig(config.ChainId)
+	simulator, err := newSystemContractSimulator(genesis)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := verifyChainId(config, genesis, simulator.evm); err != nil {
+		t.Fatal(err)
+	}
+	// CHAINID PUSH1 0 SSTORE STOP
+	constructor := hexutil.MustDecode("0x4660005500")
+	address := common.HexToAddress("0x0000000000000000000000000000000000001234")
+	if _, _, err := simulator.evm.CreateWithAddress(vm.AccountRef(common.Address{}), constructor, 1_000_000, big.NewInt(0), address); err != nil {
+		t.Fatal(err)
+	}
+	if chainId := simulator.statedb.GetState(address, common.Hash{}).Big(); chainId.Int64() != config.ChainId {
+		t.Fatalf("constructor sees chain id %s, want %d", chainId, config.ChainId)
+	}
+}
+
+func TestChainIdValidation(t *testing.T) {
+	config := localNetConfig
+	config.ChainId = 0
+	if err := config.validate(); err == nil {
+		t.Fatal("zero chain id must be rejected")
+	}
+	if warnings := chainIdWarnings(1 << 53); len(warnings) == 0 {
+		t.Fatal("chain id above 2^53-1 must produce a warning")
+	}
+	if warnings := chainIdWarnings(localNetConfig.ChainId); len(warnings) != 0 {
+		t.Fatalf("unexpected warnings: %v", warnings)
+	}
+}
@@ -0,0 +1,89 @@
+package main
+
+import (
+	"fmt"
+	"regexp"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+var addressPattern = regexp.MustCompile(`^0[xX][0-9a-fA-F]{40}$`)
+
+// checksumAddress is marshaled in EIP-55 form, common.Address is marshaled in lower case
+type checksumAddress common.Address
+
+func (a checksumAddress) Hex() string {
+	return common.Address(a).Hex()
+}
+
+func (a checksumAddress) MarshalText() ([]byte, error) {
+	return []byte(a.Hex()), nil
+}
+
+// checkConfigAddresses verifies EIP-55 checksum of all addresses in raw config (keys and values) and reports
+// addresses that are repeated in the same object or array, json.Unmarshal silently merges such entries
+func checkConfigAddresses(rawConfig []byte, allowUnchecksummed bool) error {
+	config, err := decodeOrderedJson(rawConfig)
+	if err != nil {
+		return err
+	}
+	var violations configViolations
+	checkAddress := func(path, value string) {
+		mixedCase := strings.ToLower(value[2:]) != value[2:] && strings.ToUpper(value[2:]) != value[2:]
+		if mixedCase && common.HexToAddress(value).Hex() != value {
+			violations.add(path, "invalid EIP-55 checksum of %s (expected %s)", value, common.HexToAddress(value).Hex())
+		} else if !mixedCase && !allowUnchecksummed {
+			violations.add(path, "address %s has no EIP-55 checksum, use %s (or --allow-unchecksummed)", value, common.HexToAddress(value).Hex())
+		}
+	}
+	var walk func(path string, value interface{})
+	walk = func(path string, value interface{}) {
+		seen := make(map[common.Address]string)
+		checkDuplicate := func(itemPath, value string) {
+			address := common.HexToAddress(value)
+			if prev, ok := seen[address]; ok {
+				violations.add(itemPath, "duplicates %s (%s)", prev, address.Hex())
+			}
+			seen[address] = itemPath
+		}
+		switch v := value.(type) {
+		case string:
+			if addressPattern.MatchString(v) {
+				checkAddress(path, v)
+			}
+		case orderedObject:
+			for _, field := range v {
+				fieldPath := joinConfigPath(path, field.Key)
+				if addressPattern.MatchString(field.Key) {
+					checkAddress(fieldPath, field.Key)
+					checkDuplicate(fieldPath, field.Key)
+				}
+				walk(fieldPath, field.Value)
+			}
+		case []interface{}:
+			for i, item := range v {
+				itemPath := fmt.Sprintf("%s[%d]", path, i)
+				if s, ok := item.(string); ok && addressPattern.MatchString(s) {
+					checkDuplicate(itemPath, s)
+				}
+				walk(itemPath, item)
+			}
+		}
+	}
+	walk("", config)
+	if len(violations) > 0 {
+		return violations
+	}
+	return nil
+}
+
+func joinConfigPath(path, key string) string {
+	if addressPattern.MatchString(key) {
+		return fmt.Sprintf("%s[%s]", path, key)
+	}
+	if path == "" {
+		return key
+	}
+	return path + "." + key
+}
@@ -0,0 +1,48 @@
+package main
+
+import (
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestCheckConfigAddresses(t *testing.T) {
+	// EIP-55 test vectors
+	checksummed, other := "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+	badChecksum := "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+	lower, upper := strings.ToLower(checksummed), "0x"+strings.ToUpper(checksummed[2:])
+	for _, test := range []struct {
+		config             string
+		allowUnchecksummed bool
+		// paths of expected violations
+		violations []string
+	}{
+		{`{"deployers": ["` + checksummed + `", "` + other + `"]}`, false, nil},
+		{`{"deployers": ["` + lower + `"]}`, false, []string{"deployers[0]"}},
+		{`{"deployers": ["` + upper + `"]}`, false, []string{"deployers[0]"}},
+		{`{"deployers": ["` + lower + `", "` + upper + `"]}`, true, []string{"deployers[1]"}},
+		// wrong checksum is rejected even if unchecksummed addresses are allowed
+		{`{"deployers": ["` + badChecksum + `"]}`, true, []string{"deployers[0]"}},
+		{`{"initialStakes": {"` + badChecksum + `": "1"}}`, true, []string{"initialStakes[" + badChecksum + "]"}},
+		{`{"faucet": {"` + checksummed + `": "1", "` + lower + `": "2"}}`, true, []string{"faucet[" + lower + "]"}},
+		{`{"safe": {"owners": ["` + other + `"], "fallback": "` + badChecksum + `"}}`, false, []string{"safe.fallback"}},
+	} {
+		err := checkConfigAddresses([]byte(test.config), test.allowUnchecksummed)
+		var violations configViolations
+		if len(test.violations) == 0 {
+			if err != nil {
+				t.Errorf("%s: unexpected error %s", test.config, err)
+			}
+			continue
+		}
+		if !errors.As(err, &violations) || len(violations) != len(test.violations) {
+			t.Errorf("%s: expected violations %v, got %v", test.config, test.violations, err)
+			continue
+		}
+		for i, path := range test.violations {
+			if violations[i].Path != path {
+				t.Errorf("%s: expected violation of %s, got %s", test.config, path, violations[i].Path)
+			}
+		}
+	}
+}
@@ -0,0 +1,26 @@
+package main
+
+import (
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/params"
+)
+
+// checkCodeSizes returns problems for accounts with code bigger than the limit (EIP-170 by default), node
+// refuses such accounts, so they are errors unless oversize is explicitly allowed
+func checkCodeSizes(genesis *core.Genesis, limit int, allowOversize bool) (warnings []string, err error) {
+	if limit <= 0 {
+		limit = params.MaxCodeSize
+	}
+	var problems []string
+	for _, address := range sortedAlloc(genesis) {
+		if size := len(genesis.Alloc[address].Code); size > limit {
+			problems = append(problems, fmt.Sprintf("code of %s is %d bytes, limit is %d bytes", address.Hex(), size, limit))
+		}
+	}
+	if len(problems) > 0 && !allowOversize {
+		return nil, fmt.Errorf("%s (use --allow-oversize to ignore)", problems[0])
+	}
+	return problems, nil
+}
@@ -0,0 +1,137 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// clientConfig declares node software that is going to run the chain
+type clientConfig struct {
+	// one of the clients from compatibility matrix (bsc-geth, bas-geth)
+	Name    string `json:"name"`
+	Version string `json:"version"`
+}
+
+// clientCompatibility maps genesis features to the minimal client release supporting them, feature that
+// is missing for the client isn't supported at all
+var clientCompatibility = map[string]map[string]string{
+	"bsc-geth": {
+		"parlia":          "1.0.0",
+		"clique":          "1.0.0",
+		"ethash":          "1.0.0",
+		"ramanujanBlock":  "1.0.6",
+		"nielsBlock":      "1.0.6",
+		"mirrorSyncBlock": "1.1.0",
+		"brunoBlock":      "1.1.5",
+	},
+	"bas-geth": {
+		"parlia":              "1.0.0",
+		"clique":              "1.0.0",
+		"ethash":              "1.0.0",
+		"ramanujanBlock":      "1.0.0",
+		"nielsBlock":          "1.0.0",
+		"mirrorSyncBlock":     "1.0.0",
+		"brunoBlock":          "1.0.0",
+		"runtimeUpgradeBlock": "1.1.0",
+		"customExtraData":     "1.1.0",
+	},
+}
+
+// requiredClientFeatures lists features of the genesis that node must support
+func requiredClientFeatures(genesis *core.Genesis, config genesisConfig) []string {
+	var result []string
+	chainConfig := genesis.Config
+	if chainConfig.Parlia != nil {
+		result = append(result, "parlia")
+	}
+	if chainConfig.Clique != nil {
+		result = append(result, "clique")
+	}
+	if chainConfig.Ethash != nil {
+		result = append(result, "ethash")
+	}
+	if chainConfig.RamanujanBlock != nil {
+		result = append(result, "ramanujanBlock")
+	}
+	if chainConfig.NielsBlock != nil {
+		result = append(result, "nielsBlock")
+	}
+	if chainConfig.MirrorSyncBlock != nil {
+		result = append(result, "mirrorSyncBlock")
+	}
+	if chainConfig.BrunoBlock != nil {
+		result = append(result, "brunoBlock")
+	}
+	if chainConfig.RuntimeUpgradeBlock != nil {
+		result = append(result, "runtimeUpgradeBlock")
+	}
+	if chainConfig.Parlia != nil && config.ExtraData != nil && config.ExtraData.Layout != "" && config.ExtraData.Layout != "parlia" {
+		result = append(result, "customExtraData")
+	}
+	return result
+}
+
+// checkClientCompatibility returns warnings for the features that declared client can't handle
+func checkClientCompatibility(genesis *core.Genesis, config genesisConfig) ([]string, error) {
+	if config.Client == nil {
+		return nil, nil
+	}
+	matrix, ok := clientCompatibility[config.Client.Name]
+	if !ok {
+		return nil, fmt.Errorf("unknown client: %s", config.Client.Name)
+	}
+	declared, err := parseVersion(config.Client.Version)
+	if err != nil {
+		return nil, err
+	}
+	var warnings []string
+	for _, feature := range requiredClientFeatures(genesis, config) {
+		minVersion, ok := matrix[feature]
+		if !ok {
+			warnings = append(warnings, fmt.Sprintf("%s doesn't support %s", config.Client.Name, feature))
+			continue
+		}
+		if required, _ := parseVersion(minVersion); compareVersions(declared, required) < 0 {
+			warnings = append(warnings, fmt.Sprintf("%s requires %s %s or newer, but %s is declared", feature, config.Client.Name, minVersion, config.Client.Version))
+		}
+	}
+	return warnings, nil
+}
+
+func parseVersion(version string) ([]int, error) {
+	var result []int
+	for _, part := range strings.Split(strings.TrimPrefix(version, "v"), ".") {
+		// ignore suffixes like "-beta"
+		if i := strings.IndexByte(part, '-'); i >= 0 {
+			part = part[:i]
+		}
+		number, err := strconv.Atoi(part)
+		if err != nil {
+			return nil, fmt.Errorf("invalid client version: %s", version)
+		}
+		result = append(result, number)
+	}
+	return result, nil
+}
+
+func compareVersions(a, b []int) int {
+	for i := 0; i < len(a) || i < len(b); i++ {
+		var x, y int
+		if i < len(a) {
+			x = a[i]
+		}
+		if i < len(b) {
+			y = b[i]
+		}
+		if x != y {
+			if x < y {
+				return -1
+			}
+			return 1
+		}
+	}
+	return 0
+}
@@ -0,0 +1,267 @@
+//go:build compile
+// +build compile
+
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"io/fs"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// compiler settings of truffle-config.js, artifacts built with them match the embedded ones
+const (
+	defaultSolcVersion   = "0.8.11"
+	defaultOptimizerRuns = 100
+)
+
+type solcSource struct {
+	Content string `json:"content"`
+}
+
+type solcSettings struct {
+	Optimizer struct {
+		Enabled bool `json:"enabled"`
+		Runs    int  `json:"runs"`
+	} `json:"optimizer"`
+	EvmVersion      string                         `json:"evmVersion,omitempty"`
+	OutputSelection map[string]map[string][]string `json:"outputSelection"`
+}
+
+type solcInput struct {
+	Language string                `json:"language"`
+	Sources  map[string]solcSource `json:"sources"`
+	Settings solcSettings          `json:"settings"`
+}
+
+type solcContractOutput struct {
+	Abi json.RawMessage `json:"abi"`
+	Evm struct {
+		Bytecode struct {
+			Object string `json:"object"`
+		} `json:"bytecode"`
+		DeployedBytecode struct {
+			Object              string                          `json:"object"`
+			ImmutableReferences map[string][]immutableReference `json:"immutableReferences"`
+		} `json:"deployedBytecode"`
+	} `json:"evm"`
+	StorageLayout *storageLayout `json:"storageLayout"`
+}
+
+type solcOutput struct {
+	Errors []struct {
+		Severity         string `json:"severity"`
+		FormattedMessage string `json:"formattedMessage"`
+	} `json:"errors"`
+	Contracts map[string]map[string]*solcContractOutput `json:"contracts"`
+}
+
+// compiledArtifact has the fields of truffle artifacts the generator reads, plus exact compiler settings
+type compiledArtifact struct {
+	ContractName     string          `json:"contractName"`
+	SourceName       string          `json:"sourceName"`
+	Abi              json.RawMessage `json:"abi"`
+	Bytecode         string          `json:"bytecode"`
+	DeployedBytecode string          `json:"deployedBytecode"`
+	// truffle layout, code verification ignores these ranges of deployed bytecode
+	ImmutableReferences map[string][]immutableReference `json:"immutableReferences,omitempty"`
+	StorageLayout       *storageLayout                  `json:"storageLayout,omitempty"`
+	Compiler            struct {
+		Name     string       `json:"name"`
+		Version  string       `json:"version"`
+		Settings solcSettings `json:"settings"`
+	} `json:"compiler"`
+}
+
+// newSolcInput reads all .sol files of the source directory (tests excluded), sources are keyed by path relative
+// to the project root, so imports resolve the same way as in truffle
+func newSolcInput(root, sourceDir string, optimizerRuns int, evmVersion string) (*solcInput, error) {
+	input := &solcInput{Language: "Solidity", Sources: make(map[string]solcSource)}
+	err := filepath.WalkDir(sourceDir, func(path string, entry fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if entry.IsDir() {
+			if entry.Name() == "tests" || entry.Name() == "node_modules" {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if filepath.Ext(path) != ".sol" {
+			return nil
+		}
+		content, err := os.ReadFile(path)
+		if err != nil {
+			return err
+		}
+		name, err := filepath.Rel(root, path)
+		if err != nil {
+			return err
+		}
+		input.Sources[filepath.ToSlash(name)] = solcSource{Content: string(content)}
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	if len(input.Sources) == 0 {
+		return nil, fmt.Errorf("there are no .sol files in %s", sourceDir)
+	}
+	input.Settings.Optimizer.Enabled = optimizerRuns > 0
+	input.Settings.Optimizer.Runs = optimizerRuns
+	input.Settings.EvmVersion = evmVersion
+	input.Settings.OutputSelection = map[string]map[string][]string{
+		"*": {"*": {"abi", "evm.bytecode.object", "evm.deployedBytecode.object", "evm.deployedBytecode.immutableReferences", "storageLayout"}},
+	}
+	return input, nil
+}
+
+// findSolc returns path to the solc binary of the version, svm keeps every version in its own directory
+func findSolc(solcPath, version string) string {
+	if solcPath != "" {
+		return solcPath
+	}
+	if home, err := os.UserHomeDir(); err == nil {
+		svmSolc := filepath.Join(home, ".svm", version, "solc-"+version)
+		if _, err := os.Stat(svmSolc); err == nil {
+			return svmSolc
+		}
+	}
+	return "solc"
+}
+
+// solcVersion returns full version of the compiler (e.g. 0.8.11+commit.d7f03943.Linux.g++)
+func solcVersion(solc string) (string, error) {
+	output, err := exec.Command(solc, "--version").Output()
+	if err != nil {
+		return "", fmt.Errorf("failed to run %s: %w", solc, err)
+	}
+	for _, line := range strings.Split(string(output), "\n") {
+		if strings.HasPrefix(line, "Version: ") {
+			return strings.TrimSpace(strings.TrimPrefix(line, "Version: ")), nil
+		}
+	}
+	return "", fmt.Errorf("unexpected output of %s --version", solc)
+}
+
+func runSolc(solc, root string, input *solcInput) (*solcOutput, error) {
+	data, err := json.Marshal(input)
+	if err != nil {
+		return nil, err
+	}
+	command := exec.Command(solc, "--standard-json", "--base-path", root, "--include-path", filepath.Join(root, "node_modules"))
+	command.Stdin = bytes.NewReader(data)
+	command.Stderr = os.Stderr
+	stdout, err := command.Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to run %s: %w", solc, err)
+	}
+	output := &solcOutput{}
+	if err := json.Unmarshal(stdout, output); err != nil {
+		return nil, fmt.Errorf("invalid output of %s: %w", solc, err)
+	}
+	var errors []string
+	for _, compileError := range output.Errors {
+		if compileError.Severity == "error" {
+			errors = append(errors, compileError.FormattedMessage)
+		} else {
+			log.Warn("Compiler warning", "message", compileError.FormattedMessage)
+		}
+	}
+	if len(errors) > 0 {
+		return nil, fmt.Errorf("compilation failed:\n%s", strings.Join(errors, "\n"))
+	}
+	return output, nil
+}
+
+// runGenCompileCommand builds artifacts of system contracts with a pinned compiler, output directory can be passed
+// to gen --artifacts
+func runGenCompileCommand(args []string) error {
+	flags := flag.NewFlagSet("gen compile", flag.ContinueOnError)
+	root := flags.String("root", ".", "project root, imports are resolved against it and its node_modules")
+	sourceDir := flags.String("contracts", "contracts", "directory with contract sources (relative to the root)")
+	outputDir := flags.String("output", "build/contracts", "directory to write artifacts to")
+	solcPath := flags.String("solc", "", "path to solc binary (default is svm-managed ~/.svm/<version>/solc-<version> or solc on PATH)")
+	version := flags.String("solc-version", defaultSolcVersion, "required compiler version")
+	optimizerRuns := flags.Int("optimizer-runs", defaultOptimizerRuns, "optimizer runs, 0 disables optimizer")
+	evmVersion := flags.String("evm-version", "", "target EVM version (default is compiler's default)")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen compile [--contracts <dir>] [--output <dir>] [--solc <path>] [--solc-version <version>] [--optimizer-runs <runs>]")
+	}
+	solc := findSolc(*solcPath, *version)
+	fullVersion, err := solcVersion(solc)
+	if err != nil {
+		return err
+	}
+	// the same sources with another compiler produce another genesis, so version mismatch is an error
+	if !strings.HasPrefix(fullVersion, *version+"+") {
+		return fmt.Errorf("%s is solc %s, but %s is required (install it with: svm install %s)", solc, fullVersion, *version, *version)
+	}
+	input, err := newSolcInput(*root, filepath.Join(*root, *sourceDir), *optimizerRuns, *evmVersion)
+	if err != nil {
+		return err
+	}
+	phase := startPhase("compilation")
+	output, err := runSolc(solc, *root, input)
+	if err != nil {
+		return err
+	}
+	phase.finish()
+	if err := os.MkdirAll(*outputDir, 0755); err != nil {
+		return err
+	}
+	var sourceNames []string
+	for sourceName := range output.Contracts {
+		sourceNames = append(sourceNames, sourceName)
+	}
+	sort.Strings(sourceNames)
+	written := make(map[string]string)
+	for _, sourceName := range sourceNames {
+		for contractName, contract := range output.Contracts[sourceName] {
+			// interfaces and abstract contracts have nothing to deploy
+			if contract.Evm.Bytecode.Object == "" {
+				continue
+			}
+			if strings.Contains(contract.Evm.Bytecode.Object, "__$") {
+				return fmt.Errorf("%s in %s requires library linking, it's not supported", contractName, sourceName)
+			}
+			if previous, ok := written[contractName]; ok {
+				return fmt.Errorf("contract %s is defined in both %s and %s", contractName, previous, sourceName)
+			}
+			artifact := &compiledArtifact{
+				ContractName:        contractName,
+				SourceName:          sourceName,
+				Abi:                 contract.Abi,
+				Bytecode:            "0x" + contract.Evm.Bytecode.Object,
+				DeployedBytecode:    "0x" + contract.Evm.DeployedBytecode.Object,
+				ImmutableReferences: contract.Evm.DeployedBytecode.ImmutableReferences,
+				StorageLayout:       contract.StorageLayout,
+			}
+			artifact.Compiler.Name, artifact.Compiler.Version, artifact.Compiler.Settings = "solc", fullVersion, input.Settings
+			data, _ := json.MarshalIndent(artifact, "", "  ")
+			if err := os.WriteFile(filepath.Join(*outputDir, contractName+".json"), append(data, '\n'), 0644); err != nil {
+				return err
+			}
+			written[contractName] = sourceName
+		}
+	}
+	for _, name := range defaultArtifactSet().names() {
+		if _, ok := written[name]; !ok {
+			log.Warn("System contract is not compiled, embedded artifact will be used", "contract", name)
+		}
+	}
+	log.Info("Artifacts are written", "contracts", len(written), "solc", fullVersion, "output", *outputDir)
+	return nil
+}
@@ -0,0 +1,11 @@
+//go:build !compile
+// +build !compile
+
+package main
+
+import "fmt"
+
+// runGenCompileCommand is only available in builds that may shell out to solc (-tags compile)
+func runGenCompileCommand(args []string) error {
+	return fmt.Errorf("compile support is not compiled in, rebuild with: go build -tags compile")
+}
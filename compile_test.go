@@ -0,0 +1,27 @@
+//go:build compile
+// +build compile
+
+package main
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestNewSolcInput(t *testing.T) {
+	input, err := newSolcInput(".", "contracts", defaultOptimizerRuns, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, ok := input.Sources["contracts/Staking.sol"]; !ok {
+		t.Fatalf("staking source is missing")
+	}
+	for name := range input.Sources {
+		if strings.HasPrefix(name, "contracts/tests/") {
+			t.Fatalf("test contract %s is compiled", name)
+		}
+	}
+	if !input.Settings.Optimizer.Enabled || input.Settings.Optimizer.Runs != defaultOptimizerRuns {
+		t.Fatalf("unexpected optimizer settings %+v", input.Settings.Optimizer)
+	}
+}
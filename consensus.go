@@ -0,0 +1,114 @@
+package main
+
+import (
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/params"
+)
+
+// consensus engines the genesis can be generated for
+const (
+	consensusParlia = "parlia"
+	// proof of authority devnets, genesis validators are clique signers
+	consensusClique = "clique"
+	// single node test chains (e.g. geth with --fakepow), extra data doesn't contain validators
+	consensusEthashDev = "ethash-dev"
+	// besu BFT engines, geth doesn't run them, so genesis can be written only in besu format
+	consensusIbft2 = "ibft2"
+	consensusQbft  = "qbft"
+)
+
+var consensusEngines = []string{consensusParlia, consensusClique, consensusEthashDev, consensusIbft2, consensusQbft}
+
+// consensusConfig selects engine of the chain, system contracts are deployed for any of them, but only Parlia
+// nodes call their init functions and use them for validator set
+type consensusConfig struct {
+	// parlia (default), clique, ethash-dev, ibft2 or qbft
+	Engine string `json:"engine"`
+	// round timeout of ibft2 and qbft (default is the one of besu)
+	RequestTimeoutSeconds uint64 `json:"requestTimeoutSeconds,omitempty"`
+}
+
+func (c *consensusConfig) engine() string {
+	if c == nil || c.Engine == "" {
+		return consensusParlia
+	}
+	return c.Engine
+}
+
+func (c *consensusConfig) checkEngine() error {
+	for _, engine := range consensusEngines {
+		if c.engine() == engine {
+			return nil
+		}
+	}
+	return fmt.Errorf("unknown consensus engine %q, expected one of %v", c.engine(), consensusEngines)
+}
+
+// besuBft is true for engines implemented only by besu
+func (c *consensusConfig) besuBft() bool {
+	return c.engine() == consensusIbft2 || c.engine() == consensusQbft
+}
+
+// extraDataLayout is the layout used when config doesn't specify one
+func (c *consensusConfig) extraDataLayout() string {
+	switch c.engine() {
+	case consensusClique:
+		return "clique"
+	case consensusEthashDev:
+		return "ethash"
+	case consensusIbft2, consensusQbft:
+		return c.engine()
+	}
+	return "parlia"
+}
+
+// checkExtraDataLayout makes sure that the engine is able to parse extra data of the layout
+func (c *consensusConfig) checkExtraDataLayout(layout extraDataLayout) error {
+	if c.besuBft() && layout.Encoding != c.engine() {
+		return fmt.Errorf("%s requires RLP encoded extra data of %s layout", c.engine(), c.engine())
+	}
+	if !c.besuBft() && layout.Encoding != "" {
+		return fmt.Errorf("%s extra data can be used only by %s engine", layout.Encoding, layout.Encoding)
+	}
+	switch c.engine() {
+	case consensusClique:
+		if layout != extraDataLayouts["clique"] {
+			return fmt.Errorf("clique requires 32 bytes of vanity, signer addresses and 65 bytes of seal")
+		}
+	case consensusEthashDev:
+		if !layout.WithoutValidators || layout.VanityLength+layout.SealLength > int(params.MaximumExtraDataSize) {
+			return fmt.Errorf("ethash allows at most %d bytes of extra data without validators", params.MaximumExtraDataSize)
+		}
+	}
+	return nil
+}
+
+// applyConsensusEngine sets engine section of the chain config, block period and epoch come from consensus params
+func applyConsensusEngine(chainConfig *params.ChainConfig, config *consensusConfig, period, epoch uint64) error {
+	if err := config.checkEngine(); err != nil {
+		return err
+	}
+	chainConfig.Parlia, chainConfig.Clique, chainConfig.Ethash = nil, nil, nil
+	switch config.engine() {
+	case consensusParlia:
+		chainConfig.Parlia = &params.ParliaConfig{Period: period, Epoch: epoch}
+	case consensusClique:
+		chainConfig.Clique = &params.CliqueConfig{Period: period, Epoch: epoch}
+	case consensusEthashDev:
+		chainConfig.Ethash = &params.EthashConfig{}
+	}
+	return nil
+}
+
+// extraDataConfig returns extra data config with layout defaulted by the consensus engine
+func (c genesisConfig) extraDataConfig() *extraDataConfig {
+	if c.ExtraData != nil && c.ExtraData.Layout != "" {
+		return c.ExtraData
+	}
+	result := &extraDataConfig{Layout: c.Consensus.extraDataLayout()}
+	if c.ExtraData != nil {
+		result.Vanity = c.ExtraData.Vanity
+	}
+	return result
+}
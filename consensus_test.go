@@ -0,0 +1,60 @@
+package main
+
+import (
+	"errors"
+	"path/filepath"
+	"testing"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+func TestConsensusEngines(t *testing.T) {
+	for _, engine := range []string{consensusClique, consensusEthashDev} {
+		config := devNetConfig
+		config.Consensus = &consensusConfig{Engine: engine}
+		outputFile := filepath.Join(t.TempDir(), "genesis.json")
+		if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+			t.Fatalf("failed to generate %s genesis: %s", engine, err)
+		}
+		genesis, err := bgenesis.Load(outputFile)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if genesis.Config.Parlia != nil {
+			t.Fatalf("%s genesis has parlia config", engine)
+		}
+		switch engine {
+		case consensusClique:
+			if clique := genesis.Config.Clique; clique == nil || clique.Period != 3 || clique.Epoch != 1200 {
+				t.Fatalf("unexpected clique config: %+v", clique)
+			}
+			if expected := 32 + 20*len(config.Validators) + 65; len(genesis.ExtraData) != expected {
+				t.Fatalf("clique extra data must be %d bytes, got %d", expected, len(genesis.ExtraData))
+			}
+		case consensusEthashDev:
+			if genesis.Config.Ethash == nil || len(genesis.ExtraData) != 32 {
+				t.Fatalf("unexpected ethash genesis: config %v, extra data %x", genesis.Config, genesis.ExtraData)
+			}
+		}
+	}
+}
+
+func TestConsensusEngineViolations(t *testing.T) {
+	config := devNetConfig
+	config.Consensus = &consensusConfig{Engine: "aura"}
+	var violations configViolations
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 1 || violations[0].Path != "consensus.engine" {
+		t.Fatalf("unknown engine is accepted: %v", err)
+	}
+	config.Consensus = &consensusConfig{Engine: consensusClique}
+	config.ExtraData = &extraDataConfig{Layout: "luban"}
+	if err := config.validate(); !errors.As(err, &violations) {
+		t.Fatalf("clique with luban extra data is accepted: %v", err)
+	}
+	for _, violation := range violations {
+		if violation.Path == "extraData.layout" {
+			return
+		}
+	}
+	t.Fatalf("extra data layout isn't reported: %v", violations)
+}
@@ -117,11 +117,11 @@ contract Staking is IStaking, InjectorContextHolder {
     constructor(bytes memory constructorParams) InjectorContextHolder(constructorParams) {
     }
 
-    function ctor(address[] calldata validators, uint256[] calldata initialStakes, uint16 commissionRate) external whenNotInitialized {
-        require(initialStakes.length == validators.length);
+    function ctor(address[] memory validators, address[] memory owners, uint256[] memory initialStakes, uint16[] memory commissionRates) external whenNotInitialized {
+        require(owners.length == validators.length && initialStakes.length == validators.length && commissionRates.length == validators.length);
         uint256 totalStakes = 0;
         for (uint256 i = 0; i < validators.length; i++) {
-            _addValidator(validators[i], validators[i], ValidatorStatus.Active, commissionRate, initialStakes[i], 0);
+            _addValidator(validators[i], owners[i], ValidatorStatus.Active, commissionRates[i], initialStakes[i], 0);
             totalStakes += initialStakes[i];
         }
         require(address(this).balance == totalStakes, "Staking: initial stake balance mismatch");
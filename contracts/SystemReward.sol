@@ -10,16 +10,48 @@ contract SystemReward is ISystemReward, InjectorContextHolder {
      * for the system treasury otherwise it might cause lost of funds
      */
     uint256 public constant TREASURY_AUTO_CLAIM_THRESHOLD = 50 ether;
+    // shares of the treasury accounts are in basis points, they must sum up to it
+    uint16 public constant SHARE_DENOMINATOR = 10000;
 
-    // total system fee that is available for claim for system needs
+    struct DistributionShare {
+        address account;
+        uint16 share;
+    }
+
+    event DistributionShareChanged(address account, uint16 share);
+
+    // single treasury of contracts deployed before share distribution, it's used only while there are no shares
     address internal _systemTreasury;
+    // total system fee that is available for claim for system needs
     uint256 internal _systemFee;
+    // treasury accounts with their shares of the system fee
+    DistributionShare[] internal _distributionShares;
 
     constructor(bytes memory constructorParams) InjectorContextHolder(constructorParams) {
     }
 
-    function ctor(address systemTreasury) external whenNotInitialized {
-        _systemTreasury = systemTreasury;
+    function ctor(address[] calldata accounts, uint16[] calldata shares) external whenNotInitialized {
+        _updateDistributionShare(accounts, shares);
+    }
+
+    function getDistributionShares() external view returns (DistributionShare[] memory) {
+        return _distributionShares;
+    }
+
+    function updateDistributionShare(address[] calldata accounts, uint16[] calldata shares) external onlyFromGovernance {
+        _updateDistributionShare(accounts, shares);
+    }
+
+    function _updateDistributionShare(address[] calldata accounts, uint16[] calldata shares) internal {
+        require(accounts.length == shares.length, "SystemReward: bad length");
+        delete _distributionShares;
+        uint256 totalShares = 0;
+        for (uint256 i = 0; i < accounts.length; i++) {
+            _distributionShares.push(DistributionShare(accounts[i], shares[i]));
+            totalShares += shares[i];
+            emit DistributionShareChanged(accounts[i], shares[i]);
+        }
+        require(totalShares == SHARE_DENOMINATOR, "SystemReward: bad share distribution");
     }
 
     function getSystemFee() external view override returns (uint256) {
@@ -40,8 +72,18 @@ contract SystemReward is ISystemReward, InjectorContextHolder {
     }
 
     function _claimSystemFee() internal {
-        address payable payableTreasury = payable(_systemTreasury);
-        payableTreasury.transfer(_systemFee);
-        _systemFee = 0;
+        uint256 totalFee = _systemFee;
+        if (_distributionShares.length == 0) {
+            payable(_systemTreasury).transfer(totalFee);
+            _systemFee = 0;
+            return;
+        }
+        // rounding dust stays in the contract until the next claim
+        for (uint256 i = 0; i < _distributionShares.length; i++) {
+            DistributionShare memory ds = _distributionShares[i];
+            uint256 accountFee = totalFee * ds.share / SHARE_DENOMINATOR;
+            payable(ds.account).transfer(accountFee);
+            _systemFee -= accountFee;
+        }
     }
 }
\ No newline at end of file
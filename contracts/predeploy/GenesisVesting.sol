@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity ^0.8.0;
+
+/// @title GenesisVesting
+/// @notice Locks native tokens of beneficiaries and releases them linearly after the cliff. Schedules can be
+/// added only by the deployer, in genesis the deployer is the zero address, so the set of schedules is sealed
+/// from block 0.
+contract GenesisVesting {
+
+    event ScheduleAdded(address indexed beneficiary, uint256 total, uint64 start, uint64 cliff, uint64 duration);
+    event Released(address indexed beneficiary, uint256 amount);
+
+    struct Schedule {
+        uint256 total;
+        uint256 released;
+        uint64 start;
+        uint64 cliff;
+        uint64 duration;
+    }
+
+    address public immutable setupAccount;
+
+    mapping(address => Schedule) public schedules;
+
+    constructor() {
+        setupAccount = msg.sender;
+    }
+
+    /// @dev locks msg.value for the beneficiary, nothing is released before start + cliff and everything is
+    /// released at start + duration
+    function addSchedule(address beneficiary, uint64 start, uint64 cliff, uint64 duration) external payable {
+        require(msg.sender == setupAccount, "GenesisVesting: only setup account");
+        require(beneficiary != address(0), "GenesisVesting: zero beneficiary");
+        require(msg.value > 0, "GenesisVesting: nothing to lock");
+        require(duration > 0 && cliff <= duration, "GenesisVesting: bad duration");
+        require(schedules[beneficiary].total == 0, "GenesisVesting: schedule exists");
+        schedules[beneficiary] = Schedule(msg.value, 0, start, cliff, duration);
+        emit ScheduleAdded(beneficiary, msg.value, start, cliff, duration);
+    }
+
+    function vestedAmount(address beneficiary, uint64 timestamp) public view returns (uint256) {
+        Schedule memory schedule = schedules[beneficiary];
+        if (timestamp < schedule.start + schedule.cliff) {
+            return 0;
+        }
+        if (timestamp >= schedule.start + schedule.duration) {
+            return schedule.total;
+        }
+        return schedule.total * (timestamp - schedule.start) / schedule.duration;
+    }
+
+    function releasable(address beneficiary) public view returns (uint256) {
+        return vestedAmount(beneficiary, uint64(block.timestamp)) - schedules[beneficiary].released;
+    }
+
+    function release() external {
+        uint256 amount = releasable(msg.sender);
+        require(amount > 0, "GenesisVesting: nothing to release");
+        schedules[msg.sender].released += amount;
+        payable(msg.sender).transfer(amount);
+        emit Released(msg.sender, amount);
+    }
+}
@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+pragma solidity ^0.8.0;
+
+// Safe v1.3.0 contracts aren't used by system contracts, the imports only bring their artifacts into the build,
+// singleton, proxy factory and the configured Safe instance are predeployed from the "safe" config section
+import "@gnosis.pm/safe-contracts/contracts/GnosisSafeL2.sol";
+import "@gnosis.pm/safe-contracts/contracts/proxies/GnosisSafeProxyFactory.sol";
@@ -1,19 +1,23 @@
 package main
 
 import (
+	"bytes"
+	"crypto/ecdsa"
+	"crypto/sha256"
 	_ "embed"
 	"encoding/json"
+	"flag"
 	"fmt"
-	"github.com/ethereum/go-ethereum/common/math"
 	"github.com/ethereum/go-ethereum/common/systemcontract"
-	"github.com/ethereum/go-ethereum/eth/tracers"
+	"io"
 	"io/fs"
 	"io/ioutil"
 	"math/big"
 	"os"
+	"path/filepath"
 	"reflect"
+	"strconv"
 	"strings"
-	"unicode"
 	"unsafe"
 
 	_ "github.com/ethereum/go-ethereum/eth/tracers/native"
@@ -25,18 +29,51 @@ import (
 	"github.com/ethereum/go-ethereum/common/hexutil"
 	"github.com/ethereum/go-ethereum/consensus"
 	"github.com/ethereum/go-ethereum/core"
-	"github.com/ethereum/go-ethereum/core/rawdb"
 	"github.com/ethereum/go-ethereum/core/state"
 	"github.com/ethereum/go-ethereum/core/types"
 	"github.com/ethereum/go-ethereum/core/vm"
-	"github.com/ethereum/go-ethereum/ethdb/memorydb"
+	"github.com/ethereum/go-ethereum/log"
 	"github.com/ethereum/go-ethereum/params"
-	"github.com/ethereum/go-ethereum/trie"
 )
 
 type artifactData struct {
-	Bytecode         string `json:"bytecode"`
-	DeployedBytecode string `json:"deployedBytecode"`
+	ContractName     string          `json:"contractName"`
+	Abi              json.RawMessage `json:"abi"`
+	Bytecode         string          `json:"bytecode"`
+	DeployedBytecode string          `json:"deployedBytecode"`
+	// present only if compiler is configured to output storage layout
+	StorageLayout *storageLayout `json:"storageLayout,omitempty"`
+}
+
+// verifyConstructorSignature makes sure that artifact's ctor function accepts exactly the types we encode,
+// otherwise constructor params are decoded by the contract in a wrong way or init function fails
+func verifyConstructorSignature(rawArtifact []byte, typeNames []string) error {
+	artifact := &artifactData{}
+	if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+		return err
+	}
+	contractAbi, err := abi.JSON(bytes.NewReader(artifact.Abi))
+	if err != nil {
+		return fmt.Errorf("failed to parse ABI of %s: %w", artifact.ContractName, err)
+	}
+	expectedSig := fmt.Sprintf("ctor(%s)", strings.Join(typeNames, ","))
+	method, ok := contractAbi.Methods["ctor"]
+	if !ok {
+		return fmt.Errorf("ctor function is not found in ABI of %s (expected %s)", artifact.ContractName, expectedSig)
+	}
+	if method.Sig != expectedSig {
+		return fmt.Errorf("ctor signature mismatch for %s: artifact has %s, but genesis config encodes %s", artifact.ContractName, method.Sig, expectedSig)
+	}
+	return nil
+}
+
+// parseArtifactAbi returns ABI of the artifact or nil if it can't be parsed
+func parseArtifactAbi(artifact *artifactData) *abi.ABI {
+	contractAbi, err := abi.JSON(bytes.NewReader(artifact.Abi))
+	if err != nil {
+		return nil
+	}
+	return &contractAbi
 }
 
 type dummyChainContext struct {
@@ -50,14 +87,6 @@ func (d *dummyChainContext) GetHeader(common.Hash, uint64) *types.Header {
 	return nil
 }
 
-func createExtraData(validators []common.Address) []byte {
-	extra := make([]byte, 32+20*len(validators)+65)
-	for i, v := range validators {
-		copy(extra[32+20*i:], v.Bytes())
-	}
-	return extra
-}
-
 func readDirtyStorageFromState(f *state.StateObject) state.Storage {
 	var result map[common.Hash]common.Hash
 	rs := reflect.ValueOf(*f)
@@ -71,64 +100,233 @@ func readDirtyStorageFromState(f *state.StateObject) state.Storage {
 	return result
 }
 
-func simulateSystemContract(genesis *core.Genesis, systemContract common.Address, rawArtifact []byte, constructor []byte) error {
-	artifact := &artifactData{}
-	if err := json.Unmarshal(rawArtifact, artifact); err != nil {
-		return err
+func readStateObjects(statedb *state.StateDB) map[common.Address]*state.StateObject {
+	rf := reflect.ValueOf(statedb).Elem().FieldByName("stateObjects")
+	rf = reflect.NewAt(rf.Type(), unsafe.Pointer(rf.UnsafeAddr())).Elem()
+	result := make(map[common.Address]*state.StateObject)
+	for address, object := range rf.Interface().(map[common.Address]*state.StateObject) {
+		result[address] = object
 	}
-	bytecode := append(hexutil.MustDecode(artifact.Bytecode), constructor...)
-	// simulate constructor execution
-	ethdb := rawdb.NewDatabase(memorydb.New())
-	db := state.NewDatabaseWithConfig(ethdb, &trie.Config{})
-	statedb, err := state.New(common.Hash{}, db, nil)
+	return result
+}
+
+// systemContractSimulator executes all system contract constructors against one shared state, so each
+// constructor sees already deployed (and initialized) contracts
+type systemContractSimulator struct {
+	genesis *core.Genesis
+	statedb *state.StateDB
+	evm     *vm.EVM
+	tracer  *simulationTracer
+	// directory for call traces of simulated contracts (tracing is disabled if it's empty)
+	traceDir string
+	// nonce policy of system contract accounts (0, 1 or from-simulation)
+	contractNonce string
+	// gas given to every simulated constructor, follow-up and init call
+	gasLimit uint64
+	// precompile config applied for the simulation (it's a part of the cache key)
+	precompiles *precompileConfig
+}
+
+func newSystemContractSimulator(genesis *core.Genesis) (*systemContractSimulator, error) {
+	statedb, err := newStateFromGenesis(genesis)
 	if err != nil {
-		return err
+		return nil, err
 	}
 	block := genesis.ToBlock(nil)
 	blockContext := core.NewEVMBlockContext(block.Header(), &dummyChainContext{}, &common.Address{})
 	txContext := core.NewEVMTxContext(
-		types.NewMessage(common.Address{}, &systemContract, 0, big.NewInt(0), 10_000_000, big.NewInt(0), []byte{}, nil, false),
+		types.NewMessage(common.Address{}, nil, 0, big.NewInt(0), 10_000_000, big.NewInt(0), []byte{}, nil, false),
 	)
-	tracer, err := tracers.New("callTracer", nil)
+	evm, tracer, err := newSimulationEvm(blockContext, txContext, statedb, genesis.Config)
 	if err != nil {
-		return err
+		return nil, err
+	}
+	if genesis.Alloc == nil {
+		genesis.Alloc = make(core.GenesisAlloc)
+	}
+	return &systemContractSimulator{genesis: genesis, statedb: statedb, evm: evm, tracer: tracer, gasLimit: defaultSimulationGas}, nil
+}
+
+func newSimulationEvm(blockContext vm.BlockContext, txContext vm.TxContext, statedb *state.StateDB, chainConfig *params.ChainConfig) (*vm.EVM, *simulationTracer, error) {
+	// tracer keeps state of the current call, so every EVM needs its own one
+	tracer, err := newSimulationTracer(chainConfig.Rules(blockContext.BlockNumber, false))
+	if err != nil {
+		return nil, nil, err
 	}
-	evm := vm.NewEVM(blockContext, txContext, statedb, genesis.Config, vm.Config{
+	return vm.NewEVM(blockContext, txContext, statedb, chainConfig, vm.Config{
 		Debug:  true,
 		Tracer: tracer,
-	})
-	deployedBytecode, _, err := evm.CreateWithAddress(vm.AccountRef(common.Address{}), bytecode, 10_000_000, big.NewInt(0), systemContract)
-	if err != nil {
-		for _, c := range deployedBytecode[64:] {
-			if c >= 32 && c <= unicode.MaxASCII {
-				print(string(c))
-			}
+	}), tracer, nil
+}
+
+type accountSnapshot struct {
+	balance *big.Int
+	nonce   uint64
+	// state object of the account, statedb replaces it with a fresh one when the account is recreated
+	object *state.StateObject
+}
+
+func (s *systemContractSimulator) snapshotAccounts() map[common.Address]accountSnapshot {
+	result := make(map[common.Address]accountSnapshot)
+	for address, object := range readStateObjects(s.statedb) {
+		result[address] = accountSnapshot{balance: s.statedb.GetBalance(address), nonce: s.statedb.GetNonce(address), object: object}
+	}
+	return result
+}
+
+// mergeAccountChanges persists changes made by the current ctor to the account into genesis alloc
+func (s *systemContractSimulator) mergeAccountChanges(address common.Address, object *state.StateObject, before accountSnapshot) {
+	// self-destructed account is removed from state at the end of the transaction, so it can't stay in genesis
+	if s.statedb.HasSuicided(address) {
+		if _, exist := s.genesis.Alloc[address]; exist {
+			log.Info("Removing self-destructed account from genesis", "address", address)
 		}
-		println()
-		return err
+		delete(s.genesis.Alloc, address)
+		return
+	}
+	storage := readDirtyStorageFromState(object)
+	balance, nonce, code := s.statedb.GetBalance(address), s.statedb.GetNonce(address), s.statedb.GetCode(address)
+	account, exist := s.genesis.Alloc[address]
+	balanceChanged := before.balance == nil || balance.Cmp(before.balance) != 0
+	// recreated account (e.g. CREATE2 after selfdestruct) starts with empty storage, so accumulated slots are
+	// dropped and dirty storage of the new object is its whole storage
+	reset := exist && before.object != nil && before.object != object
+	if reset {
+		account.Storage, account.Code = nil, nil
+	}
+	if !exist && len(storage) == 0 && len(code) == 0 && balance.Sign() == 0 && nonce == 0 {
+		return
+	}
+	if exist && !reset && len(storage) == 0 && !balanceChanged && nonce == before.nonce {
+		return
+	}
+	if account.Storage == nil && len(storage) > 0 {
+		account.Storage = make(map[common.Hash]common.Hash)
+	}
+	for key, value := range storage {
+		account.Storage[key] = value
+	}
+	if !exist || balanceChanged {
+		account.Balance = new(big.Int).Set(balance)
+	}
+	if !exist || nonce != before.nonce {
+		account.Nonce = nonce
+	}
+	if len(code) > 0 {
+		account.Code = code
+	}
+	s.genesis.Alloc[address] = account
+}
+
+// simulationResult contains execution details of the system contract deployment
+type simulationResult struct {
+	// gas used by constructor (bytecode deployment)
+	GasUsed uint64
+	// gas used by init function (execution of ctor with params)
+	InitGasUsed uint64
+	// precompiles called by constructor, follow-up calls and init
+	PrecompileCalls []precompileCall
+	// events emitted by constructor, follow-up calls and init
+	Logs []simulatedLog
+	// nonce of the contract after constructor and follow-up calls
+	Nonce uint64
+	// ABI of the contract to decode its events
+	contractAbi *abi.ABI
+}
+
+// deploySystemContract deploys the contract with ctor params and runs follow-up calls, init is done separately
+func (s *systemContractSimulator) deploySystemContract(systemContract common.Address, rawArtifact []byte, constructor []byte, balance *big.Int, followUpCalls []followUpCall) (*simulationResult, error) {
+	artifact := &artifactData{}
+	if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+		return nil, err
+	}
+	bytecode := append(hexutil.MustDecode(artifact.Bytecode), constructor...)
+	s.tracer.takePrecompileCalls()
+	logCount := len(s.statedb.Logs())
+	gasUsed, err := s.deploy(systemContract, bytecode, balance)
+	if err != nil {
+		return nil, withRevertAbi(err, artifact.Abi)
+	}
+	if err := s.runFollowUpCalls(systemContract, followUpCalls); err != nil {
+		return nil, withRevertAbi(err, artifact.Abi)
+	}
+	return &simulationResult{
+		GasUsed:         gasUsed,
+		PrecompileCalls: s.tracer.takePrecompileCalls(),
+		Logs:            s.logsSince(logCount),
+		Nonce:           s.statedb.GetNonce(systemContract),
+		contractAbi:     parseArtifactAbi(artifact),
+	}, nil
+}
+
+// initSystemContract calls init function of the deployed contract, gas and precompile calls go to the result
+func (s *systemContractSimulator) initSystemContract(systemContract common.Address, result *simulationResult) error {
+	s.statedb.Finalise(false)
+	s.tracer.takePrecompileCalls()
+	logCount := len(s.statedb.Logs())
+	// make sure ctor working fine (better to fail here instead of in consensus engine), init changes are kept
+	// only in simulated state (node does init by itself), but next contracts can rely on this one
+	errorCode, leftOverGas, err := s.evm.Call(vm.AccountRef(common.Address{}), systemContract, hexutil.MustDecode("0xe1c7392a"), s.gasLimit, big.NewInt(0))
+	if err != nil {
+		return fmt.Errorf("init failed: %w", &revertError{cause: s.withGasLimit(err), data: errorCode})
+	}
+	result.InitGasUsed = s.gasLimit - leftOverGas
+	result.PrecompileCalls = append(result.PrecompileCalls, s.tracer.takePrecompileCalls()...)
+	result.Logs = append(result.Logs, s.logsSince(logCount)...)
+	return nil
+}
+
+// deploy executes init code at the address and persists deployed code with all state changes into genesis alloc
+func (s *systemContractSimulator) deploy(contract common.Address, initCode []byte, balance *big.Int) (uint64, error) {
+	// flush changes of previous deployments, after this dirty storage contains only changes made by this ctor
+	s.statedb.Finalise(false)
+	s.statedb.SetBalance(contract, balance)
+	accountsBefore := s.snapshotAccounts()
+	// simulate constructor execution
+	deployedBytecode, leftOverGas, err := s.evm.CreateWithAddress(vm.AccountRef(common.Address{}), initCode, s.gasLimit, big.NewInt(0), contract)
+	if err != nil {
+		return 0, &revertError{cause: s.withGasLimit(err), data: deployedBytecode}
+	}
+	if s.statedb.HasSuicided(contract) {
+		return 0, fmt.Errorf("constructor self-destructs the contract")
 	}
-	storage := readDirtyStorageFromState(statedb.GetOrNewStateObject(systemContract))
 	// read state changes from state database
-	genesisAccount := core.GenesisAccount{
+	s.genesis.Alloc[contract] = core.GenesisAccount{
 		Code:    deployedBytecode,
-		Storage: storage.Copy(),
-		Balance: big.NewInt(0),
+		Storage: readDirtyStorageFromState(s.statedb.GetOrNewStateObject(contract)).Copy(),
+		Balance: new(big.Int).Set(balance),
 		Nonce:   0,
 	}
-	if genesis.Alloc == nil {
-		genesis.Alloc = make(core.GenesisAlloc)
+	// ctor might also modify other accounts (write into storage of deployed contracts, create new contracts
+	// or transfer funds), all of them must be persisted as well
+	for address, object := range readStateObjects(s.statedb) {
+		if address == contract || address == (common.Address{}) {
+			continue
+		}
+		s.mergeAccountChanges(address, object, accountsBefore[address])
 	}
-	genesis.Alloc[systemContract] = genesisAccount
-	// make sure ctor working fine (better to fail here instead of in consensus engine)
-	errorCode, _, err := evm.Call(vm.AccountRef(common.Address{}), systemContract, hexutil.MustDecode("0xe1c7392a"), 10_000_000, big.NewInt(0))
-	if err != nil {
-		for _, c := range errorCode[64:] {
-			if c >= 32 && c <= unicode.MaxASCII {
-				print(string(c))
+	return s.gasLimit - leftOverGas, nil
+}
+
+// runFollowUpCalls executes calls against the deployed contract and persists all modified accounts into genesis alloc
+func (s *systemContractSimulator) runFollowUpCalls(systemContract common.Address, calls []followUpCall) error {
+	for i, call := range calls {
+		s.statedb.Finalise(false)
+		accountsBefore := s.snapshotAccounts()
+		value := call.value
+		if value == nil {
+			value = big.NewInt(0)
+		}
+		result, _, err := s.evm.Call(vm.AccountRef(call.from), systemContract, call.input, s.gasLimit, value)
+		if err != nil {
+			return fmt.Errorf("follow-up call #%d to %s failed: %w", i, systemContract.Hex(), &revertError{cause: s.withGasLimit(err), data: result})
+		}
+		for address, object := range readStateObjects(s.statedb) {
+			if address == (common.Address{}) {
+				continue
 			}
+			s.mergeAccountChanges(address, object, accountsBefore[address])
 		}
-		println()
-		return err
 	}
 	return nil
 }
@@ -180,125 +378,624 @@ func newArguments(typeNames ...string) abi.Arguments {
 }
 
 type consensusParams struct {
-	ActiveValidatorsLength   uint32                `json:"activeValidatorsLength"`
-	EpochBlockInterval       uint32                `json:"epochBlockInterval"`
-	MisdemeanorThreshold     uint32                `json:"misdemeanorThreshold"`
-	FelonyThreshold          uint32                `json:"felonyThreshold"`
-	ValidatorJailEpochLength uint32                `json:"validatorJailEpochLength"`
-	UndelegatePeriod         uint32                `json:"undelegatePeriod"`
-	MinValidatorStakeAmount  *math.HexOrDecimal256 `json:"minValidatorStakeAmount"`
-	MinStakingAmount         *math.HexOrDecimal256 `json:"minStakingAmount"`
+	ActiveValidatorsLength   uint32       `json:"activeValidatorsLength"`
+	EpochBlockInterval       uint32       `json:"epochBlockInterval"`
+	MisdemeanorThreshold     uint32       `json:"misdemeanorThreshold"`
+	FelonyThreshold          uint32       `json:"felonyThreshold"`
+	ValidatorJailEpochLength uint32       `json:"validatorJailEpochLength"`
+	UndelegatePeriod         uint32       `json:"undelegatePeriod"`
+	MinValidatorStakeAmount  *amountValue `json:"minValidatorStakeAmount"`
+	MinStakingAmount         *amountValue `json:"minStakingAmount"`
+	BlockPeriodSeconds       uint64       `json:"blockPeriodSeconds"`
+}
+
+// default Parlia block period, used when config doesn't specify it
+const defaultBlockPeriodSeconds = 3
+
+func (p consensusParams) blockPeriod() (uint64, error) {
+	period := p.BlockPeriodSeconds
+	if period == 0 {
+		period = defaultBlockPeriodSeconds
+	}
+	if p.EpochBlockInterval == 0 {
+		return 0, fmt.Errorf("epoch block interval must be greater than zero")
+	}
+	// epoch duration in seconds (period * epoch) must not overflow
+	if period > (1<<63)/uint64(p.EpochBlockInterval) {
+		return 0, fmt.Errorf("block period (%d) is too large for epoch block interval (%d)", period, p.EpochBlockInterval)
+	}
+	return period, nil
+}
+
+// commissionRate is validator commission in basis points (10000 is 100%)
+type commissionRate int64
+
+const maxCommissionRate = 10_000
+
+func (r commissionRate) basisPoints() (uint16, error) {
+	if r < 0 || r > maxCommissionRate {
+		return 0, fmt.Errorf("must be in range 0-%d basis points, got %d", maxCommissionRate, r)
+	}
+	return uint16(r), nil
+}
+
+// votingPeriod is duration of governance voting in blocks
+type votingPeriod int64
+
+func (p votingPeriod) blocks() (*big.Int, error) {
+	if p <= 0 {
+		return nil, fmt.Errorf("must be positive number of blocks, got %d", p)
+	}
+	return big.NewInt(int64(p)), nil
 }
 
 type genesisConfig struct {
-	ChainId         int64                     `json:"chainId"`
-	Deployers       []common.Address          `json:"deployers"`
-	Validators      []common.Address          `json:"validators"`
-	SystemTreasury  common.Address            `json:"systemTreasury"`
-	ConsensusParams consensusParams           `json:"consensusParams"`
-	VotingPeriod    int64                     `json:"votingPeriod"`
-	Faucet          map[common.Address]string `json:"faucet"`
-	CommissionRate  int64                     `json:"commissionRate"`
-	InitialStakes   map[common.Address]string `json:"initialStakes"`
+	// schema version of the config, older versions are migrated when the config is read
+	Version int `json:"version,omitempty"`
+
+	ChainId         int64                           `json:"chainId"`
+	Deployers       []common.Address                `json:"deployers"`
+	Validators      []validatorConfig               `json:"validators"`
+	SystemTreasury  treasuryConfig                  `json:"systemTreasury"`
+	ConsensusParams consensusParams                 `json:"consensusParams"`
+	VotingPeriod    votingPeriod                    `json:"votingPeriod"`
+	Faucet          map[common.Address]*amountValue `json:"faucet"`
+	CommissionRate  commissionRate                  `json:"commissionRate"`
+	InitialStakes   map[common.Address]*amountValue `json:"initialStakes"`
+
+	// address of the EVM hook that applies runtime upgrades (default is the one built into the node)
+	RuntimeUpgradeEvmHook *common.Address `json:"runtimeUpgradeEvmHook,omitempty"`
+	// consensus engine of the chain (default is parlia)
+	Consensus *consensusConfig `json:"consensus,omitempty"`
+	// fork blocks of newer BSC releases written into chain config (geth and erigon formats only)
+	Forks *bscForkConfig `json:"forks,omitempty"`
+	// extra data encoding (default is layout of the consensus engine)
+	ExtraData *extraDataConfig `json:"extraData,omitempty"`
+	// meta accounts required by consensus engine (intermediary system account is always created)
+	SystemAccounts []systemAccountConfig `json:"systemAccounts,omitempty"`
+	// node software the genesis is generated for, used to warn about unsupported features
+	Client *clientConfig `json:"client,omitempty"`
+	// min balance of validator and owner accounts in genesis (for gas of the first transactions)
+	ValidatorOperationalBalance *amountValue `json:"validatorOperationalBalance,omitempty"`
+	// accounts written into genesis as is (balance, nonce, code and storage), they exist during simulation
+	Accounts map[common.Address]accountConfig `json:"accounts,omitempty"`
+	// calls to system contracts executed right after their deployment
+	FollowUpCalls []followUpCallConfig `json:"followUpCalls,omitempty"`
+	// additional contracts deployed in genesis (tokens, multicall, etc)
+	ExtraContracts []extraContractConfig `json:"extraContracts,omitempty"`
+	// genesis block header fields (timestamp, gas limit, etc)
+	Header *headerConfig `json:"header,omitempty"`
+	// ERC-20 tokens with initial holder balances
+	Tokens []tokenConfig `json:"tokens,omitempty"`
+	// allocations locked in the vesting contract
+	Vesting *vestingConfig `json:"vesting,omitempty"`
+	// multisig (Safe) set up with owners and threshold, e.g. to be the treasury
+	Safe *safeConfig `json:"safe,omitempty"`
+	// precompiles available for constructors during simulation (default is the set of the node)
+	Precompiles *precompileConfig `json:"precompiles,omitempty"`
+	// view calls checked against generated genesis, generation fails if any of them doesn't hold
+	Assertions []assertionConfig `json:"assertions,omitempty"`
+	// nonce of system contract accounts: 0 (default), 1 or from-simulation
+	SystemContractNonce string `json:"systemContractNonce,omitempty"`
+	// generation fails if sum of all genesis balances exceeds it (optional)
+	MaxTotalSupply *amountValue `json:"maxTotalSupply,omitempty"`
+	// validators listed in faucet are rejected unless it's intended (e.g. single node local net)
+	AllowValidatorFaucet bool `json:"allowValidatorFaucet,omitempty"`
+}
+
+type followUpCallConfig struct {
+	// name of the system contract (e.g. Governance)
+	Contract string         `json:"contract"`
+	From     common.Address `json:"from"`
+	Data     hexutil.Bytes  `json:"data"`
+	Value    *amountValue   `json:"value,omitempty"`
+}
+
+type systemAccountConfig struct {
+	Address common.Address `json:"address"`
+	Balance *amountValue   `json:"balance,omitempty"`
+	Nonce   uint64         `json:"nonce,omitempty"`
+}
+
+func (c genesisConfig) systemAccounts() []systemAccountConfig {
+	result := c.SystemAccounts
+	for _, account := range result {
+		if account.Address == intermediarySystemAddress {
+			return result
+		}
+	}
+	// parlia expects intermediary system account to exist
+	return append([]systemAccountConfig{{Address: intermediarySystemAddress}}, result...)
+}
+
+func (c genesisConfig) systemAccountAddresses() []common.Address {
+	var result []common.Address
+	for _, account := range c.systemAccounts() {
+		result = append(result, account.Address)
+	}
+	return result
+}
+
+func (c genesisConfig) runtimeUpgradeEvmHook() common.Address {
+	if c.RuntimeUpgradeEvmHook != nil {
+		return *c.RuntimeUpgradeEvmHook
+	}
+	return systemcontract.EvmHookRuntimeUpgradeAddress
+}
+
+func invokeConstructor(simulator *systemContractSimulator, deployment *systemContractDeployment, rawArtifact []byte) (*simulationResult, error) {
+	ctor, err := encodeConstructor(deployment, rawArtifact)
+	if err != nil {
+		return nil, err
+	}
+	return simulator.deploySystemContract(deployment.address, rawArtifact, ctor, deployment.initialBalance(), deployment.followUpCalls)
 }
 
-func invokeConstructorOrPanic(genesis *core.Genesis, contract common.Address, rawArtifact []byte, typeNames []string, params []interface{}, silent bool) {
-	ctor, err := newArguments(typeNames...).Pack(params...)
+// encodeConstructor packs ctor params of the deployment, signature is checked against ABI of the artifact
+func encodeConstructor(deployment *systemContractDeployment, rawArtifact []byte) ([]byte, error) {
+	contract, typeNames := deployment.address, deployment.typeNames
+	if err := verifyConstructorSignature(rawArtifact, typeNames); err != nil {
+		return nil, err
+	}
+	if err := verifyAbiSelectors(rawArtifact); err != nil {
+		return nil, err
+	}
+	ctor, err := newArguments(typeNames...).Pack(deployment.params...)
 	if err != nil {
-		panic(err)
+		return nil, err
 	}
 	sig := crypto.Keccak256([]byte(fmt.Sprintf("ctor(%s)", strings.Join(typeNames, ","))))[:4]
 	ctor = append(sig, ctor...)
 	ctor, err = newArguments("bytes").Pack(ctor)
 	if err != nil {
-		panic(err)
+		return nil, err
 	}
-	if !silent {
-		fmt.Printf(" + calling constructor: address=%s sig=%s ctor=%s\n", contract.Hex(), hexutil.Encode(sig), hexutil.Encode(ctor))
+	log.Debug("Encoded constructor", "contract", deployment.name, "address", contract, "sig", hexutil.Encode(sig), "ctor", hexutil.Encode(ctor))
+	return ctor, nil
+}
+
+// simulateDeployment deploys the contract and calls its init function against the shared state
+func simulateDeployment(simulator *systemContractSimulator, cache *simulationCache, deployment *systemContractDeployment, rawArtifact []byte) (*simulationResult, error) {
+	result, err := cache.invokeConstructor(simulator, deployment, rawArtifact)
+	if err != nil {
+		return nil, err
 	}
-	if err := simulateSystemContract(genesis, contract, rawArtifact, ctor); err != nil {
-		panic(err)
+	if err := simulator.applyContractNonce(deployment.address, result); err != nil {
+		return nil, err
+	}
+	if err := simulator.initSystemContract(deployment.address, result); err != nil {
+		return nil, err
+	}
+	warnNonstandardPrecompiles(deployment.name, result.PrecompileCalls)
+	return result, nil
+}
+
+// generateOptions are settings of the generator that are not part of the genesis config
+type generateOptions struct {
+	Artifacts artifactSet
+	// path to write canonical genesis spec to (optional)
+	SpecFile string
+	// path to write deployment report to (optional)
+	ReportFile string
+	// client specific layout of the genesis file (default is geth)
+	Format string
+	// serialization of the genesis file and deployment report: json, yaml or toml (default is json)
+	OutputFormat string
+	// names of utility contracts from the predeploy catalog
+	Predeploys []string
+	// max size of the account code (default is EIP-170 limit) and whether exceeding it is only a warning
+	MaxCodeSize   int
+	AllowOversize bool
+	// warn instead of failing when code of system contract differs from deployed bytecode of its artifact
+	AllowCodeMismatch bool
+	// JSON merge patch applied to generated genesis (optional)
+	PatchFile string
+	// CSV file with "address,amount" rows added to genesis balances (optional)
+	AirdropFile string
+	// validate and simulate everything, but print the plan instead of writing files
+	DryRun bool
+	// max number of independent system contracts simulated concurrently (0 is number of CPUs, 1 is sequential)
+	Parallelism int
+	// directory with cached constructor results (optional)
+	CacheDir string
+	// directory to write call traces of simulated system contracts to (optional)
+	TraceDir string
+	// storage layouts by contract name, used to label storage slots in the report (optional)
+	StorageLayouts map[string]*storageLayout
+	// strip zero storage values and empty accounts from alloc
+	Prune bool
+	// "strip" or "zero" normalizes solc metadata appended to deployed code (default keeps code as compiled)
+	CodeMetadata string
+	// simulate with the genesis gas limit instead of the fixed budget and fail if init calls don't fit into a block
+	MeterGas bool
+	// path to write system transactions that initialize system contracts at block 1 to (optional)
+	TransactionsFile string
+	// genesis is written as JSON to the writer instead of the target file (optional, for builds without filesystem)
+	Output io.Writer
+	// deployment report is written as JSON to the writer (optional)
+	ReportOutput io.Writer
+}
+
+func defaultGenerateOptions() *generateOptions {
+	return &generateOptions{Artifacts: defaultArtifactSet()}
+}
+
+func writeOutput(targetFile string, data []byte) error {
+	if targetFile == "stdout" {
+		_, err := os.Stdout.Write(data)
+		return err
+	} else if targetFile == "stderr" {
+		_, err := os.Stderr.Write(data)
+		return err
 	}
+	return ioutil.WriteFile(targetFile, data, fs.ModePerm)
 }
 
-func createGenesisConfig(config genesisConfig, targetFile string) error {
+func createGenesisConfig(config genesisConfig, targetFile string, options *generateOptions) error {
+	phase := startPhase("validation")
+	if err := config.resolveCreate2Addresses(); err != nil {
+		return err
+	}
+	if err := config.validate(); err != nil {
+		return err
+	}
+	phase.finish()
 	genesis := defaultGenesisConfig(config.ChainId)
 	// extra data
-	genesis.ExtraData = createExtraData(config.Validators)
-	genesis.Config.Parlia.Epoch = uint64(config.ConsensusParams.EpochBlockInterval)
+	extraData, err := createExtraData(config.extraDataConfig(), config.Validators)
+	if err != nil {
+		return err
+	}
+	genesis.ExtraData = extraData
+	if format := options.Format; len(config.Forks.blocks()) > 0 && format != "" && format != "geth" && format != "erigon" {
+		return fmt.Errorf("BSC fork blocks can't be written in %s format", format)
+	}
+	// besu BFT blocks carry fixed mix hash, header config can still override it
+	if config.Consensus.besuBft() {
+		if options.Format != "besu" {
+			return fmt.Errorf("%s genesis can be written only in besu format, use --format besu", config.Consensus.engine())
+		}
+		genesis.Mixhash = bftMixHash
+	}
+	if err := applyHeaderConfig(genesis, config.Header); err != nil {
+		return err
+	}
+	blockPeriod, err := config.ConsensusParams.blockPeriod()
+	if err != nil {
+		return err
+	}
+	if err := applyConsensusEngine(genesis.Config, config.Consensus, blockPeriod, uint64(config.ConsensusParams.EpochBlockInterval)); err != nil {
+		return err
+	}
+	config.Forks.apply(genesis.Config)
 	// execute system contracts
 	var initialStakes []*big.Int
 	initialStakeTotal := big.NewInt(0)
-	for _, v := range config.Validators {
-		rawInitialStake, ok := config.InitialStakes[v]
-		if !ok {
+	for _, v := range config.validatorAddresses() {
+		initialStake := (*big.Int)(config.InitialStakes[v])
+		if initialStake == nil {
 			return fmt.Errorf("initial stake is not found for validator: %s", v.Hex())
 		}
-		initialStake, err := hexutil.DecodeBig(rawInitialStake)
-		if err != nil {
-			return err
-		}
 		initialStakes = append(initialStakes, initialStake)
 		initialStakeTotal.Add(initialStakeTotal, initialStake)
 	}
-	silent := targetFile == "stdout"
-	invokeConstructorOrPanic(genesis, stakingAddress, stakingRawArtifact, []string{"address[]", "uint256[]", "uint16"}, []interface{}{
-		config.Validators,
-		initialStakes,
-		uint16(config.CommissionRate),
-	}, silent)
-	invokeConstructorOrPanic(genesis, chainConfigAddress, chainConfigRawArtifact, []string{"uint32", "uint32", "uint32", "uint32", "uint32", "uint32", "uint256", "uint256"}, []interface{}{
-		config.ConsensusParams.ActiveValidatorsLength,
-		config.ConsensusParams.EpochBlockInterval,
-		config.ConsensusParams.MisdemeanorThreshold,
-		config.ConsensusParams.FelonyThreshold,
-		config.ConsensusParams.ValidatorJailEpochLength,
-		config.ConsensusParams.UndelegatePeriod,
-		(*big.Int)(config.ConsensusParams.MinValidatorStakeAmount),
-		(*big.Int)(config.ConsensusParams.MinStakingAmount),
-	}, silent)
-	invokeConstructorOrPanic(genesis, slashingIndicatorAddress, slashingIndicatorRawArtifact, []string{}, []interface{}{}, silent)
-	invokeConstructorOrPanic(genesis, stakingPoolAddress, stakingPoolRawArtifact, []string{}, []interface{}{}, silent)
-	invokeConstructorOrPanic(genesis, systemRewardAddress, systemRewardRawArtifact, []string{"address"}, []interface{}{
-		config.SystemTreasury,
-	}, silent)
-	invokeConstructorOrPanic(genesis, governanceAddress, governanceRawArtifact, []string{"uint256"}, []interface{}{
-		big.NewInt(config.VotingPeriod),
-	}, silent)
-	invokeConstructorOrPanic(genesis, runtimeUpgradeAddress, runtimeUpgradeRawArtifact, []string{"address"}, []interface{}{
-		systemcontract.EvmHookRuntimeUpgradeAddress,
-	}, silent)
-	invokeConstructorOrPanic(genesis, deployerProxyAddress, deployerProxyRawArtifact, []string{"address[]"}, []interface{}{
-		config.Deployers,
-	}, silent)
-	// create system contract
-	genesis.Alloc[intermediarySystemAddress] = core.GenesisAccount{
-		Balance: big.NewInt(0),
-	}
-	// set staking allocation
-	stakingAlloc := genesis.Alloc[stakingAddress]
-	stakingAlloc.Balance = initialStakeTotal
-	genesis.Alloc[stakingAddress] = stakingAlloc
+	commissionRates, err := config.validatorCommissionRates()
+	if err != nil {
+		return err
+	}
+	votingPeriodBlocks, err := config.VotingPeriod.blocks()
+	if err != nil {
+		return fmt.Errorf("votingPeriod %w", err)
+	}
+	treasuryAccounts, treasuryShares := config.SystemTreasury.shares()
+	// stdout carries the genesis, so the report isn't printed there
+	silent := (targetFile == "stdout" || options.Output != nil) && !options.DryRun
+	log.Info("Genesis timestamp", "time", formatTimestamp(genesis.Timestamp))
+	deployments, err := orderDeployments(withFollowUpCalls([]*systemContractDeployment{
+		{
+			name:      "Staking",
+			address:   stakingAddress,
+			typeNames: []string{"address[]", "address[]", "uint256[]", "uint16[]"},
+			params: []interface{}{
+				config.validatorAddresses(),
+				config.validatorOwners(),
+				initialStakes,
+				commissionRates,
+			},
+			// staking ctor checks that its balance matches total initial stake
+			balance: initialStakeTotal,
+			// validators are added using epoch and min stake from chain config
+			dependsOn: []common.Address{chainConfigAddress},
+		},
+		{
+			name:      "ChainConfig",
+			address:   chainConfigAddress,
+			typeNames: []string{"uint32", "uint32", "uint32", "uint32", "uint32", "uint32", "uint256", "uint256"},
+			params: []interface{}{
+				config.ConsensusParams.ActiveValidatorsLength,
+				config.ConsensusParams.EpochBlockInterval,
+				config.ConsensusParams.MisdemeanorThreshold,
+				config.ConsensusParams.FelonyThreshold,
+				config.ConsensusParams.ValidatorJailEpochLength,
+				config.ConsensusParams.UndelegatePeriod,
+				(*big.Int)(config.ConsensusParams.MinValidatorStakeAmount),
+				(*big.Int)(config.ConsensusParams.MinStakingAmount),
+			},
+		},
+		{
+			name:      "SlashingIndicator",
+			address:   slashingIndicatorAddress,
+			typeNames: []string{},
+			params:    []interface{}{},
+		},
+		// staking pool is linked with staking (0x...1000) through the injector's init, it doesn't have pool fee params yet,
+		// so the only thing we can do is to make sure that ABI still matches empty ctor
+		{
+			name:      "StakingPool",
+			address:   stakingPoolAddress,
+			typeNames: []string{},
+			params:    []interface{}{},
+		},
+		{
+			name:      "SystemReward",
+			address:   systemRewardAddress,
+			typeNames: []string{"address[]", "uint16[]"},
+			params: []interface{}{
+				treasuryAccounts,
+				treasuryShares,
+			},
+		},
+		{
+			name:      "Governance",
+			address:   governanceAddress,
+			typeNames: []string{"uint256"},
+			params: []interface{}{
+				votingPeriodBlocks,
+			},
+		},
+		// runtime upgrade is active from the genesis block (runtimeUpgradeBlock=0), so the contract must be in state
+		{
+			name:      "RuntimeUpgrade",
+			address:   runtimeUpgradeAddress,
+			typeNames: []string{"address"},
+			params: []interface{}{
+				config.runtimeUpgradeEvmHook(),
+			},
+		},
+		{
+			name:      "DeployerProxy",
+			address:   deployerProxyAddress,
+			typeNames: []string{"address[]"},
+			params: []interface{}{
+				config.Deployers,
+			},
+		},
+	}, config.FollowUpCalls))
+	if err != nil {
+		return err
+	}
+	phase = startPhase("simulation")
+	defer lockSimulation(config.Precompiles)()
+	restorePrecompiles, err := applyPrecompileConfig(config.Precompiles, genesis.Config)
+	if err != nil {
+		return err
+	}
+	defer restorePrecompiles()
+	accountsTotal := applyAccountConfigs(genesis, config.Accounts)
+	simulator, err := newSystemContractSimulator(genesis)
+	if err != nil {
+		return err
+	}
+	if err := verifyChainId(config, genesis, simulator.evm); err != nil {
+		return err
+	}
+	simulator.enableTracing(options.TraceDir)
+	simulator.contractNonce = config.systemContractNonce()
+	simulator.precompiles = config.Precompiles
+	if options.MeterGas {
+		simulator.gasLimit = genesis.GasLimit
+	}
+	report := &deploymentReport{ChainId: config.ChainId, Timing: newConsensusTimingReport(config), storageLayouts: options.StorageLayouts}
+	report.addValidators(config)
+	// don't stop on the first failed deployment, report all of them at once
+	cache := newSimulationCache(options.CacheDir)
+	failures := simulateDeployments(simulator, cache, deployments, options.Artifacts, options.Parallelism, report)
+	// utility contracts requested with --predeploy
+	failures = append(failures, deployPredeploys(simulator, options.Predeploys)...)
+	// deploy extra contracts declared in config, they can rely on system contracts
+	failures = append(failures, deployExtraContracts(simulator, config.ExtraContracts)...)
+	failures = append(failures, deployTokens(simulator, config.Tokens)...)
+	vestingTotal, vestingFailures := deployVesting(simulator, config.Vesting)
+	failures = append(failures, vestingFailures...)
+	failures = append(failures, deploySafe(simulator, config.Safe)...)
+	if len(failures) > 0 {
+		return failures
+	}
+	codeWarnings, err := verifyDeployedCode(genesis, deployments, options.Artifacts, options.AllowCodeMismatch)
+	if err != nil {
+		return err
+	}
+	for _, warning := range codeWarnings {
+		log.Warn(warning)
+	}
+	report.Immutables = collectImmutables(genesis, immutableSources(config, deployments, options.Artifacts))
+	if options.MeterGas {
+		if err := verifyBlockGas(report, genesis.GasLimit); err != nil {
+			return err
+		}
+	}
+	phase.finish()
+	phase = startPhase("allocation")
+	// create system accounts
+	for _, systemAccount := range config.systemAccounts() {
+		if _, ok := genesis.Alloc[systemAccount.Address]; ok {
+			return fmt.Errorf("system account %s collides with system contract", systemAccount.Address.Hex())
+		}
+		balance := big.NewInt(0)
+		if systemAccount.Balance != nil {
+			balance = (*big.Int)(systemAccount.Balance)
+		}
+		genesis.Alloc[systemAccount.Address] = core.GenesisAccount{
+			Balance: balance,
+			Nonce:   systemAccount.Nonce,
+		}
+	}
 	// apply faucet
+	faucetTotal := big.NewInt(0)
 	for key, value := range config.Faucet {
-		balance, ok := new(big.Int).SetString(value[2:], 16)
-		if !ok {
-			return fmt.Errorf("failed to parse number (%s)", value)
-		}
+		balance := new(big.Int).Set((*big.Int)(value))
 		genesis.Alloc[key] = core.GenesisAccount{
 			Balance: balance,
 		}
+		faucetTotal.Add(faucetTotal, balance)
 	}
-	// save to file
-	newJson, _ := json.MarshalIndent(genesis, "", "  ")
-	if targetFile == "stdout" {
-		_, err := os.Stdout.Write(newJson)
+	// airdrop balances from CSV file (e.g. migrated from a snapshot of another chain)
+	airdropTotal := big.NewInt(0)
+	if options.AirdropFile != "" {
+		balances, err := readAirdrop(options.AirdropFile, phase.progress)
+		if err != nil {
+			return err
+		}
+		airdropTotal = applyAirdrop(genesis, balances)
+	}
+	// top up validators, they need some funds to pay for the first transactions
+	operationalTotal := big.NewInt(0)
+	if floor := config.ValidatorOperationalBalance; floor != nil {
+		for _, validator := range config.validatorOperationalAccounts() {
+			account := genesis.Alloc[validator]
+			if account.Balance == nil {
+				account.Balance = big.NewInt(0)
+			}
+			if account.Balance.Cmp((*big.Int)(floor)) >= 0 {
+				continue
+			}
+			operationalTotal.Add(operationalTotal, new(big.Int).Sub((*big.Int)(floor), account.Balance))
+			account.Balance = new(big.Int).Set((*big.Int)(floor))
+			genesis.Alloc[validator] = account
+		}
+	}
+	phase.finish()
+	log.Info("Genesis supply", "initialStakes", formatAmount(initialStakeTotal), "vesting", formatAmount(vestingTotal), "faucet", formatAmount(faucetTotal), "accounts", formatAmount(accountsTotal), "airdrop", formatAmount(airdropTotal), "validatorOperationalBalances", formatAmount(operationalTotal))
+	phase = startPhase("verification")
+	// operator customizations go last, all checks below are done against patched genesis
+	if options.PatchFile != "" {
+		if err := applyGenesisPatch(genesis, options.PatchFile); err != nil {
+			return err
+		}
+	}
+	if options.CodeMetadata != "" && options.CodeMetadata != codeMetadataKeep {
+		if report.Metadata, err = normalizeCodeMetadata(genesis, options.CodeMetadata); err != nil {
+			return err
+		}
+		report.updateCodeHashes(genesis)
+	}
+	if options.Prune {
+		report.Pruned = pruneAlloc(genesis, config.systemAccountAddresses())
+	}
+	// supply is counted after patch, so it's exactly what the chain starts with
+	report.Supply = newSupplyReport(genesis, initialStakeTotal, vestingTotal, faucetTotal, accountsTotal, airdropTotal, operationalTotal)
+	if err := verifySupplyCap(report.Supply, config.MaxTotalSupply); err != nil {
 		return err
-	} else if targetFile == "stderr" {
-		_, err := os.Stderr.Write(newJson)
+	}
+	if err := verifyExtraData(config.extraDataConfig(), genesis.ExtraData, config.Validators); err != nil {
+		return err
+	}
+	// pre-flight check that declared client is able to run this genesis
+	warnings, err := checkClientCompatibility(genesis, config)
+	if err != nil {
+		return err
+	}
+	warnings = append(warnings, chainIdWarnings(config.ChainId)...)
+	warnings = append(warnings, epochWarnings(config.ConsensusParams)...)
+	forkWarnings, err := checkForkSchedule(forkSchedule(genesis.Config, config.Forks))
+	if err != nil {
+		return err
+	}
+	warnings = append(warnings, forkWarnings...)
+	codeSizeWarnings, err := checkCodeSizes(genesis, options.MaxCodeSize, options.AllowOversize)
+	if err != nil {
+		return err
+	}
+	warnings = append(warnings, codeSizeWarnings...)
+	for _, warning := range warnings {
+		log.Warn(warning)
+	}
+	// make sure deployer whitelist works before the chain launch
+	if err := verifyDeployerProxyGating(genesis, config.Deployers); err != nil {
+		return err
+	}
+	// mis-encoded ctor args are caught by operator's assertions before the chain is launched
+	if err := verifyAssertions(genesis, config.Assertions, deployments, options.Artifacts); err != nil {
+		return err
+	}
+	phase.finish()
+	// deployment report for operators and auditors
+	if !silent {
+		report.print(os.Stdout)
+	}
+	if options.DryRun {
+		printFundedAccounts(os.Stdout, genesis)
+		fmt.Printf("dry run, nothing is written\n")
+		return nil
+	}
+	phase = startPhase("writing output")
+	if options.ReportOutput != nil {
+		if err := writeJsonStream(options.ReportOutput, report); err != nil {
+			return err
+		}
+	}
+	if options.ReportFile != "" {
+		reportData, err := encodeOutput(report, options.OutputFormat)
+		if err != nil {
+			return err
+		}
+		if err := writeOutput(options.ReportFile, reportData); err != nil {
+			return err
+		}
+	}
+	// the same deployments as replayable transactions for clients that don't use pre-allocated state
+	if options.TransactionsFile != "" {
+		bundle, err := newReplayBundle(config.ChainId, deployments, options.Artifacts, report)
+		if err != nil {
+			return err
+		}
+		bundleData, err := encodeOutput(bundle, options.OutputFormat)
+		if err != nil {
+			return err
+		}
+		if err := writeOutput(options.TransactionsFile, bundleData); err != nil {
+			return err
+		}
+	}
+	// canonical spec for section-level verification
+	if options.SpecFile != "" {
+		spec, err := createGenesisSpec(genesis)
+		if err != nil {
+			return err
+		}
+		specJson, _ := json.MarshalIndent(spec, "", "  ")
+		if err := writeOutput(options.SpecFile, specJson); err != nil {
+			return err
+		}
+	}
+	// save to file in the client specific layout
+	formatted, err := formatGenesis(genesis, options.Format)
+	if err != nil {
+		return err
+	}
+	formatted = withBscForks(formatted, config.Forks)
+	if config.Consensus.besuBft() {
+		formatted = withBesuBftConfig(formatted, config.Consensus, blockPeriod, uint64(config.ConsensusParams.EpochBlockInterval))
+	}
+	if options.Output != nil {
+		if err := writeJsonStream(options.Output, formatted); err != nil {
+			return err
+		}
+	} else if err := writeFormattedOutput(targetFile, formatted, options.OutputFormat); err != nil {
 		return err
 	}
-	return ioutil.WriteFile(targetFile, newJson, fs.ModePerm)
+	phase.finish()
+	return nil
 }
 
 func defaultGenesisConfig(chainId int64) *core.Genesis {
@@ -319,8 +1016,7 @@ func defaultGenesisConfig(chainId int64) *core.Genesis {
 		BrunoBlock:          big.NewInt(0),
 		RuntimeUpgradeBlock: big.NewInt(0),
 		Parlia: &params.ParliaConfig{
-			Period: 3,
-			// epoch length is managed by consensus params
+			// period and epoch length are managed by consensus params
 		},
 	}
 	return &core.Genesis{
@@ -346,31 +1042,34 @@ var localNetConfig = genesisConfig{
 		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"),
 	},
 	// list of default validators
-	Validators: []common.Address{
-		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"),
+	Validators: []validatorConfig{
+		{Address: common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725")},
 	},
-	SystemTreasury: common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"),
+	SystemTreasury: singleTreasury(common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725")),
 	ConsensusParams: consensusParams{
 		ActiveValidatorsLength:   1,
-		EpochBlockInterval:       100,
+		EpochBlockInterval:       99,
 		MisdemeanorThreshold:     10,
 		FelonyThreshold:          100,
 		ValidatorJailEpochLength: 1,
 		UndelegatePeriod:         0,
-		MinValidatorStakeAmount:  (*math.HexOrDecimal256)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // 1 ether
-		MinStakingAmount:         (*math.HexOrDecimal256)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // 1 ether
+		MinValidatorStakeAmount:  (*amountValue)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // 1 ether
+		MinStakingAmount:         (*amountValue)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // 1 ether
+		BlockPeriodSeconds:       3,
 	},
-	InitialStakes: map[common.Address]string{
-		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"): "0x3635c9adc5dea00000", // 1000 eth
+	InitialStakes: map[common.Address]*amountValue{
+		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"): (*amountValue)(hexutil.MustDecodeBig("0x3635c9adc5dea00000")), // 1000 eth
 	},
 	// owner of the governance
 	VotingPeriod: 20, // 1 minute
 	// faucet
-	Faucet: map[common.Address]string{
-		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"): "0x21e19e0c9bab2400000",
-		common.HexToAddress("0x57BA24bE2cF17400f37dB3566e839bfA6A2d018a"): "0x21e19e0c9bab2400000",
-		common.HexToAddress("0xEbCf9D06cf9333706E61213F17A795B2F7c55F1b"): "0x21e19e0c9bab2400000",
+	Faucet: map[common.Address]*amountValue{
+		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"): (*amountValue)(hexutil.MustDecodeBig("0x21e19e0c9bab2400000")),
+		common.HexToAddress("0x57BA24bE2cF17400f37dB3566e839bfA6A2d018a"): (*amountValue)(hexutil.MustDecodeBig("0x21e19e0c9bab2400000")),
+		common.HexToAddress("0xEbCf9D06cf9333706E61213F17A795B2F7c55F1b"): (*amountValue)(hexutil.MustDecodeBig("0x21e19e0c9bab2400000")),
 	},
+	// the only validator is funded by faucet to pay for the first transactions
+	AllowValidatorFaucet: true,
 }
 
 var devNetConfig = genesisConfig{
@@ -378,14 +1077,14 @@ var devNetConfig = genesisConfig{
 	// who is able to deploy smart contract from genesis block (it won't generate event log)
 	Deployers: []common.Address{},
 	// list of default validators (it won't generate event log)
-	Validators: []common.Address{
-		common.HexToAddress("0x08fae3885e299c24ff9841478eb946f41023ac69"),
-		common.HexToAddress("0x751aaca849b09a3e347bbfe125cf18423cc24b40"),
-		common.HexToAddress("0xa6ff33e3250cc765052ac9d7f7dfebda183c4b9b"),
-		common.HexToAddress("0x49c0f7c8c11a4c80dc6449efe1010bb166818da8"),
-		common.HexToAddress("0x8e1ea6eaa09c3b40f4a51fcd056a031870a0549a"),
+	Validators: []validatorConfig{
+		{Address: common.HexToAddress("0x08fae3885e299c24ff9841478eb946f41023ac69")},
+		{Address: common.HexToAddress("0x751aaca849b09a3e347bbfe125cf18423cc24b40")},
+		{Address: common.HexToAddress("0xa6ff33e3250cc765052ac9d7f7dfebda183c4b9b")},
+		{Address: common.HexToAddress("0x49c0f7c8c11a4c80dc6449efe1010bb166818da8")},
+		{Address: common.HexToAddress("0x8e1ea6eaa09c3b40f4a51fcd056a031870a0549a")},
 	},
-	SystemTreasury: common.HexToAddress(""),
+	SystemTreasury: singleTreasury(common.HexToAddress("")),
 	ConsensusParams: consensusParams{
 		ActiveValidatorsLength:   25,   // suggested values are (3k+1, where k is honest validators, even better): 7, 13, 19, 25, 31...
 		EpochBlockInterval:       1200, // better to use 1 day epoch (86400/3=28800, where 3s is block time)
@@ -394,54 +1093,361 @@ var devNetConfig = genesisConfig{
 		ValidatorJailEpochLength: 7,    // how many epochs validator should stay in jail (7 epochs = ~7 days)
 		UndelegatePeriod:         6,    // allow claiming funds only after 6 epochs (~7 days)
 
-		MinValidatorStakeAmount: (*math.HexOrDecimal256)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // how many tokens validator must stake to create a validator (in ether)
-		MinStakingAmount:        (*math.HexOrDecimal256)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // minimum staking amount for delegators (in ether)
+		MinValidatorStakeAmount: (*amountValue)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // how many tokens validator must stake to create a validator (in ether)
+		MinStakingAmount:        (*amountValue)(hexutil.MustDecodeBig("0xde0b6b3a7640000")), // minimum staking amount for delegators (in ether)
+		BlockPeriodSeconds:      3,                                                          // block time in seconds (Parlia period)
 	},
-	InitialStakes: map[common.Address]string{
-		common.HexToAddress("0x08fae3885e299c24ff9841478eb946f41023ac69"): "0x3635c9adc5dea00000", // 1000 eth
-		common.HexToAddress("0x751aaca849b09a3e347bbfe125cf18423cc24b40"): "0x3635c9adc5dea00000", // 1000 eth
-		common.HexToAddress("0xa6ff33e3250cc765052ac9d7f7dfebda183c4b9b"): "0x3635c9adc5dea00000", // 1000 eth
-		common.HexToAddress("0x49c0f7c8c11a4c80dc6449efe1010bb166818da8"): "0x3635c9adc5dea00000", // 1000 eth
-		common.HexToAddress("0x8e1ea6eaa09c3b40f4a51fcd056a031870a0549a"): "0x3635c9adc5dea00000", // 1000 eth
+	InitialStakes: map[common.Address]*amountValue{
+		common.HexToAddress("0x08fae3885e299c24ff9841478eb946f41023ac69"): (*amountValue)(hexutil.MustDecodeBig("0x3635c9adc5dea00000")), // 1000 eth
+		common.HexToAddress("0x751aaca849b09a3e347bbfe125cf18423cc24b40"): (*amountValue)(hexutil.MustDecodeBig("0x3635c9adc5dea00000")), // 1000 eth
+		common.HexToAddress("0xa6ff33e3250cc765052ac9d7f7dfebda183c4b9b"): (*amountValue)(hexutil.MustDecodeBig("0x3635c9adc5dea00000")), // 1000 eth
+		common.HexToAddress("0x49c0f7c8c11a4c80dc6449efe1010bb166818da8"): (*amountValue)(hexutil.MustDecodeBig("0x3635c9adc5dea00000")), // 1000 eth
+		common.HexToAddress("0x8e1ea6eaa09c3b40f4a51fcd056a031870a0549a"): (*amountValue)(hexutil.MustDecodeBig("0x3635c9adc5dea00000")), // 1000 eth
 	},
 	// owner of the governance
 	VotingPeriod: 60, // 3 minutes
 	// faucet
-	Faucet: map[common.Address]string{
-		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"): "0x21e19e0c9bab2400000",    // governance
-		common.HexToAddress("0xb891fe7b38f857f53a7b5529204c58d5c487280b"): "0x52b7d2dcc80cd2e4000000", // faucet (10kk)
+	Faucet: map[common.Address]*amountValue{
+		common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"): (*amountValue)(hexutil.MustDecodeBig("0x21e19e0c9bab2400000")),    // governance
+		common.HexToAddress("0xb891fe7b38f857f53a7b5529204c58d5c487280b"): (*amountValue)(hexutil.MustDecodeBig("0x52b7d2dcc80cd2e4000000")), // faucet (10kk)
 	},
 }
 
-func main() {
-	args := os.Args[1:]
+// readGenesisConfig parses config file, ${NAME} placeholders are filled from vars or environment, unchecksummed
+// (all lower or upper case) addresses are rejected unless allowed
+func readGenesisConfig(path string, allowUnchecksummed bool, vars map[string]string) (*genesisConfig, error) {
+	template, err := readConfigTemplate(path)
+	if err != nil {
+		return nil, err
+	}
+	return genesisConfigFromTemplate(template, path, allowUnchecksummed, vars)
+}
+
+// readConfigTemplate reads config file, "-" is stdin
+func readConfigTemplate(path string) ([]byte, error) {
+	if path == "-" {
+		return io.ReadAll(os.Stdin)
+	}
+	return os.ReadFile(path)
+}
+
+// genesisConfigFromTemplate is readGenesisConfig of already read file, keystores of validators are resolved
+// relative to the file (working directory for stdin)
+func genesisConfigFromTemplate(template []byte, path string, allowUnchecksummed bool, vars map[string]string) (*genesisConfig, error) {
+	config, err := parseGenesisConfig(template, allowUnchecksummed, vars)
+	if err != nil {
+		return nil, err
+	}
+	if err := config.resolveValidatorKeystores(filepath.Dir(path)); err != nil {
+		return nil, err
+	}
+	return config, nil
+}
+
+// parseGenesisConfig is readGenesisConfig without the filesystem, keystores of validators aren't resolved
+func parseGenesisConfig(template []byte, allowUnchecksummed bool, vars map[string]string) (*genesisConfig, error) {
+	fileContents, err := expandConfigVariables(template, vars)
+	if err != nil {
+		return nil, err
+	}
+	return decodeGenesisConfig(fileContents, allowUnchecksummed)
+}
+
+// decodeGenesisConfig is parseGenesisConfig of config with already expanded placeholders
+func decodeGenesisConfig(fileContents []byte, allowUnchecksummed bool) (*genesisConfig, error) {
+	if err := checkConfigAddresses(fileContents, allowUnchecksummed); err != nil {
+		return nil, err
+	}
+	fileContents, err := migrateConfig(fileContents)
+	if err != nil {
+		return nil, err
+	}
+	if fileContents, err = resolveConfigDurations(fileContents); err != nil {
+		return nil, err
+	}
+	config := &genesisConfig{}
+	if err := json.Unmarshal(fileContents, config); err != nil {
+		return nil, err
+	}
+	return config, nil
+}
+
+func runGenCommand(args []string) error {
 	if len(args) > 0 {
-		fileContents, err := os.ReadFile(args[0])
+		switch args[0] {
+		case "keys":
+			return runGenKeysCommand(args[1:])
+		case "devnet":
+			return runGenDevnetCommand(args[1:])
+		case "call":
+			return runGenCallCommand(args[1:])
+		case "batch":
+			return runGenBatchCommand(args[1:])
+		case "verify":
+			return runGenVerifyCommand(args[1:])
+		case "import-state":
+			return runGenImportStateCommand(args[1:])
+		case "snapshot":
+			return runGenSnapshotCommand(args[1:])
+		case "create2":
+			return runGenCreate2Command(args[1:])
+		case "compile":
+			return runGenCompileCommand(args[1:])
+		case "schema":
+			return runGenSchemaCommand(args[1:])
+		case "serve":
+			return runGenServeCommand(args[1:])
+		case "init":
+			return runGenInitCommand(args[1:])
+		case "genesis":
+			// explicit name of the default command, e.g. gen genesis --config - --output -
+			args = args[1:]
+		}
+	}
+	flags := flag.NewFlagSet("gen", flag.ContinueOnError)
+	networkName := flags.String("network", "", "name of the network profile registered with \"networks add\"")
+	configPath := flags.String("config", "", "path to genesis config file, - reads it from stdin")
+	varsPath := flags.String("vars", "", "JSON file with values of ${NAME} placeholders in the config (missing ones are taken from environment)")
+	artifactsPath := flags.String("artifacts", "", "path to directory with contract artifacts (default is embedded)")
+	outputFile := flags.String("output", "", "output file, .gz suffix enables gzip compression, - or default is stdout")
+	specFile := flags.String("spec", "", "write canonical genesis spec with per-section hashes to the file")
+	reportFile := flags.String("report", "", "write deployment report to the file (default is genesis-report.<output-format> next to the output file)")
+	transactionsFile := flags.String("transactions", "", "write system transactions that deploy and initialize system contracts at block 1 to the file")
+	format := flags.String("format", "geth", "genesis layout: "+strings.Join(genesisFormatNames(), ", "))
+	outputFormat := flags.String("output-format", "json", "serialization of the genesis and report: "+strings.Join(outputFormatNames(), ", "))
+	predeploy := flags.String("predeploy", "", "comma separated utility contracts to predeploy: "+strings.Join(predeployNames(), ", "))
+	maxCodeSize := flags.Int("max-code-size", params.MaxCodeSize, "max size of contract code in genesis")
+	allowOversize := flags.Bool("allow-oversize", false, "warn instead of failing when contract code exceeds max code size")
+	allowCodeMismatch := flags.Bool("allow-code-mismatch", false, "warn instead of failing when system contract code differs from deployed bytecode of its artifact")
+	patchFile := flags.String("patch", "", "JSON document deep-merged over the generated genesis")
+	allowUnchecksummed := flags.Bool("allow-unchecksummed", false, "accept all lower case addresses in config without EIP-55 checksum")
+	airdropFile := flags.String("airdrop", "", "CSV file with address,amount rows to add to genesis balances")
+	lockFile := flags.String("lock", "", "lockfile with exact generation inputs (default is genesis.lock next to the output file, none for stdin config or - output)")
+	frozen := flags.Bool("frozen", false, "fail if inputs don't match the lockfile exactly")
+	dryRun := flags.Bool("dry-run", false, "validate and simulate, print deployments and funded accounts without writing files")
+	cacheDir := flags.String("cache-dir", "", "directory to cache simulated constructors in, unchanged contracts aren't simulated again")
+	traceDir := flags.String("trace", "", "directory to write call traces (callTracer JSON) of every simulated system contract to")
+	signKey := flags.String("sign-key", "", "hex private key or keystore file to sign the canonical genesis JSON with (writes <output>.sig)")
+	signPassword := flags.String("sign-password", "", "password file of the signing keystore")
+	prune := flags.Bool("prune", false, "strip zero storage values and empty accounts (EIP-161) from alloc")
+	codeMetadata := flags.String("code-metadata", codeMetadataKeep, "solc metadata at the end of deployed code: "+strings.Join(codeMetadataModes, ", ")+" (zero keeps code size)")
+	meterGas := flags.Bool("meter-gas", false, "simulate with the genesis block gas limit and fail if system contract init calls don't fit into block 1")
+	parallel := flags.Int("parallel", 0, "max number of independent system contracts simulated concurrently (0 is number of CPUs, 1 disables it)")
+	overrides := registerConfigOverrides(flags)
+	applyLoggingFlags := registerLoggingFlags(flags)
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if err := applyLoggingFlags(); err != nil {
+		return err
+	}
+	if *networkName != "" {
+		profile, err := lookupNetworkProfile(*networkName)
 		if err != nil {
-			panic(err)
+			return err
+		}
+		// explicit flags have priority over the profile
+		if *configPath == "" {
+			*configPath = profile.Config
+		}
+		if *artifactsPath == "" {
+			*artifactsPath = profile.Artifacts
+		}
+		if *outputFile == "" {
+			*outputFile = profile.Output
+		}
+	}
+	if *configPath == "" {
+		return fmt.Errorf("either --network or --config must be specified")
+	}
+	// with stdin config or stdout genesis nothing is written to the working directory unless asked by flags, so
+	// the command runs in containers without writable volumes
+	singleShot := *configPath == "-" || *outputFile == "-"
+	if *outputFile == "" || *outputFile == "-" {
+		*outputFile = "stdout"
+	}
+	if singleShot && *frozen && *lockFile == "" {
+		return fmt.Errorf("--frozen with stdin config or stdout output requires --lock")
+	}
+	var signingKey *ecdsa.PrivateKey
+	if *signKey != "" {
+		if *outputFile == "stdout" || *outputFile == "stderr" {
+			return fmt.Errorf("--sign-key requires --output file")
 		}
-		genesis := &genesisConfig{}
-		err = json.Unmarshal(fileContents, genesis)
+		key, err := loadSigningKey(*signKey, *signPassword)
 		if err != nil {
-			panic(err)
+			return err
 		}
-		outputFile := "stdout"
-		if len(args) > 1 {
-			outputFile = args[1]
+		signingKey = key
+	}
+	phase := startPhase("parsing")
+	vars, err := readConfigVariables(*varsPath)
+	if err != nil {
+		return err
+	}
+	// stdin can be read only once, so the same template is parsed and locked
+	rawTemplate, err := readConfigTemplate(*configPath)
+	if err != nil {
+		return err
+	}
+	config, err := genesisConfigFromTemplate(rawTemplate, *configPath, *allowUnchecksummed, vars)
+	if err != nil {
+		return err
+	}
+	appliedOverrides, err := applyConfigOverrides(config, overrides)
+	if err != nil {
+		return err
+	}
+	// "now" is resolved once, so the lockfile and provenance record the timestamp that is actually in genesis
+	if resolved, err := config.resolveTimestampNow(*frozen); err != nil {
+		return err
+	} else if resolved != "" {
+		appliedOverrides["timestamp"] = resolved
+	}
+	artifacts, err := loadArtifactSet(*artifactsPath)
+	if err != nil {
+		return err
+	}
+	storageLayouts, err := loadStorageLayouts(artifacts, *artifactsPath)
+	if err != nil {
+		return err
+	}
+	phase.finish()
+	if *reportFile == "" && *outputFile != "stdout" && *outputFile != "stderr" {
+		*reportFile = filepath.Join(filepath.Dir(*outputFile), "genesis-report."+*outputFormat)
+	}
+	if *lockFile == "" && !singleShot {
+		*lockFile = "genesis.lock"
+		if *outputFile != "stdout" && *outputFile != "stderr" {
+			*lockFile = filepath.Join(filepath.Dir(*outputFile), "genesis.lock")
+		}
+	}
+	predeploys, err := parsePredeployList(*predeploy)
+	if err != nil {
+		return err
+	}
+	if !isCodeMetadataMode(*codeMetadata) {
+		return fmt.Errorf("unknown code metadata mode %q, available are: %s", *codeMetadata, strings.Join(codeMetadataModes, ", "))
+	}
+	// rendered config is locked, so changed variables are detected as well
+	rawConfig, err := expandConfigVariables(rawTemplate, vars)
+	if err != nil {
+		return err
+	}
+	lock := newGenesisLock(rawConfig, artifacts, map[string]string{
+		"network":        *networkName,
+		"config":         *configPath,
+		"vars":           *varsPath,
+		"artifacts":      *artifactsPath,
+		"output":         *outputFile,
+		"spec":           *specFile,
+		"report":         *reportFile,
+		"format":         *format,
+		"output-format":  *outputFormat,
+		"predeploy":      *predeploy,
+		"max-code-size":  strconv.Itoa(*maxCodeSize),
+		"allow-oversize": strconv.FormatBool(*allowOversize),
+		"patch":          *patchFile,
+		"airdrop":        *airdropFile,
+	})
+	// pruning changes the alloc, it's locked only when enabled, so existing lockfiles stay valid
+	if *prune {
+		lock.Flags["prune"] = "true"
+	}
+	if *meterGas {
+		lock.Flags["meter-gas"] = "true"
+	}
+	if *allowCodeMismatch {
+		lock.Flags["allow-code-mismatch"] = "true"
+	}
+	if *codeMetadata != codeMetadataKeep {
+		lock.Flags["code-metadata"] = *codeMetadata
+	}
+	// overrides change the config, so they are locked as flags regardless of the source (env or flag)
+	for name, value := range appliedOverrides {
+		lock.Flags[name] = value
+	}
+	// input files passed with flags are locked by content as well
+	for flagName, path := range map[string]string{"patch": *patchFile, "airdrop": *airdropFile} {
+		if path == "" {
+			continue
 		}
-		err = createGenesisConfig(*genesis, outputFile)
+		data, err := os.ReadFile(path)
 		if err != nil {
-			panic(err)
+			return err
 		}
-		return
+		lock.Flags[flagName+"-sha256"] = fmt.Sprintf("%x", sha256.Sum256(data))
 	}
-	fmt.Printf("building local net\n")
-	if err := createGenesisConfig(localNetConfig, "localnet.json"); err != nil {
-		panic(err)
+	if *frozen {
+		if err := verifyGenesisLock(*lockFile, lock); err != nil {
+			return err
+		}
+	}
+	err = createGenesisConfig(*config, *outputFile, &generateOptions{
+		Artifacts:         artifacts,
+		SpecFile:          *specFile,
+		ReportFile:        *reportFile,
+		Format:            *format,
+		OutputFormat:      *outputFormat,
+		Predeploys:        predeploys,
+		MaxCodeSize:       *maxCodeSize,
+		AllowOversize:     *allowOversize,
+		AllowCodeMismatch: *allowCodeMismatch,
+		PatchFile:         *patchFile,
+		AirdropFile:       *airdropFile,
+		DryRun:            *dryRun,
+		Parallelism:       *parallel,
+		CacheDir:          *cacheDir,
+		TraceDir:          *traceDir,
+		StorageLayouts:    storageLayouts,
+		Prune:             *prune,
+		MeterGas:          *meterGas,
+		CodeMetadata:      *codeMetadata,
+		TransactionsFile:  *transactionsFile,
+	})
+	if err != nil || *dryRun {
+		return err
+	}
+	if signingKey != nil {
+		if err := signGenesis(*outputFile, signingKey); err != nil {
+			return err
+		}
+	}
+	// checksum and provenance let validators verify which inputs produced the genesis they boot
+	if err := writeGenesisAttestation(*outputFile, lock); err != nil {
+		return err
 	}
-	fmt.Printf("\nbuilding dev net\n")
-	if err := createGenesisConfig(devNetConfig, "devnet.json"); err != nil {
-		panic(err)
+	if *frozen || *lockFile == "" {
+		return nil
+	}
+	return lock.writeTo(*lockFile)
+}
+
+func runCommand(args []string) error {
+	if len(args) > 0 {
+		switch args[0] {
+		case "gen":
+			return runGenCommand(args[1:])
+		case "networks":
+			return runNetworksCommand(args[1:])
+		case "inspect":
+			return runInspectCommand(args[1:])
+		}
+		// legacy mode has no flags, so only wrong checksums are rejected there
+		config, err := readGenesisConfig(args[0], true, nil)
+		if err != nil {
+			return err
+		}
+		outputFile := "stdout"
+		if len(args) > 1 {
+			outputFile = args[1]
+		}
+		return createGenesisConfig(*config, outputFile, defaultGenerateOptions())
+	}
+	log.Info("Building local net")
+	if err := createGenesisConfig(localNetConfig, "localnet.json", defaultGenerateOptions()); err != nil {
+		return err
 	}
-	fmt.Printf("\n")
+	log.Info("Building dev net")
+	return createGenesisConfig(devNetConfig, "devnet.json", defaultGenerateOptions())
 }
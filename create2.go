@@ -0,0 +1,112 @@
+package main
+
+import (
+	"encoding/json"
+	"flag"
+	"fmt"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+// defaultCreate2Deployer is the deterministic deployment proxy, most networks have it at the same address
+var defaultCreate2Deployer = predeployCatalog["create2deployer"].address
+
+// create2Address returns the address CREATE2 of the init code with the salt by the deployer results in
+func create2Address(deployer common.Address, salt common.Hash, initCode []byte) common.Address {
+	return crypto.CreateAddress2(deployer, salt, crypto.Keccak256(initCode))
+}
+
+func (c extraContractConfig) create2Deployer() common.Address {
+	if c.Create2Deployer != nil {
+		return *c.Create2Deployer
+	}
+	return defaultCreate2Deployer
+}
+
+// resolveCreate2Addresses sets addresses of extra contracts with salt, explicit address must match the computed
+// one, so config can pin the expected address
+func (c *genesisConfig) resolveCreate2Addresses() error {
+	var violations configViolations
+	contracts := make([]extraContractConfig, len(c.ExtraContracts))
+	copy(contracts, c.ExtraContracts)
+	for i := range contracts {
+		contract, path := &contracts[i], fmt.Sprintf("extraContracts[%d]", i)
+		if contract.Salt == nil {
+			if contract.Create2Deployer != nil {
+				violations.add(path+".create2Deployer", "requires salt")
+			}
+			continue
+		}
+		initCode, err := contract.initCode()
+		if err != nil {
+			violations.add(path+".artifact", "%s", err)
+			continue
+		}
+		address := create2Address(contract.create2Deployer(), *contract.Salt, initCode)
+		if contract.Address != (common.Address{}) && contract.Address != address {
+			violations.add(path+".address", "is %s, but CREATE2 with the salt results in %s", contract.Address.Hex(), address.Hex())
+			continue
+		}
+		contract.Address = address
+	}
+	if len(violations) > 0 {
+		return violations
+	}
+	c.ExtraContracts = contracts
+	return nil
+}
+
+// runGenCreate2Command prints CREATE2 address of init code (raw or built from artifact and ctor arguments)
+func runGenCreate2Command(args []string) error {
+	flags := flag.NewFlagSet("gen create2", flag.ContinueOnError)
+	salt := flags.String("salt", "", "hex encoded salt, shorter values are left padded to 32 bytes")
+	deployer := flags.String("deployer", defaultCreate2Deployer.Hex(), "address of the contract executing CREATE2")
+	initCodeHex := flags.String("init-code", "", "hex encoded init code")
+	artifact := flags.String("artifact", "", "path to truffle/hardhat artifact or to the file with hex encoded bytecode")
+	ctorArgs := flags.String("args", "", "JSON array or object with constructor arguments of the artifact")
+	jsonOutput := flags.Bool("json", false, "print result as JSON")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if *salt == "" || (*initCodeHex == "") == (*artifact == "") || flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen create2 --salt <hex> (--init-code <hex> | --artifact <file> [--args <json>]) [--deployer <address>] [--json]")
+	}
+	if !common.IsHexAddress(*deployer) {
+		return fmt.Errorf("invalid deployer address: %s", *deployer)
+	}
+	saltBytes, err := hexutil.Decode(*salt)
+	if err != nil || len(saltBytes) > common.HashLength {
+		return fmt.Errorf("invalid salt: %s", *salt)
+	}
+	var initCode []byte
+	if *initCodeHex != "" {
+		if initCode, err = hexutil.Decode(*initCodeHex); err != nil {
+			return fmt.Errorf("invalid init code: %w", err)
+		}
+	} else {
+		contract := extraContractConfig{Artifact: *artifact}
+		if strings.TrimSpace(*ctorArgs) != "" {
+			contract.ConstructorArgs = json.RawMessage(*ctorArgs)
+		}
+		if initCode, err = contract.initCode(); err != nil {
+			return err
+		}
+	}
+	saltHash := common.BytesToHash(saltBytes)
+	address := create2Address(common.HexToAddress(*deployer), saltHash, initCode)
+	if *jsonOutput {
+		data, _ := json.MarshalIndent(struct {
+			Address      common.Address `json:"address"`
+			Deployer     common.Address `json:"deployer"`
+			Salt         common.Hash    `json:"salt"`
+			InitCodeHash common.Hash    `json:"initCodeHash"`
+		}{address, common.HexToAddress(*deployer), saltHash, crypto.Keccak256Hash(initCode)}, "", "  ")
+		fmt.Println(string(data))
+		return nil
+	}
+	fmt.Println(address.Hex())
+	return nil
+}
@@ -0,0 +1,40 @@
+package main
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestCreate2Address(t *testing.T) {
+	// first example of EIP-1014
+	address := create2Address(common.Address{}, common.Hash{}, []byte{0x00})
+	if address != common.HexToAddress("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38") {
+		t.Fatalf("wrong address %s", address.Hex())
+	}
+}
+
+func TestResolveCreate2Addresses(t *testing.T) {
+	artifact := filepath.Join(t.TempDir(), "code.hex")
+	if err := os.WriteFile(artifact, []byte("0xdeadbeef\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	salt := common.HexToHash("0x01")
+	expected := create2Address(defaultCreate2Deployer, salt, []byte{0xde, 0xad, 0xbe, 0xef})
+	config := &genesisConfig{ExtraContracts: []extraContractConfig{{Artifact: artifact, Salt: &salt}}}
+	if err := config.resolveCreate2Addresses(); err != nil {
+		t.Fatal(err)
+	}
+	if config.ExtraContracts[0].Address != expected {
+		t.Fatalf("address is %s, expected %s", config.ExtraContracts[0].Address.Hex(), expected.Hex())
+	}
+	// pinned address must match the computed one
+	config.ExtraContracts[0].Address = common.HexToAddress("0x0000000000000000000000000000000000000001")
+	var violations configViolations
+	if err := config.resolveCreate2Addresses(); !errors.As(err, &violations) || violations[0].Path != "extraContracts[0].address" {
+		t.Fatalf("expected address violation, got %v", err)
+	}
+}
@@ -0,0 +1,78 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// immutableReference is a range of deployed bytecode filled by the constructor (solc's immutableReferences)
+type immutableReference struct {
+	Start  int `json:"start"`
+	Length int `json:"length"`
+}
+
+// deployedCodeArtifact has fields of the artifact needed to compare deployed code, truffle keeps immutable
+// references at the top level
+type deployedCodeArtifact struct {
+	DeployedBytecode    string                          `json:"deployedBytecode"`
+	ImmutableReferences map[string][]immutableReference `json:"immutableReferences"`
+}
+
+// compareDeployedCode returns description of the first difference between code in alloc and the artifact's
+// deployed bytecode, values of immutable variables are ignored, because only the constructor knows them
+func compareDeployedCode(rawArtifact []byte, code []byte) (string, error) {
+	artifact := &deployedCodeArtifact{}
+	if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+		return "", err
+	}
+	if artifact.DeployedBytecode == "" || artifact.DeployedBytecode == "0x" {
+		return "", nil
+	}
+	expected, err := hexutil.Decode(artifact.DeployedBytecode)
+	if err != nil {
+		return "", fmt.Errorf("invalid deployed bytecode: %w", err)
+	}
+	if len(expected) != len(code) {
+		return fmt.Sprintf("code is %d bytes, artifact's deployed bytecode is %d bytes", len(code), len(expected)), nil
+	}
+	for _, references := range artifact.ImmutableReferences {
+		for _, reference := range references {
+			if reference.Start < 0 || reference.Length < 0 || reference.Start+reference.Length > len(expected) {
+				return "", fmt.Errorf("immutable reference %d+%d is out of bytecode", reference.Start, reference.Length)
+			}
+			copy(expected[reference.Start:reference.Start+reference.Length], code[reference.Start:reference.Start+reference.Length])
+		}
+	}
+	for i := range expected {
+		if expected[i] != code[i] {
+			return fmt.Sprintf("code differs from artifact's deployed bytecode at offset %d", i), nil
+		}
+	}
+	return "", nil
+}
+
+// verifyDeployedCode compares code of deployed system contracts with their artifacts, mismatch means that ctor
+// modifies the code or artifact's ABI and bytecode come from different builds, it's an error unless allowed
+func verifyDeployedCode(genesis *core.Genesis, deployments []*systemContractDeployment, artifacts artifactSet, allowMismatch bool) (warnings []string, err error) {
+	var problems []string
+	for _, deployment := range deployments {
+		rawArtifact, ok := artifacts[deployment.name]
+		if !ok {
+			continue
+		}
+		mismatch, err := compareDeployedCode(rawArtifact, genesis.Alloc[deployment.address].Code)
+		if err != nil {
+			return nil, fmt.Errorf("artifact of %s: %w", deployment.name, err)
+		}
+		if mismatch != "" {
+			problems = append(problems, fmt.Sprintf("%s (%s): %s", deployment.name, deployment.address.Hex(), mismatch))
+		}
+	}
+	if len(problems) > 0 && !allowMismatch {
+		return nil, fmt.Errorf("%s (use --allow-code-mismatch to ignore)", problems[0])
+	}
+	return problems, nil
+}
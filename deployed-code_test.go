@@ -0,0 +1,30 @@
+package main
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+func TestCompareDeployedCode(t *testing.T) {
+	// immutable is 4 bytes at offset 2
+	artifact := []byte(`{"deployedBytecode": "0x6080000000005b00", "immutableReferences": {"12": [{"start": 2, "length": 4}]}}`)
+	if mismatch, err := compareDeployedCode(artifact, hexutil.MustDecode("0x6080deadbeef5b00")); err != nil || mismatch != "" {
+		t.Fatalf("immutable value is reported as mismatch: %q (%v)", mismatch, err)
+	}
+	if mismatch, _ := compareDeployedCode(artifact, hexutil.MustDecode("0x6080deadbeef5b01")); !strings.Contains(mismatch, "offset 7") {
+		t.Fatalf("unexpected mismatch %q", mismatch)
+	}
+	if mismatch, _ := compareDeployedCode(artifact, hexutil.MustDecode("0x6080")); !strings.Contains(mismatch, "2 bytes") {
+		t.Fatalf("unexpected mismatch %q", mismatch)
+	}
+}
+
+func TestEmbeddedArtifactsMatchDeployedCode(t *testing.T) {
+	// generation fails if code of any system contract differs from its artifact
+	if err := createGenesisConfig(devNetConfig, filepath.Join(t.TempDir(), "genesis.json"), defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+}
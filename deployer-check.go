@@ -0,0 +1,50 @@
+package main
+
+import (
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/core/vm"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+// verifyDeployerProxyGating simulates contract registration from the whitelisted deployers and from
+// a non-whitelisted account against generated state, the same way as node does it for each new contract
+func verifyDeployerProxyGating(genesis *core.Genesis, deployers []common.Address) error {
+	statedb, err := newStateFromGenesis(genesis)
+	if err != nil {
+		return err
+	}
+	evm := newGenesisEVM(genesis, statedb)
+	// system contracts are initialized by consensus engine in the first block, lets do the same
+	if err := initSystemContracts(evm); err != nil {
+		return err
+	}
+	registerDeployedContract := func(deployer common.Address) ([]byte, error) {
+		input, err := newArguments("address", "address").Pack(deployer, crypto.CreateAddress(deployer, 0))
+		if err != nil {
+			return nil, err
+		}
+		input = append(crypto.Keccak256([]byte("registerDeployedContract(address,address)"))[:4], input...)
+		// registration is allowed only from coinbase that is zero address in simulated block
+		result, _, err := evm.Call(vm.AccountRef(common.Address{}), deployerProxyAddress, input, 10_000_000, big.NewInt(0))
+		return result, err
+	}
+	whitelisted := make(map[common.Address]bool)
+	for _, deployer := range deployers {
+		whitelisted[deployer] = true
+		if result, err := registerDeployedContract(deployer); err != nil {
+			return fmt.Errorf("deployer proxy rejects whitelisted deployer %s: %w (%s)", deployer.Hex(), err, revertReason(result))
+		}
+	}
+	nonWhitelisted := common.BytesToAddress(crypto.Keccak256([]byte("non-whitelisted deployer")))
+	if whitelisted[nonWhitelisted] {
+		return nil
+	}
+	if _, err := registerDeployedContract(nonWhitelisted); err == nil {
+		return fmt.Errorf("deployer proxy permits non-whitelisted deployer %s", nonWhitelisted.Hex())
+	}
+	return nil
+}
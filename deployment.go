@@ -0,0 +1,135 @@
+package main
+
+import (
+	"fmt"
+	"math/big"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+// systemContractDeployment describes one system contract that must be simulated and placed into genesis alloc
+type systemContractDeployment struct {
+	name      string
+	address   common.Address
+	typeNames []string
+	params    []interface{}
+	// initial contract balance, it's available for the contract while ctor is executed
+	balance *big.Int
+	// contracts that must be deployed and initialized before this one, because its ctor calls them
+	dependsOn []common.Address
+	// calls executed right after deployment, their state changes go to genesis alloc
+	followUpCalls []followUpCall
+}
+
+func (d *systemContractDeployment) initialBalance() *big.Int {
+	if d.balance == nil {
+		return big.NewInt(0)
+	}
+	return d.balance
+}
+
+// followUpCall is a call to the freshly deployed contract, it's executed before init, so it must not
+// touch state that init function expects to be empty
+type followUpCall struct {
+	from  common.Address
+	input []byte
+	value *big.Int
+}
+
+// orderDeployments sorts deployments in a way that each contract is deployed after all its dependencies,
+// contracts without dependencies between each other keep declaration order, so the result is deterministic
+func orderDeployments(deployments []*systemContractDeployment) ([]*systemContractDeployment, error) {
+	byAddress := make(map[common.Address]*systemContractDeployment)
+	for _, d := range deployments {
+		if _, ok := byAddress[d.address]; ok {
+			return nil, fmt.Errorf("contract %s is declared twice (%s)", d.address.Hex(), d.name)
+		}
+		byAddress[d.address] = d
+	}
+	for _, d := range deployments {
+		for _, dep := range d.dependsOn {
+			if _, ok := byAddress[dep]; !ok {
+				return nil, fmt.Errorf("%s depends on unknown contract %s", d.name, dep.Hex())
+			}
+		}
+	}
+	var result []*systemContractDeployment
+	deployed := make(map[common.Address]bool)
+	for len(result) < len(deployments) {
+		progress := false
+		for _, d := range deployments {
+			if deployed[d.address] {
+				continue
+			}
+			ready := true
+			for _, dep := range d.dependsOn {
+				if !deployed[dep] {
+					ready = false
+					break
+				}
+			}
+			if !ready {
+				continue
+			}
+			deployed[d.address] = true
+			result = append(result, d)
+			progress = true
+		}
+		if !progress {
+			var cycle []string
+			for _, d := range deployments {
+				if !deployed[d.address] {
+					cycle = append(cycle, d.name)
+				}
+			}
+			return nil, fmt.Errorf("cyclic dependency between system contracts: %v", cycle)
+		}
+	}
+	return result, nil
+}
+
+// withFollowUpCalls attaches configured follow-up calls to deployments (contract names are validated by config)
+func withFollowUpCalls(deployments []*systemContractDeployment, calls []followUpCallConfig) []*systemContractDeployment {
+	for _, call := range calls {
+		for _, d := range deployments {
+			if !strings.EqualFold(d.name, call.Contract) {
+				continue
+			}
+			d.followUpCalls = append(d.followUpCalls, followUpCall{from: call.From, input: call.Data, value: (*big.Int)(call.Value)})
+		}
+	}
+	return deployments
+}
+
+// deploymentFailure is a failed contract deployment with the reason
+type deploymentFailure struct {
+	Contract string
+	Address  common.Address
+	Cause    error
+}
+
+// deploymentFailures collects failures of all deployments, so they can be fixed in one pass
+type deploymentFailures []deploymentFailure
+
+func (f deploymentFailures) Error() string {
+	lines := []string{fmt.Sprintf("failed to deploy %d contract(s):", len(f))}
+	for _, failure := range f {
+		lines = append(lines, fmt.Sprintf("  %s (%s): %s", failure.Contract, failure.Address.Hex(), failure.Cause))
+	}
+	return strings.Join(lines, "\n")
+}
+
+func (f *deploymentFailures) add(contract string, address common.Address, cause error) {
+	*f = append(*f, deploymentFailure{Contract: contract, Address: address, Cause: cause})
+}
+
+// failedDependency returns name of the failed contract the deployment depends on
+func failedDependency(deployment *systemContractDeployment, failed map[common.Address]string) string {
+	for _, dependency := range deployment.dependsOn {
+		if name, ok := failed[dependency]; ok {
+			return name
+		}
+	}
+	return ""
+}
@@ -0,0 +1,99 @@
+package main
+
+import (
+	"crypto/rand"
+	"encoding/hex"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// devnetPeers returns static-nodes.json content of each node (all nodes except itself), nodes listen on the
+// same host with consecutive ports, operator edits the addresses for multi-host setups
+func devnetPeers(validators []*generatedValidator, host string, basePort int) [][]string {
+	var result [][]string
+	for i := range validators {
+		peers := []string{}
+		for j, peer := range validators {
+			if i != j {
+				peers = append(peers, fmt.Sprintf("enode://%s@%s:%d", peer.NodeId, host, basePort+j))
+			}
+		}
+		result = append(result, peers)
+	}
+	return result
+}
+
+func runGenDevnetCommand(args []string) error {
+	flags := flag.NewFlagSet("gen devnet", flag.ContinueOnError)
+	nodes := flags.Int("nodes", 4, "number of validator nodes")
+	outputDir := flags.String("out", "devnet", "output directory, each node gets node<N> data directory")
+	passwordFile := flags.String("password", "", "file with keystore password(s) (default is random password written to password.txt)")
+	withBls := flags.Bool("bls", false, "generate BLS vote keys as well")
+	chainId := flags.Int64("chain-id", localNetConfig.ChainId, "chain id of the network")
+	host := flags.String("host", "127.0.0.1", "host of the nodes in static-nodes.json")
+	basePort := flags.Int("base-port", 30311, "p2p port of the first node, next nodes use consecutive ports")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if *nodes <= 0 {
+		return fmt.Errorf("number of nodes must be positive")
+	}
+	if err := os.MkdirAll(*outputDir, 0700); err != nil {
+		return err
+	}
+	if *passwordFile == "" {
+		password := make([]byte, 16)
+		if _, err := rand.Read(password); err != nil {
+			return err
+		}
+		*passwordFile = filepath.Join(*outputDir, "password.txt")
+		if err := os.WriteFile(*passwordFile, []byte(hex.EncodeToString(password)+"\n"), 0600); err != nil {
+			return err
+		}
+	}
+	passwords, err := readPasswords(*passwordFile, *nodes)
+	if err != nil {
+		return err
+	}
+	// keystores of the devnet aren't worth strong KDF, nodes unlock them on every start
+	validators, err := generateValidators(*outputDir, *passwordFile, *nodes, *withBls, true)
+	if err != nil {
+		return err
+	}
+	rawConfig, _ := encodeJson(genesisConfigSkeleton(*chainId, validators))
+	if err := writeOutput(filepath.Join(*outputDir, "genesis-config.json"), append(rawConfig, '\n')); err != nil {
+		return err
+	}
+	config := genesisConfig{}
+	if err := json.Unmarshal(rawConfig, &config); err != nil {
+		return err
+	}
+	genesisFile := filepath.Join(*outputDir, "genesis.json")
+	if err := createGenesisConfig(config, genesisFile, defaultGenerateOptions()); err != nil {
+		return err
+	}
+	genesisData, err := os.ReadFile(genesisFile)
+	if err != nil {
+		return err
+	}
+	for i, peers := range devnetPeers(validators, *host, *basePort) {
+		validator := validators[i]
+		staticNodes, _ := json.MarshalIndent(peers, "", "  ")
+		for file, data := range map[string][]byte{
+			"genesis.json":           genesisData,
+			"geth/static-nodes.json": staticNodes,
+			"address":                []byte(validator.Address.Hex() + "\n"),
+			"password.txt":           []byte(passwords[i] + "\n"),
+		} {
+			if err := os.WriteFile(filepath.Join(validator.Dir, file), data, 0600); err != nil {
+				return err
+			}
+		}
+		fmt.Printf(" + %s: validator %s, p2p port %d\n", validator.Dir, validator.Address.Hex(), *basePort+i)
+	}
+	fmt.Printf("devnet is written to %s, init each node with: geth init --datadir <node> <node>/genesis.json\n", *outputDir)
+	return nil
+}
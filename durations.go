@@ -0,0 +1,166 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"strconv"
+	"strings"
+	"time"
+)
+
+var durationUnits = map[string]time.Duration{
+	"s": time.Second, "sec": time.Second, "second": time.Second, "seconds": time.Second,
+	"m": time.Minute, "min": time.Minute, "minute": time.Minute, "minutes": time.Minute,
+	"h": time.Hour, "hour": time.Hour, "hours": time.Hour,
+	"d": 24 * time.Hour, "day": 24 * time.Hour, "days": 24 * time.Hour,
+	"w": 7 * 24 * time.Hour, "week": 7 * 24 * time.Hour, "weeks": 7 * 24 * time.Hour,
+}
+
+// parseConfigDuration parses "<number> <unit>" (e.g. "7 days") or Go duration (e.g. "36h")
+func parseConfigDuration(value string) (time.Duration, error) {
+	value = strings.TrimSpace(value)
+	if duration, err := time.ParseDuration(value); err == nil && duration > 0 {
+		return duration, nil
+	}
+	fields := strings.Fields(value)
+	if len(fields) == 2 {
+		amount, err := strconv.ParseUint(fields[0], 10, 32)
+		unit, ok := durationUnits[strings.ToLower(fields[1])]
+		if err == nil && ok && amount > 0 {
+			return time.Duration(amount) * unit, nil
+		}
+	}
+	return 0, fmt.Errorf("invalid duration %q, expected e.g. \"7 days\", \"12 hours\" or \"90m\"", value)
+}
+
+// durationToCount converts duration into whole number of units (blocks or epochs), durations that don't fit
+// exactly are rejected, because silent rounding is exactly the unit confusion durations are meant to prevent
+func durationToCount(value string, unit time.Duration, unitName string) (uint32, error) {
+	duration, err := parseConfigDuration(value)
+	if err != nil {
+		return 0, err
+	}
+	if duration%unit != 0 {
+		return 0, fmt.Errorf("%s isn't a whole number of %ss (%s each), nearest is %d", value, unitName, unit, (duration+unit/2)/unit)
+	}
+	count := duration / unit
+	if count > 1<<32-1 {
+		return 0, fmt.Errorf("%s is too long", value)
+	}
+	return uint32(count), nil
+}
+
+// resolveConfigDurations replaces durations in epoch and voting settings of raw config with numbers of blocks
+// or epochs, durations are converted with the block period and epoch of the same config
+func resolveConfigDurations(data []byte) ([]byte, error) {
+	config := make(map[string]json.RawMessage)
+	if err := json.Unmarshal(data, &config); err != nil {
+		return nil, err
+	}
+	params := make(map[string]json.RawMessage)
+	if raw, ok := config["consensusParams"]; ok {
+		if err := json.Unmarshal(raw, &params); err != nil {
+			return nil, err
+		}
+	}
+	changed := false
+	var violations configViolations
+	resolve := func(object map[string]json.RawMessage, key, path string, unit time.Duration, unitName string) {
+		var value string
+		if json.Unmarshal(object[key], &value) != nil {
+			// absent or already a number
+			return
+		}
+		count, err := durationToCount(value, unit, unitName)
+		if err != nil {
+			violations.add(path, "%s", err)
+			return
+		}
+		object[key], changed = json.RawMessage(strconv.FormatUint(uint64(count), 10)), true
+	}
+	var period uint64
+	if raw, ok := params["blockPeriodSeconds"]; ok {
+		if err := json.Unmarshal(raw, &period); err != nil {
+			return nil, err
+		}
+	}
+	if period == 0 {
+		period = defaultBlockPeriodSeconds
+	}
+	block := time.Duration(period) * time.Second
+	resolve(params, "epochBlockInterval", "consensusParams.epochBlockInterval", block, "block")
+	resolve(config, "votingPeriod", "votingPeriod", block, "block")
+	var epoch uint32
+	if err := json.Unmarshal(params["epochBlockInterval"], &epoch); err == nil && epoch > 0 {
+		epochDuration := blocksDuration(period, uint64(epoch))
+		resolve(params, "validatorJailEpochLength", "consensusParams.validatorJailEpochLength", epochDuration, "epoch")
+		resolve(params, "undelegatePeriod", "consensusParams.undelegatePeriod", epochDuration, "epoch")
+	}
+	if len(violations) > 0 {
+		return nil, violations
+	}
+	if !changed {
+		return data, nil
+	}
+	rawParams, _ := json.Marshal(params)
+	config["consensusParams"] = rawParams
+	return json.Marshal(config)
+}
+
+// durationReport is a setting in blocks or epochs with its wall-clock equivalent
+type durationReport struct {
+	Value    uint64 `json:"value"`
+	Unit     string `json:"unit"`
+	Duration string `json:"duration"`
+}
+
+// consensusTimingReport translates block and epoch based settings into wall-clock time at the block period
+type consensusTimingReport struct {
+	BlockPeriod      string          `json:"blockPeriod"`
+	Epoch            *durationReport `json:"epoch"`
+	ValidatorJail    *durationReport `json:"validatorJail"`
+	UndelegatePeriod *durationReport `json:"undelegatePeriod"`
+	VotingPeriod     *durationReport `json:"votingPeriod"`
+}
+
+// formatWallClock renders duration in days and hours, e.g. "7d 12h", shorter durations as Go duration
+func formatWallClock(duration time.Duration) string {
+	if duration < 24*time.Hour {
+		return duration.String()
+	}
+	days, rest := duration/(24*time.Hour), duration%(24*time.Hour)
+	if rest == 0 {
+		return fmt.Sprintf("%dd", days)
+	}
+	return fmt.Sprintf("%dd %s", days, rest)
+}
+
+func newConsensusTimingReport(config genesisConfig) *consensusTimingReport {
+	params := config.ConsensusParams
+	period, err := params.blockPeriod()
+	if err != nil {
+		return nil
+	}
+	epoch := uint64(params.EpochBlockInterval)
+	newReport := func(value uint64, unit string, duration time.Duration) *durationReport {
+		return &durationReport{Value: value, Unit: unit, Duration: formatWallClock(duration)}
+	}
+	return &consensusTimingReport{
+		BlockPeriod:      formatWallClock(blocksDuration(period)),
+		Epoch:            newReport(epoch, "blocks", blocksDuration(period, epoch)),
+		ValidatorJail:    newReport(uint64(params.ValidatorJailEpochLength), "epochs", blocksDuration(period, epoch, uint64(params.ValidatorJailEpochLength))),
+		UndelegatePeriod: newReport(uint64(params.UndelegatePeriod), "epochs", blocksDuration(period, epoch, uint64(params.UndelegatePeriod))),
+		VotingPeriod:     newReport(uint64(config.VotingPeriod), "blocks", blocksDuration(period, uint64(config.VotingPeriod))),
+	}
+}
+
+func (r *consensusTimingReport) print(w io.Writer) {
+	fmt.Fprintf(w, "consensus timing (block period %s):\n", r.BlockPeriod)
+	for _, entry := range []struct {
+		name   string
+		report *durationReport
+	}{{"epoch", r.Epoch}, {"validator jail", r.ValidatorJail}, {"undelegate period", r.UndelegatePeriod}, {"voting period", r.VotingPeriod}} {
+		fmt.Fprintf(w, " - %s: %d %s = %s\n", entry.name, entry.report.Value, entry.report.Unit, entry.report.Duration)
+	}
+}
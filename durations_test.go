@@ -0,0 +1,42 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"testing"
+)
+
+func TestResolveConfigDurations(t *testing.T) {
+	raw := []byte(`{
+		"consensusParams": {"blockPeriodSeconds": 3, "epochBlockInterval": "1 day", "validatorJailEpochLength": "7 days", "undelegatePeriod": 6},
+		"votingPeriod": "3 minutes"
+	}`)
+	data, err := resolveConfigDurations(raw)
+	if err != nil {
+		t.Fatal(err)
+	}
+	config := &genesisConfig{}
+	if err := json.Unmarshal(data, config); err != nil {
+		t.Fatal(err)
+	}
+	params := config.ConsensusParams
+	if params.EpochBlockInterval != 28800 || params.ValidatorJailEpochLength != 7 || params.UndelegatePeriod != 6 || config.VotingPeriod != 60 {
+		t.Fatalf("durations are not converted: %s", data)
+	}
+	// half a day isn't a whole number of 1 day epochs
+	raw = []byte(`{"consensusParams": {"epochBlockInterval": "24h", "undelegatePeriod": "12 hours"}}`)
+	var violations configViolations
+	if _, err := resolveConfigDurations(raw); !errors.As(err, &violations) || violations[0].Path != "consensusParams.undelegatePeriod" {
+		t.Fatalf("expected undelegate period violation, got %v", err)
+	}
+}
+
+func TestConsensusTimingReport(t *testing.T) {
+	report := newConsensusTimingReport(devNetConfig)
+	if report.BlockPeriod != "3s" || report.Epoch.Duration != "1h0m0s" || report.ValidatorJail.Duration != "7h0m0s" {
+		t.Fatalf("unexpected timing %+v %+v %+v", report, report.Epoch, report.ValidatorJail)
+	}
+	if formatWallClock(36*60*60*1e9) != "1d 12h0m0s" {
+		t.Fatalf("unexpected format %s", formatWallClock(36*60*60*1e9))
+	}
+}
@@ -0,0 +1,214 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+
+	"gopkg.in/yaml.v2"
+)
+
+// outputEncoders serialize generated files, ordered values keep field order of the JSON layout
+var outputEncoders = map[string]func(value interface{}) ([]byte, error){
+	"json": encodeJson,
+	"yaml": encodeYaml,
+	"toml": encodeToml,
+}
+
+func outputFormatNames() []string {
+	var result []string
+	for name := range outputEncoders {
+		result = append(result, name)
+	}
+	sort.Strings(result)
+	return result
+}
+
+func encodeOutput(value interface{}, format string) ([]byte, error) {
+	if format == "" {
+		format = "json"
+	}
+	encoder, ok := outputEncoders[format]
+	if !ok {
+		return nil, fmt.Errorf("unknown output format %s, supported formats are: %s", format, strings.Join(outputFormatNames(), ", "))
+	}
+	return encoder(value)
+}
+
+func encodeJson(value interface{}) ([]byte, error) {
+	return json.MarshalIndent(value, "", "  ")
+}
+
+// toOrderedValue converts value into the JSON model (orderedObject, []interface{}, json.Number, string, bool or nil)
+func toOrderedValue(value interface{}) (interface{}, error) {
+	data, err := json.Marshal(value)
+	if err != nil {
+		return nil, err
+	}
+	return decodeOrderedJson(data)
+}
+
+func encodeYaml(value interface{}) ([]byte, error) {
+	ordered, err := toOrderedValue(value)
+	if err != nil {
+		return nil, err
+	}
+	return yaml.Marshal(toYamlValue(ordered))
+}
+
+func toYamlValue(value interface{}) interface{} {
+	switch v := value.(type) {
+	case orderedObject:
+		result := yaml.MapSlice{}
+		for _, field := range v {
+			result = append(result, yaml.MapItem{Key: field.Key, Value: toYamlValue(field.Value)})
+		}
+		return result
+	case []interface{}:
+		result := make([]interface{}, len(v))
+		for i, item := range v {
+			result[i] = toYamlValue(item)
+		}
+		return result
+	case json.Number:
+		if i, err := v.Int64(); err == nil {
+			return i
+		} else if u, err := strconv.ParseUint(v.String(), 10, 64); err == nil {
+			return u
+		} else if f, err := v.Float64(); err == nil && strings.ContainsAny(v.String(), ".eE") {
+			return f
+		}
+		// yaml integers are limited to 64 bits as well
+		return v.String()
+	}
+	return value
+}
+
+var tomlBareKey = regexp.MustCompile(`^[A-Za-z0-9_-]+$`)
+
+func tomlKey(key string) string {
+	if tomlBareKey.MatchString(key) {
+		return key
+	}
+	quoted, _ := json.Marshal(key)
+	return string(quoted)
+}
+
+// encodeToml writes the root object as TOML document, key/values of each table go before its sub-tables
+// because TOML doesn't allow them after table headers, otherwise field order is kept
+func encodeToml(value interface{}) ([]byte, error) {
+	ordered, err := toOrderedValue(value)
+	if err != nil {
+		return nil, err
+	}
+	object, ok := ordered.(orderedObject)
+	if !ok {
+		return nil, fmt.Errorf("only objects can be encoded as TOML")
+	}
+	buf := &bytes.Buffer{}
+	if err := writeTomlTable(buf, nil, object); err != nil {
+		return nil, err
+	}
+	return buf.Bytes(), nil
+}
+
+func isTomlTableArray(values []interface{}) bool {
+	if len(values) == 0 {
+		return false
+	}
+	for _, value := range values {
+		if _, ok := value.(orderedObject); !ok {
+			return false
+		}
+	}
+	return true
+}
+
+func writeTomlTable(buf *bytes.Buffer, path []string, object orderedObject) error {
+	var tables []orderedField
+	for _, field := range object {
+		switch v := field.Value.(type) {
+		case nil:
+			// TOML has no null, missing key means the same
+			continue
+		case orderedObject:
+			tables = append(tables, field)
+			continue
+		case []interface{}:
+			if isTomlTableArray(v) {
+				tables = append(tables, field)
+				continue
+			}
+		}
+		value, err := tomlInlineValue(field.Value)
+		if err != nil {
+			return fmt.Errorf("%s: %w", strings.Join(append(path, field.Key), "."), err)
+		}
+		fmt.Fprintf(buf, "%s = %s\n", tomlKey(field.Key), value)
+	}
+	for _, table := range tables {
+		tablePath := append(path[:len(path):len(path)], tomlKey(table.Key))
+		switch v := table.Value.(type) {
+		case orderedObject:
+			fmt.Fprintf(buf, "\n[%s]\n", strings.Join(tablePath, "."))
+			if err := writeTomlTable(buf, tablePath, v); err != nil {
+				return err
+			}
+		case []interface{}:
+			for _, item := range v {
+				fmt.Fprintf(buf, "\n[[%s]]\n", strings.Join(tablePath, "."))
+				if err := writeTomlTable(buf, tablePath, item.(orderedObject)); err != nil {
+					return err
+				}
+			}
+		}
+	}
+	return nil
+}
+
+func tomlInlineValue(value interface{}) (string, error) {
+	switch v := value.(type) {
+	case nil:
+		return "", fmt.Errorf("null can't be encoded as TOML")
+	case string:
+		quoted, _ := json.Marshal(v)
+		return string(quoted), nil
+	case bool:
+		return strconv.FormatBool(v), nil
+	case json.Number:
+		if _, err := v.Int64(); err == nil || strings.ContainsAny(v.String(), ".eE") {
+			return v.String(), nil
+		}
+		// TOML integers are limited to 64 bits
+		quoted, _ := json.Marshal(v.String())
+		return string(quoted), nil
+	case []interface{}:
+		var items []string
+		for _, item := range v {
+			encoded, err := tomlInlineValue(item)
+			if err != nil {
+				return "", err
+			}
+			items = append(items, encoded)
+		}
+		return "[" + strings.Join(items, ", ") + "]", nil
+	case orderedObject:
+		var items []string
+		for _, field := range v {
+			if field.Value == nil {
+				continue
+			}
+			encoded, err := tomlInlineValue(field.Value)
+			if err != nil {
+				return "", err
+			}
+			items = append(items, tomlKey(field.Key)+" = "+encoded)
+		}
+		return "{" + strings.Join(items, ", ") + "}", nil
+	}
+	return "", fmt.Errorf("unsupported value %v", value)
+}
@@ -0,0 +1,89 @@
+package main
+
+import (
+	"encoding/json"
+	"reflect"
+	"testing"
+
+	"gopkg.in/yaml.v2"
+)
+
+// encodingInput has keys out of alphabetical order, integers above 64 bits, hex strings, nested objects and
+// arrays of objects
+var encodingInput = json.RawMessage(`{
+	"zeta": {"nested": {"value": 1}, "list": [1, 2]},
+	"alpha": "0x01",
+	"big": 123456789012345678901234567890,
+	"negative": -5,
+	"float": 1.5,
+	"flag": true,
+	"empty": null,
+	"dotted.key": "x",
+	"items": [{"name": "a"}, {"name": "b"}]
+}`)
+
+func TestEncodeYamlRoundTrip(t *testing.T) {
+	data, err := encodeOutput(encodingInput, "yaml")
+	if err != nil {
+		t.Fatal(err)
+	}
+	decoded := yaml.MapSlice{}
+	if err := yaml.Unmarshal(data, &decoded); err != nil {
+		t.Fatalf("encoded YAML can't be decoded: %s\n%s", err, data)
+	}
+	expected := yaml.MapSlice{
+		{Key: "zeta", Value: yaml.MapSlice{
+			{Key: "nested", Value: yaml.MapSlice{{Key: "value", Value: 1}}},
+			{Key: "list", Value: []interface{}{1, 2}},
+		}},
+		// hex strings must stay strings, YAML 1.1 reads unquoted 0x01 as a number
+		{Key: "alpha", Value: "0x01"},
+		// YAML integers are 64 bits, so bigger ones are strings
+		{Key: "big", Value: "123456789012345678901234567890"},
+		{Key: "negative", Value: -5},
+		{Key: "float", Value: 1.5},
+		{Key: "flag", Value: true},
+		{Key: "empty", Value: nil},
+		{Key: "dotted.key", Value: "x"},
+		{Key: "items", Value: []interface{}{
+			yaml.MapSlice{{Key: "name", Value: "a"}},
+			yaml.MapSlice{{Key: "name", Value: "b"}},
+		}},
+	}
+	if !reflect.DeepEqual(decoded, expected) {
+		t.Fatalf("unexpected YAML round trip:\n%s\n%#v", data, decoded)
+	}
+}
+
+func TestEncodeToml(t *testing.T) {
+	data, err := encodeOutput(encodingInput, "toml")
+	if err != nil {
+		t.Fatal(err)
+	}
+	// values go before tables, null is omitted and keys that aren't bare are quoted
+	expected := `alpha = "0x01"
+big = "123456789012345678901234567890"
+negative = -5
+float = 1.5
+flag = true
+"dotted.key" = "x"
+
+[zeta]
+list = [1, 2]
+
+[zeta.nested]
+value = 1
+
+[[items]]
+name = "a"
+
+[[items]]
+name = "b"
+`
+	if string(data) != expected {
+		t.Fatalf("unexpected TOML:\n%s", data)
+	}
+	if _, err := encodeOutput(json.RawMessage(`[1]`), "toml"); err == nil {
+		t.Fatal("array is encoded as TOML document")
+	}
+}
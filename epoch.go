@@ -0,0 +1,69 @@
+package main
+
+import (
+	"fmt"
+	"math"
+	"math/bits"
+	"time"
+)
+
+// bounds of sane wall-clock durations of epoch based settings, values outside of them are likely typos
+const (
+	minEpochDuration   = time.Minute
+	maxEpochDuration   = 7 * 24 * time.Hour
+	maxLockingDuration = 90 * 24 * time.Hour
+)
+
+// blocksDuration returns duration of period seconds multiplied by all factors, it saturates instead of overflowing
+func blocksDuration(period uint64, factors ...uint64) time.Duration {
+	const maxSeconds = math.MaxInt64 / uint64(time.Second)
+	seconds := period
+	for _, factor := range factors {
+		hi, lo := bits.Mul64(seconds, factor)
+		if hi != 0 || lo > maxSeconds {
+			return math.MaxInt64
+		}
+		seconds = lo
+	}
+	if seconds > maxSeconds {
+		return math.MaxInt64
+	}
+	return time.Duration(seconds) * time.Second
+}
+
+// epochWarnings returns non-fatal problems of epoch related consensus params, durations are estimated with the
+// block period, so they hold only while the chain produces blocks in time
+func epochWarnings(params consensusParams) []string {
+	period, err := params.blockPeriod()
+	if err != nil || params.ActiveValidatorsLength == 0 {
+		// reported by validation
+		return nil
+	}
+	var warnings []string
+	epoch, active := uint64(params.EpochBlockInterval), uint64(params.ActiveValidatorsLength)
+	if epoch%active != 0 {
+		warnings = append(warnings, fmt.Sprintf("epoch of %d blocks isn't a multiple of %d active validators, validators get unequal number of in-turn blocks per epoch", epoch, active))
+	}
+	// validator is slashed once per missed in-turn block, so it can't be slashed more times per epoch than it has turns
+	if turns := epoch / active; uint64(params.FelonyThreshold) > turns {
+		warnings = append(warnings, fmt.Sprintf("felony threshold %d is never reached when all %d validator slots are filled, each validator has only %d in-turn block(s) per epoch", params.FelonyThreshold, active, turns))
+	}
+	if params.MisdemeanorThreshold >= params.FelonyThreshold {
+		warnings = append(warnings, fmt.Sprintf("misdemeanor threshold %d isn't below felony threshold %d, validators are jailed before losing rewards", params.MisdemeanorThreshold, params.FelonyThreshold))
+	}
+	epochDuration := blocksDuration(period, epoch)
+	if epochDuration < minEpochDuration {
+		warnings = append(warnings, fmt.Sprintf("epoch lasts %s, validator set and rewards are updated too often", epochDuration))
+	} else if epochDuration > maxEpochDuration {
+		warnings = append(warnings, fmt.Sprintf("epoch lasts %s, validator set changes take effect too slowly", epochDuration))
+	}
+	if params.ValidatorJailEpochLength == 0 {
+		warnings = append(warnings, "validator jail length is 0 epochs, jailed validators are released immediately")
+	} else if jail := blocksDuration(period, epoch, uint64(params.ValidatorJailEpochLength)); jail > maxLockingDuration {
+		warnings = append(warnings, fmt.Sprintf("validator jail lasts %s (%d epochs), it's over %d days", jail, params.ValidatorJailEpochLength, maxLockingDuration/(24*time.Hour)))
+	}
+	if undelegate := blocksDuration(period, epoch, uint64(params.UndelegatePeriod)); undelegate > maxLockingDuration {
+		warnings = append(warnings, fmt.Sprintf("undelegated funds are locked for %s (%d epochs), it's over %d days", undelegate, params.UndelegatePeriod, maxLockingDuration/(24*time.Hour)))
+	}
+	return warnings
+}
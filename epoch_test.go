@@ -0,0 +1,35 @@
+package main
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestEpochWarnings(t *testing.T) {
+	params := consensusParams{
+		ActiveValidatorsLength:   5,
+		EpochBlockInterval:       28800, // 1 day
+		MisdemeanorThreshold:     50,
+		FelonyThreshold:          150,
+		ValidatorJailEpochLength: 7,
+		UndelegatePeriod:         6,
+		BlockPeriodSeconds:       3,
+	}
+	if warnings := epochWarnings(params); len(warnings) != 0 {
+		t.Fatalf("sane params have warnings: %v", warnings)
+	}
+	params.ActiveValidatorsLength = 7
+	params.FelonyThreshold = 5000
+	params.UndelegatePeriod = 365
+	warnings := strings.Join(epochWarnings(params), "\n")
+	for _, expected := range []string{"isn't a multiple of 7", "felony threshold 5000 is never reached", "locked for 8760h0m0s"} {
+		if !strings.Contains(warnings, expected) {
+			t.Fatalf("warning %q is missing in:\n%s", expected, warnings)
+		}
+	}
+	// durations saturate instead of overflowing
+	params.EpochBlockInterval, params.UndelegatePeriod = 1<<31, 1<<31
+	if warnings := strings.Join(epochWarnings(params), "\n"); !strings.Contains(warnings, "too slowly") {
+		t.Fatalf("long epoch isn't reported:\n%s", warnings)
+	}
+}
@@ -0,0 +1,90 @@
+package main
+
+import (
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// simulatedLog is an event emitted during simulation (constructor, follow-up calls or init)
+type simulatedLog struct {
+	Address common.Address `json:"address"`
+	Topics  []common.Hash  `json:"topics"`
+	Data    hexutil.Bytes  `json:"data"`
+}
+
+// logsSince returns logs emitted after the first count logs, simulator never starts new transaction, so all
+// logs are kept in order under the same (zero) transaction hash
+func (s *systemContractSimulator) logsSince(count int) []simulatedLog {
+	var result []simulatedLog
+	logs := s.statedb.Logs()
+	for _, log := range logs[count:] {
+		result = append(result, simulatedLog{Address: log.Address, Topics: log.Topics, Data: log.Data})
+	}
+	return result
+}
+
+type eventArgReport struct {
+	Name  string      `json:"name"`
+	Type  string      `json:"type"`
+	Value interface{} `json:"value"`
+}
+
+type eventReport struct {
+	Address checksumAddress `json:"address"`
+	// event name, or the first topic if the event isn't declared in the ABI of the contract
+	Event string           `json:"event"`
+	Args  []eventArgReport `json:"args,omitempty"`
+	// raw data of events that can't be decoded
+	Data hexutil.Bytes `json:"data,omitempty"`
+}
+
+func (e *eventReport) String() string {
+	if len(e.Data) > 0 {
+		return fmt.Sprintf("%s %s", e.Event, e.Data)
+	}
+	result := e.Event + "("
+	for i, arg := range e.Args {
+		if i > 0 {
+			result += ", "
+		}
+		result += fmt.Sprintf("%s=%v", arg.Name, arg.Value)
+	}
+	return result + ")"
+}
+
+// decodeEvent decodes the log using ABI of the contract that emitted it (contractAbi is nil if it's unknown)
+func decodeEvent(log simulatedLog, contractAbi *abi.ABI) *eventReport {
+	result := &eventReport{Address: checksumAddress(log.Address), Event: "anonymous", Data: log.Data}
+	if len(log.Topics) == 0 {
+		return result
+	}
+	result.Event = log.Topics[0].Hex()
+	if contractAbi == nil {
+		return result
+	}
+	event, err := contractAbi.EventByID(log.Topics[0])
+	if err != nil {
+		return result
+	}
+	values := make(map[string]interface{})
+	if err := event.Inputs.UnpackIntoMap(values, log.Data); err != nil {
+		return result
+	}
+	var indexed abi.Arguments
+	for _, input := range event.Inputs {
+		if input.Indexed {
+			indexed = append(indexed, input)
+		}
+	}
+	if err := abi.ParseTopicsIntoMap(values, indexed, log.Topics[1:]); err != nil {
+		return result
+	}
+	result.Event, result.Data = event.Name, nil
+	for _, input := range event.Inputs {
+		result.Args = append(result.Args, eventArgReport{Name: input.Name, Type: input.Type.String(), Value: values[input.Name]})
+	}
+	return result
+}
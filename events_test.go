@@ -0,0 +1,31 @@
+package main
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestDecodeEvent(t *testing.T) {
+	contractAbi, err := abi.JSON(strings.NewReader(`[{"type":"event","name":"ValidatorAdded","anonymous":false,"inputs":[
+		{"name":"validator","type":"address","indexed":true},
+		{"name":"stake","type":"uint256","indexed":false}]}]`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	validator := common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725")
+	log := simulatedLog{
+		Address: stakingAddress,
+		Topics:  []common.Hash{contractAbi.Events["ValidatorAdded"].ID, common.BytesToHash(validator.Bytes())},
+		Data:    common.LeftPadBytes([]byte{100}, 32),
+	}
+	decoded := decodeEvent(log, &contractAbi)
+	if decoded.Event != "ValidatorAdded" || len(decoded.Args) != 2 || decoded.Args[0].Value != validator || decoded.String() != "ValidatorAdded(validator="+validator.Hex()+", stake=100)" {
+		t.Fatalf("unexpected event: %s", decoded)
+	}
+	if raw := decodeEvent(log, nil); raw.Event != log.Topics[0].Hex() || len(raw.Data) != 32 {
+		t.Fatalf("unexpected raw event: %s", raw)
+	}
+}
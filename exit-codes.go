@@ -0,0 +1,133 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"strings"
+)
+
+// exit codes of failed commands, pipelines branch on them instead of parsing messages
+const (
+	exitCodeFailure    = 1
+	exitCodeConfig     = 2
+	exitCodeSimulation = 3
+	exitCodeIo         = 4
+)
+
+var errorCategories = map[int]string{
+	exitCodeFailure:    "failure",
+	exitCodeConfig:     "config",
+	exitCodeSimulation: "simulation",
+	exitCodeIo:         "io",
+}
+
+// errorExitCode returns exit code of the error category
+func errorExitCode(err error) int {
+	var violations configViolations
+	var artifacts artifactFailures
+	var syntaxError *json.SyntaxError
+	var typeError *json.UnmarshalTypeError
+	if errors.As(err, &violations) || errors.As(err, &artifacts) || errors.As(err, &syntaxError) || errors.As(err, &typeError) {
+		return exitCodeConfig
+	}
+	var failures deploymentFailures
+	var revert *revertError
+	if errors.As(err, &failures) || errors.As(err, &revert) {
+		return exitCodeSimulation
+	}
+	var pathError *fs.PathError
+	var linkError *os.LinkError
+	var syscallError *os.SyscallError
+	if errors.As(err, &pathError) || errors.As(err, &linkError) || errors.As(err, &syscallError) {
+		return exitCodeIo
+	}
+	return exitCodeFailure
+}
+
+type errorViolation struct {
+	Path    string `json:"path"`
+	Message string `json:"message"`
+}
+
+type errorFailure struct {
+	Contract string          `json:"contract"`
+	Address  checksumAddress `json:"address"`
+	Message  string          `json:"message"`
+}
+
+// errorOutput is the error object written with --error-format json
+type errorOutput struct {
+	Category   string           `json:"category"`
+	ExitCode   int              `json:"exitCode"`
+	Message    string           `json:"message"`
+	Violations []errorViolation `json:"violations,omitempty"`
+	Failures   []errorFailure   `json:"failures,omitempty"`
+}
+
+func newErrorOutput(err error) *errorOutput {
+	code := errorExitCode(err)
+	result := &errorOutput{Category: errorCategories[code], ExitCode: code, Message: err.Error()}
+	var violations configViolations
+	if errors.As(err, &violations) {
+		for _, violation := range violations {
+			result.Violations = append(result.Violations, errorViolation{Path: violation.Path, Message: violation.Message})
+		}
+	}
+	var failures deploymentFailures
+	if errors.As(err, &failures) {
+		for _, failure := range failures {
+			result.Failures = append(result.Failures, errorFailure{Contract: failure.Contract, Address: checksumAddress(failure.Address), Message: failure.Cause.Error()})
+		}
+	}
+	return result
+}
+
+// extractErrorFormat removes --error-format from arguments (it's accepted by every command, in any position
+// before --) and returns its value
+func extractErrorFormat(args []string) (string, []string, error) {
+	format, rest := "text", make([]string, 0, len(args))
+	for i := 0; i < len(args); i++ {
+		arg := args[i]
+		if arg == "--" {
+			rest = append(rest, args[i:]...)
+			break
+		}
+		name := strings.TrimLeft(arg, "-")
+		switch {
+		case arg != name && name == "error-format":
+			if i+1 >= len(args) {
+				return "", nil, fmt.Errorf("--error-format requires a value")
+			}
+			format = args[i+1]
+			i++
+		case arg != name && strings.HasPrefix(name, "error-format="):
+			format = strings.TrimPrefix(name, "error-format=")
+		default:
+			rest = append(rest, arg)
+		}
+	}
+	if format != "text" && format != "json" {
+		return "", nil, fmt.Errorf("unknown error format %s, supported formats are: text, json", format)
+	}
+	return format, rest, nil
+}
+
+// writeError prints the error in the format, it goes to stderr because stdout can carry generated genesis
+func writeError(w io.Writer, err error, format string) {
+	if format == "json" {
+		data, _ := json.Marshal(newErrorOutput(err))
+		fmt.Fprintf(w, "%s\n", data)
+		return
+	}
+	fmt.Fprintf(w, "error: %s\n", err)
+}
+
+// exitWithError prints the error and exits with the code of its category
+func exitWithError(err error, format string) {
+	writeError(os.Stderr, err, format)
+	os.Exit(errorExitCode(err))
+}
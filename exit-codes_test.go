@@ -0,0 +1,56 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"os"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestErrorExitCode(t *testing.T) {
+	var violations configViolations
+	violations.add("chainId", "must be greater than zero (EIP-155)")
+	var failures deploymentFailures
+	failures.add("Staking", common.HexToAddress("0x0000000000000000000000000000000000001000"), fmt.Errorf("execution reverted"))
+	_, ioErr := os.ReadFile("/nonexistent/genesis-config.json")
+	for _, test := range []struct {
+		err  error
+		code int
+	}{
+		{violations, exitCodeConfig},
+		{fmt.Errorf("failed to read config: %w", violations), exitCodeConfig},
+		{failures, exitCodeSimulation},
+		{ioErr, exitCodeIo},
+		{fmt.Errorf("something else"), exitCodeFailure},
+	} {
+		if code := errorExitCode(test.err); code != test.code {
+			t.Fatalf("exit code of %q is %d, expected %d", test.err, code, test.code)
+		}
+	}
+	buf := &bytes.Buffer{}
+	writeError(buf, violations, "json")
+	output := &errorOutput{}
+	if err := json.Unmarshal(buf.Bytes(), output); err != nil {
+		t.Fatal(err)
+	}
+	if output.Category != "config" || output.ExitCode != exitCodeConfig || len(output.Violations) != 1 || output.Violations[0].Path != "chainId" {
+		t.Fatalf("unexpected error output %s", buf.String())
+	}
+}
+
+func TestExtractErrorFormat(t *testing.T) {
+	format, rest, err := extractErrorFormat([]string{"gen", "--config", "config.json", "--error-format", "json", "--output", "genesis.json"})
+	if err != nil || format != "json" || len(rest) != 5 || rest[3] != "--output" {
+		t.Fatalf("unexpected result %s %v (%v)", format, rest, err)
+	}
+	format, rest, err = extractErrorFormat([]string{"--error-format=json", "gen", "--", "--error-format=yaml"})
+	if err != nil || format != "json" || len(rest) != 3 {
+		t.Fatalf("unexpected result %s %v (%v)", format, rest, err)
+	}
+	if _, _, err := extractErrorFormat([]string{"gen", "--error-format", "yaml"}); err == nil {
+		t.Fatalf("unknown format is accepted")
+	}
+}
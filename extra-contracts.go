@@ -0,0 +1,120 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"os"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// extraContractConfig is a regular (not system) contract deployed in genesis, e.g. wrapped token or multicall
+type extraContractConfig struct {
+	Name    string         `json:"name,omitempty"`
+	Address common.Address `json:"address"`
+	// path to truffle/hardhat artifact or to the file with hex encoded bytecode
+	Artifact string `json:"artifact"`
+	// path to ABI JSON (default is ABI from the artifact), required only if ctor has arguments
+	Abi string `json:"abi,omitempty"`
+	// JSON array or object with argument names
+	ConstructorArgs json.RawMessage `json:"constructorArgs,omitempty"`
+	Balance         *amountValue    `json:"balance,omitempty"`
+	// with salt the address is computed as CREATE2 of the init code by create2Deployer (default is the
+	// deterministic deployment proxy), so the contract lands at the same address as on other networks
+	Salt            *common.Hash    `json:"salt,omitempty"`
+	Create2Deployer *common.Address `json:"create2Deployer,omitempty"`
+}
+
+func (c extraContractConfig) name() string {
+	if c.Name != "" {
+		return c.Name
+	}
+	return c.Address.Hex()
+}
+
+// initCode returns contract bytecode followed by ABI encoded constructor arguments
+func (c extraContractConfig) initCode() ([]byte, error) {
+	data, err := os.ReadFile(c.Artifact)
+	if err != nil {
+		return nil, err
+	}
+	return c.initCodeFrom(data)
+}
+
+// initCodeFrom is initCode with already loaded artifact (or hex bytecode) data
+func (c extraContractConfig) initCodeFrom(data []byte) ([]byte, error) {
+	var err error
+	var bytecode []byte
+	var rawAbi json.RawMessage
+	if trimmed := bytes.TrimSpace(data); len(trimmed) > 0 && trimmed[0] == '{' {
+		artifact := &artifactData{}
+		if err := json.Unmarshal(trimmed, artifact); err != nil {
+			return nil, fmt.Errorf("failed to parse artifact %s: %w", c.Artifact, err)
+		}
+		if bytecode, err = hexutil.Decode(artifact.Bytecode); err != nil {
+			return nil, fmt.Errorf("invalid bytecode in %s: %w", c.Artifact, err)
+		}
+		rawAbi = artifact.Abi
+	} else {
+		hexBytecode := strings.TrimSpace(string(trimmed))
+		if !strings.HasPrefix(hexBytecode, "0x") {
+			hexBytecode = "0x" + hexBytecode
+		}
+		if bytecode, err = hexutil.Decode(hexBytecode); err != nil {
+			return nil, fmt.Errorf("invalid bytecode in %s: %w", c.Artifact, err)
+		}
+	}
+	if c.Abi != "" {
+		if rawAbi, err = os.ReadFile(c.Abi); err != nil {
+			return nil, err
+		}
+	}
+	if len(rawAbi) == 0 {
+		if len(c.ConstructorArgs) > 0 {
+			return nil, fmt.Errorf("ABI is required to encode constructor arguments of %s", c.name())
+		}
+		return bytecode, nil
+	}
+	parsed, err := abi.JSON(bytes.NewReader(rawAbi))
+	if err != nil {
+		return nil, fmt.Errorf("invalid ABI of %s: %w", c.name(), err)
+	}
+	rawArgs := c.ConstructorArgs
+	if len(rawArgs) == 0 {
+		rawArgs = json.RawMessage("[]")
+	}
+	args, err := encodeAbiArguments(parsed.Constructor.Inputs, rawArgs)
+	if err != nil {
+		return nil, fmt.Errorf("constructor of %s: %w", c.name(), err)
+	}
+	return append(bytecode, args...), nil
+}
+
+// deployExtraContracts executes constructors of extra contracts after system contracts are deployed
+func deployExtraContracts(simulator *systemContractSimulator, contracts []extraContractConfig) deploymentFailures {
+	var failures deploymentFailures
+	for _, contract := range contracts {
+		initCode, err := contract.initCode()
+		if err != nil {
+			failures.add(contract.name(), contract.Address, err)
+			continue
+		}
+		balance := (*big.Int)(contract.Balance)
+		if balance == nil {
+			balance = big.NewInt(0)
+		}
+		gasUsed, err := simulator.deploy(contract.Address, initCode, balance)
+		if err != nil {
+			failures.add(contract.name(), contract.Address, err)
+			continue
+		}
+		log.Info("Deployed extra contract", "name", contract.name(), "address", contract.Address, "gasUsed", gasUsed)
+	}
+	return failures
+}
@@ -0,0 +1,144 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// extraDataLayout describes how validator set is encoded into genesis extra data
+type extraDataLayout struct {
+	// length of the vanity prefix
+	VanityLength int `json:"vanityLength"`
+	// length of the signature suffix
+	SealLength int `json:"sealLength"`
+	// if set then validator list is prefixed with one byte containing number of validators
+	ValidatorCountPrefix bool `json:"validatorCountPrefix"`
+	// if set then each validator address is followed by its BLS public key (BEP-126)
+	ValidatorBlsKeys bool `json:"validatorBlsKeys"`
+	// if set then validator set isn't encoded (engines without signers)
+	WithoutValidators bool `json:"withoutValidators"`
+	// RLP encoding of besu BFT engines (ibft2 or qbft), vanity and validators are wrapped into it
+	Encoding string `json:"encoding,omitempty"`
+}
+
+// known extra data layouts, forks with different encoding can use "custom" layout
+var extraDataLayouts = map[string]extraDataLayout{
+	"parlia": {VanityLength: 32, SealLength: 65},
+	// parlia with fast finality, vote attestation is empty in genesis
+	"luban": {VanityLength: 32, SealLength: 65, ValidatorCountPrefix: true, ValidatorBlsKeys: true},
+	// signers between vanity and seal, the same bytes as parlia
+	"clique": {VanityLength: 32, SealLength: 65},
+	// vanity only, ethash limits extra data to 32 bytes
+	"ethash": {VanityLength: 32, WithoutValidators: true},
+	// RLP list of vanity, validators, vote, round and seals
+	"ibft2": {VanityLength: 32, Encoding: consensusIbft2},
+	"qbft":  {VanityLength: 32, Encoding: consensusQbft},
+}
+
+type extraDataConfig struct {
+	// one of the known layouts or "custom"
+	Layout string `json:"layout"`
+	// custom layout spec, used only for "custom" layout
+	Custom *extraDataLayout `json:"custom,omitempty"`
+	// vanity bytes (must not exceed vanity length)
+	Vanity hexutil.Bytes `json:"vanity,omitempty"`
+}
+
+func (c *extraDataConfig) layout() (extraDataLayout, error) {
+	if c == nil || c.Layout == "" {
+		return extraDataLayouts["parlia"], nil
+	}
+	if c.Layout == "custom" {
+		if c.Custom == nil {
+			return extraDataLayout{}, fmt.Errorf("custom extra data layout is not specified")
+		}
+		if c.Custom.VanityLength < 0 || c.Custom.SealLength < 0 {
+			return extraDataLayout{}, fmt.Errorf("extra data vanity and seal lengths can't be negative")
+		}
+		return *c.Custom, nil
+	}
+	layout, ok := extraDataLayouts[c.Layout]
+	if !ok {
+		return extraDataLayout{}, fmt.Errorf("unknown extra data layout: %s", c.Layout)
+	}
+	return layout, nil
+}
+
+func createExtraData(config *extraDataConfig, validators []validatorConfig) ([]byte, error) {
+	layout, err := config.layout()
+	if err != nil {
+		return nil, err
+	}
+	var vanity []byte
+	if config != nil {
+		vanity = config.Vanity
+	}
+	if len(vanity) > layout.VanityLength {
+		return nil, fmt.Errorf("extra data vanity is too long (%d > %d)", len(vanity), layout.VanityLength)
+	}
+	if layout.Encoding != "" {
+		return encodeBftExtraData(layout.Encoding, layout.VanityLength, vanity, validators)
+	}
+	if layout.WithoutValidators {
+		extra := make([]byte, layout.VanityLength, layout.VanityLength+layout.SealLength)
+		copy(extra, vanity)
+		return append(extra, make([]byte, layout.SealLength)...), nil
+	}
+	validatorLength := common.AddressLength
+	if layout.ValidatorBlsKeys {
+		validatorLength += blsPublicKeyLength
+	}
+	extra := make([]byte, layout.VanityLength, layout.VanityLength+1+validatorLength*len(validators)+layout.SealLength)
+	copy(extra, vanity)
+	if layout.ValidatorCountPrefix {
+		if len(validators) > 255 {
+			return nil, fmt.Errorf("too many validators for count prefixed extra data (%d)", len(validators))
+		}
+		extra = append(extra, byte(len(validators)))
+	}
+	for _, v := range validators {
+		extra = append(extra, v.Address.Bytes()...)
+		if !layout.ValidatorBlsKeys {
+			continue
+		}
+		if err := verifyBlsPublicKey(v.BlsPublicKey); err != nil {
+			return nil, fmt.Errorf("validator %s: %w", v.Address.Hex(), err)
+		}
+		extra = append(extra, v.BlsPublicKey...)
+	}
+	return append(extra, make([]byte, layout.SealLength)...), nil
+}
+
+// verifyExtraData makes sure that extra data (it might be patched) still contains the genesis validator set
+func verifyExtraData(config *extraDataConfig, extra []byte, validators []validatorConfig) error {
+	expected, err := createExtraData(config, validators)
+	if err != nil {
+		return err
+	}
+	layout, _ := config.layout()
+	if layout.Encoding != "" {
+		encoded, err := decodeBftValidators(extra)
+		if err != nil {
+			return err
+		}
+		if len(encoded) != len(validators) {
+			return fmt.Errorf("extra data doesn't contain genesis validator set")
+		}
+		for i, v := range validators {
+			if encoded[i] != v.Address {
+				return fmt.Errorf("extra data doesn't contain genesis validator set")
+			}
+		}
+		return nil
+	}
+	if len(extra) != len(expected) {
+		return fmt.Errorf("extra data must be %d bytes for %d validator(s), got %d", len(expected), len(validators), len(extra))
+	}
+	if !bytes.Equal(extra[layout.VanityLength:len(extra)-layout.SealLength], expected[layout.VanityLength:len(expected)-layout.SealLength]) {
+		return fmt.Errorf("extra data doesn't contain genesis validator set")
+	}
+	return nil
+}
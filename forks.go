@@ -0,0 +1,145 @@
+package main
+
+import (
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/params"
+)
+
+// bscForkConfig are fork blocks of BSC releases newer than the node the generator is built with, they are written
+// into chain config of geth and erigon genesis as is (simulation runs with rules of the built-in node)
+type bscForkConfig struct {
+	EulerBlock  *uint64 `json:"eulerBlock,omitempty"`
+	GibbsBlock  *uint64 `json:"gibbsBlock,omitempty"`
+	NanoBlock   *uint64 `json:"nanoBlock,omitempty"`
+	MoranBlock  *uint64 `json:"moranBlock,omitempty"`
+	PlanckBlock *uint64 `json:"planckBlock,omitempty"`
+	// fast finality, validators vote with BLS keys (requires luban extra data layout at genesis)
+	LubanBlock    *uint64 `json:"lubanBlock,omitempty"`
+	PlatoBlock    *uint64 `json:"platoBlock,omitempty"`
+	BerlinBlock   *uint64 `json:"berlinBlock,omitempty"`
+	HertzBlock    *uint64 `json:"hertzBlock,omitempty"`
+	HertzfixBlock *uint64 `json:"hertzfixBlock,omitempty"`
+}
+
+// blocks returns configured forks unknown to the built-in node in activation order
+func (c *bscForkConfig) blocks() []orderedField {
+	if c == nil {
+		return nil
+	}
+	var result []orderedField
+	for _, fork := range []struct {
+		name  string
+		block *uint64
+	}{
+		{"eulerBlock", c.EulerBlock},
+		{"gibbsBlock", c.GibbsBlock},
+		{"nanoBlock", c.NanoBlock},
+		{"moranBlock", c.MoranBlock},
+		{"planckBlock", c.PlanckBlock},
+		{"lubanBlock", c.LubanBlock},
+		{"platoBlock", c.PlatoBlock},
+		{"hertzBlock", c.HertzBlock},
+		{"hertzfixBlock", c.HertzfixBlock},
+	} {
+		if fork.block != nil {
+			result = append(result, orderedField{Key: fork.name, Value: *fork.block})
+		}
+	}
+	return result
+}
+
+// apply sets forks known to the built-in node in the chain config, so simulation runs with their rules
+func (c *bscForkConfig) apply(chainConfig *params.ChainConfig) {
+	if c != nil && c.BerlinBlock != nil {
+		chainConfig.BerlinBlock = new(big.Int).SetUint64(*c.BerlinBlock)
+	}
+}
+
+type forkBlock struct {
+	name  string
+	block *big.Int
+}
+
+// forkSchedule returns all forks of the chain in the order of BSC releases, runtime upgrade is BAS specific and
+// independent of them
+func forkSchedule(chainConfig *params.ChainConfig, forks *bscForkConfig) []forkBlock {
+	if forks == nil {
+		forks = &bscForkConfig{}
+	}
+	block := func(value *uint64) *big.Int {
+		if value == nil {
+			return nil
+		}
+		return new(big.Int).SetUint64(*value)
+	}
+	berlin := chainConfig.BerlinBlock
+	if forks.BerlinBlock != nil {
+		berlin = block(forks.BerlinBlock)
+	}
+	return []forkBlock{
+		{"homesteadBlock", chainConfig.HomesteadBlock},
+		{"eip150Block", chainConfig.EIP150Block},
+		{"eip155Block", chainConfig.EIP155Block},
+		{"eip158Block", chainConfig.EIP158Block},
+		{"byzantiumBlock", chainConfig.ByzantiumBlock},
+		{"constantinopleBlock", chainConfig.ConstantinopleBlock},
+		{"petersburgBlock", chainConfig.PetersburgBlock},
+		{"istanbulBlock", chainConfig.IstanbulBlock},
+		{"muirGlacierBlock", chainConfig.MuirGlacierBlock},
+		{"ramanujanBlock", chainConfig.RamanujanBlock},
+		{"nielsBlock", chainConfig.NielsBlock},
+		{"mirrorSyncBlock", chainConfig.MirrorSyncBlock},
+		{"brunoBlock", chainConfig.BrunoBlock},
+		{"eulerBlock", block(forks.EulerBlock)},
+		{"gibbsBlock", block(forks.GibbsBlock)},
+		{"nanoBlock", block(forks.NanoBlock)},
+		{"moranBlock", block(forks.MoranBlock)},
+		{"planckBlock", block(forks.PlanckBlock)},
+		{"lubanBlock", block(forks.LubanBlock)},
+		{"platoBlock", block(forks.PlatoBlock)},
+		{"berlinBlock", berlin},
+		{"hertzBlock", block(forks.HertzBlock)},
+		{"hertzfixBlock", block(forks.HertzfixBlock)},
+	}
+}
+
+// checkForkSchedule fails if a fork is activated before the one preceding it (like geth's CheckConfigForkOrder),
+// skipped forks followed by activated ones are returned as warnings, clients treat them differently
+func checkForkSchedule(schedule []forkBlock) ([]string, error) {
+	var warnings []string
+	var last *forkBlock
+	var skipped []string
+	for i := range schedule {
+		fork := &schedule[i]
+		if fork.block == nil {
+			skipped = append(skipped, fork.name)
+			continue
+		}
+		if last != nil && fork.block.Cmp(last.block) < 0 {
+			return nil, fmt.Errorf("%s (%s) must not be activated before %s (%s)", fork.name, fork.block, last.name, last.block)
+		}
+		for _, name := range skipped {
+			warnings = append(warnings, fmt.Sprintf("fork %s is not scheduled, but later %s is activated at block %s", name, fork.name, fork.block))
+		}
+		skipped = nil
+		last = fork
+	}
+	return warnings, nil
+}
+
+// withBscForks adds fork blocks into config of the geth or erigon genesis
+func withBscForks(formatted interface{}, forks *bscForkConfig) interface{} {
+	blocks := forks.blocks()
+	if len(blocks) == 0 {
+		return formatted
+	}
+	result := formatted.(orderedObject)
+	for i, field := range result {
+		if field.Key == "config" {
+			result[i].Value = append(field.Value.(orderedObject), blocks...)
+		}
+	}
+	return result
+}
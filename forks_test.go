@@ -0,0 +1,63 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestBscForkBlocks(t *testing.T) {
+	zero, later := uint64(0), uint64(100)
+	config := devNetConfig
+	config.Forks = &bscForkConfig{PlanckBlock: &zero, PlatoBlock: &later}
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+	data, err := os.ReadFile(outputFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	genesis := &struct {
+		Config map[string]interface{} `json:"config"`
+	}{}
+	if err := json.Unmarshal(data, genesis); err != nil {
+		t.Fatal(err)
+	}
+	if genesis.Config["planckBlock"] != float64(0) || genesis.Config["platoBlock"] != float64(100) || genesis.Config["lubanBlock"] != nil {
+		t.Fatalf("unexpected fork blocks in chain config: %v", genesis.Config)
+	}
+}
+
+func TestBscForkViolations(t *testing.T) {
+	zero, later := uint64(0), uint64(100)
+	config := devNetConfig
+	config.Forks = &bscForkConfig{PlanckBlock: &later, LubanBlock: &zero}
+	var violations configViolations
+	// luban before planck and without BLS keys in extra data
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 2 {
+		t.Fatalf("expected 2 violations, got %v", err)
+	}
+}
+
+func TestForkScheduleOrder(t *testing.T) {
+	chainConfig := defaultGenesisConfig(14000).Config
+	plato, berlin := uint64(200), uint64(100)
+	if _, err := checkForkSchedule(forkSchedule(chainConfig, &bscForkConfig{PlatoBlock: &plato, BerlinBlock: &berlin})); err == nil {
+		t.Fatal("berlin before plato is accepted")
+	}
+	berlin = 200
+	warnings, err := checkForkSchedule(forkSchedule(chainConfig, &bscForkConfig{PlatoBlock: &plato, BerlinBlock: &berlin}))
+	if err != nil {
+		t.Fatal(err)
+	}
+	// euler, gibbs, nano, moran, planck and luban are skipped
+	if len(warnings) != 6 {
+		t.Fatalf("expected 6 warnings about skipped forks, got %v", warnings)
+	}
+	if warnings, err := checkForkSchedule(forkSchedule(chainConfig, nil)); err != nil || len(warnings) != 0 {
+		t.Fatalf("default schedule isn't clean: %v %v", warnings, err)
+	}
+}
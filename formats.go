@@ -0,0 +1,338 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// orderedField is a key of the orderedObject
+type orderedField struct {
+	Key   string
+	Value interface{}
+}
+
+// orderedObject is a JSON object that keeps order of the keys when encoded
+type orderedObject []orderedField
+
+func (o orderedObject) MarshalJSON() ([]byte, error) {
+	buf := &bytes.Buffer{}
+	buf.WriteByte('{')
+	for i, field := range o {
+		if i > 0 {
+			buf.WriteByte(',')
+		}
+		key, _ := json.Marshal(field.Key)
+		buf.Write(key)
+		buf.WriteByte(':')
+		value, err := json.Marshal(field.Value)
+		if err != nil {
+			return nil, err
+		}
+		buf.Write(value)
+	}
+	buf.WriteByte('}')
+	return buf.Bytes(), nil
+}
+
+// decodeOrderedJson decodes JSON value keeping order of the object keys (objects are decoded
+// as orderedObject and numbers as json.Number)
+func decodeOrderedJson(data []byte) (interface{}, error) {
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.UseNumber()
+	return decodeOrderedValue(decoder)
+}
+
+func decodeOrderedValue(decoder *json.Decoder) (interface{}, error) {
+	token, err := decoder.Token()
+	if err != nil {
+		return nil, err
+	}
+	delim, ok := token.(json.Delim)
+	if !ok {
+		return token, nil
+	}
+	switch delim {
+	case '{':
+		object := orderedObject{}
+		for decoder.More() {
+			key, err := decoder.Token()
+			if err != nil {
+				return nil, err
+			}
+			value, err := decodeOrderedValue(decoder)
+			if err != nil {
+				return nil, err
+			}
+			object = append(object, orderedField{Key: key.(string), Value: value})
+		}
+		_, err = decoder.Token()
+		return object, err
+	case '[':
+		array := []interface{}{}
+		for decoder.More() {
+			value, err := decodeOrderedValue(decoder)
+			if err != nil {
+				return nil, err
+			}
+			array = append(array, value)
+		}
+		_, err = decoder.Token()
+		return array, err
+	}
+	return nil, fmt.Errorf("unexpected delimiter %s", delim)
+}
+
+// genesisFormatters convert genesis into the layout expected by the specific client
+var genesisFormatters = map[string]func(genesis *core.Genesis) (interface{}, error){
+	"geth":       formatGethGenesis,
+	"erigon":     formatErigonGenesis,
+	"besu":       formatBesuGenesis,
+	"nethermind": formatNethermindChainspec,
+}
+
+func genesisFormatNames() []string {
+	var result []string
+	for name := range genesisFormatters {
+		result = append(result, name)
+	}
+	sort.Strings(result)
+	return result
+}
+
+func formatGenesis(genesis *core.Genesis, format string) (interface{}, error) {
+	if format == "" {
+		format = "geth"
+	}
+	formatter, ok := genesisFormatters[format]
+	if !ok {
+		return nil, fmt.Errorf("unknown genesis format %s, supported formats are: %s", format, strings.Join(genesisFormatNames(), ", "))
+	}
+	return formatter(genesis)
+}
+
+func formatGethGenesis(genesis *core.Genesis) (interface{}, error) {
+	// alloc is encoded separately by the writer, it can be huge
+	header := *genesis
+	header.Alloc = core.GenesisAlloc{}
+	data, err := json.Marshal(&header)
+	if err != nil {
+		return nil, err
+	}
+	result, err := decodeOrderedJson(data)
+	if err != nil {
+		return nil, err
+	}
+	for i, field := range result.(orderedObject) {
+		if field.Key == "alloc" {
+			result.(orderedObject)[i].Value = streamedAlloc{
+				genesis: genesis,
+				key:     func(address common.Address) string { return strings.ToLower(address.Hex()) },
+				account: func(account core.GenesisAccount) interface{} { return account },
+			}
+		}
+	}
+	return result, nil
+}
+
+// formatErigonGenesis returns geth layout with explicit consensus name, erigon requires it to pick the engine
+func formatErigonGenesis(genesis *core.Genesis) (interface{}, error) {
+	result, err := formatGethGenesis(genesis)
+	if err != nil {
+		return nil, err
+	}
+	consensus := "parlia"
+	switch {
+	case genesis.Config.Clique != nil:
+		consensus = "clique"
+	case genesis.Config.Ethash != nil:
+		consensus = "ethash"
+	}
+	for i, field := range result.(orderedObject) {
+		if field.Key == "config" {
+			result.(orderedObject)[i].Value = append(field.Value.(orderedObject), orderedField{Key: "consensus", Value: consensus})
+		}
+	}
+	return result, nil
+}
+
+// forkBlocks maps fork blocks supported by all clients to their names in geth's config
+func forkBlocks(genesis *core.Genesis) []orderedField {
+	config := genesis.Config
+	var result []orderedField
+	for _, fork := range []struct {
+		name  string
+		block *big.Int
+	}{
+		{"homesteadBlock", config.HomesteadBlock},
+		{"eip150Block", config.EIP150Block},
+		{"eip155Block", config.EIP155Block},
+		{"eip158Block", config.EIP158Block},
+		{"byzantiumBlock", config.ByzantiumBlock},
+		{"constantinopleBlock", config.ConstantinopleBlock},
+		{"petersburgBlock", config.PetersburgBlock},
+		{"istanbulBlock", config.IstanbulBlock},
+		{"muirGlacierBlock", config.MuirGlacierBlock},
+		{"berlinBlock", config.BerlinBlock},
+	} {
+		if fork.block != nil {
+			result = append(result, orderedField{Key: fork.name, Value: fork.block})
+		}
+	}
+	return result
+}
+
+// sortedAlloc returns alloc addresses in ascending order, so output doesn't depend on map iteration
+func sortedAlloc(genesis *core.Genesis) []common.Address {
+	var result []common.Address
+	for address := range genesis.Alloc {
+		result = append(result, address)
+	}
+	sort.Slice(result, func(i, j int) bool {
+		return bytes.Compare(result[i].Bytes(), result[j].Bytes()) < 0
+	})
+	return result
+}
+
+func formatAllocAccount(account core.GenesisAccount, trimPrefix bool) orderedObject {
+	result := orderedObject{}
+	balance := account.Balance
+	if balance == nil {
+		balance = big.NewInt(0)
+	}
+	result = append(result, orderedField{Key: "balance", Value: hexutil.EncodeBig(balance)})
+	if account.Nonce > 0 {
+		result = append(result, orderedField{Key: "nonce", Value: hexutil.EncodeUint64(account.Nonce)})
+	}
+	if len(account.Code) > 0 {
+		result = append(result, orderedField{Key: "code", Value: hexutil.Encode(account.Code)})
+	}
+	if len(account.Storage) > 0 {
+		var slots []common.Hash
+		for slot := range account.Storage {
+			slots = append(slots, slot)
+		}
+		sort.Slice(slots, func(i, j int) bool {
+			return bytes.Compare(slots[i].Bytes(), slots[j].Bytes()) < 0
+		})
+		storage := orderedObject{}
+		for _, slot := range slots {
+			key := slot.Hex()
+			if trimPrefix {
+				key = key[2:]
+			}
+			storage = append(storage, orderedField{Key: key, Value: account.Storage[slot].Hex()})
+		}
+		result = append(result, orderedField{Key: "storage", Value: storage})
+	}
+	return result
+}
+
+// formatBesuGenesis maps params and alloc into besu's genesis file, besu doesn't implement Parlia, so consensus
+// section (ibft2/qbft) must be provided by the operator unless the chain runs clique or ethash
+func formatBesuGenesis(genesis *core.Genesis) (interface{}, error) {
+	config := orderedObject{{Key: "chainId", Value: genesis.Config.ChainID}}
+	config = append(config, forkBlocks(genesis)...)
+	if clique := genesis.Config.Clique; clique != nil {
+		config = append(config, orderedField{Key: "clique", Value: orderedObject{
+			{Key: "blockperiodseconds", Value: clique.Period},
+			{Key: "epochlength", Value: clique.Epoch},
+		}})
+	}
+	if genesis.Config.Ethash != nil {
+		config = append(config, orderedField{Key: "ethash", Value: orderedObject{}})
+	}
+	alloc := streamedAlloc{
+		genesis: genesis,
+		key:     func(address common.Address) string { return strings.ToLower(address.Hex()[2:]) },
+		account: func(account core.GenesisAccount) interface{} { return formatAllocAccount(account, true) },
+	}
+	return orderedObject{
+		{Key: "config", Value: config},
+		{Key: "nonce", Value: hexutil.EncodeUint64(genesis.Nonce)},
+		{Key: "timestamp", Value: hexutil.EncodeUint64(genesis.Timestamp)},
+		{Key: "extraData", Value: hexutil.Encode(genesis.ExtraData)},
+		{Key: "gasLimit", Value: hexutil.EncodeUint64(genesis.GasLimit)},
+		{Key: "difficulty", Value: hexutil.EncodeBig(genesis.Difficulty)},
+		{Key: "mixHash", Value: genesis.Mixhash.Hex()},
+		{Key: "coinbase", Value: genesis.Coinbase.Hex()},
+		{Key: "alloc", Value: alloc},
+	}, nil
+}
+
+// formatNethermindChainspec maps genesis into parity-style chainspec used by nethermind, EIP transitions are
+// derived from geth fork blocks
+func formatNethermindChainspec(genesis *core.Genesis) (interface{}, error) {
+	config := genesis.Config
+	transitions := []struct {
+		block *big.Int
+		eips  []string
+	}{
+		{config.EIP150Block, []string{"eip150Transition"}},
+		{config.EIP155Block, []string{"eip155Transition"}},
+		{config.EIP158Block, []string{"eip160Transition", "eip161abcTransition", "eip161dTransition"}},
+		{config.ByzantiumBlock, []string{"eip140Transition", "eip211Transition", "eip214Transition", "eip658Transition"}},
+		{config.ConstantinopleBlock, []string{"eip145Transition", "eip1014Transition", "eip1052Transition"}},
+		{config.IstanbulBlock, []string{"eip152Transition", "eip1108Transition", "eip1344Transition", "eip1884Transition", "eip2028Transition", "eip2200Transition"}},
+		{config.BerlinBlock, []string{"eip2565Transition", "eip2929Transition", "eip2930Transition"}},
+	}
+	chainId := hexutil.EncodeBig(config.ChainID)
+	params := orderedObject{
+		{Key: "chainID", Value: chainId},
+		{Key: "networkID", Value: chainId},
+		{Key: "gasLimitBoundDivisor", Value: "0x400"},
+		{Key: "maximumExtraDataSize", Value: hexutil.EncodeUint64(uint64(len(genesis.ExtraData)))},
+		{Key: "minGasLimit", Value: "0x1388"},
+	}
+	for _, transition := range transitions {
+		if transition.block == nil {
+			continue
+		}
+		for _, eip := range transition.eips {
+			params = append(params, orderedField{Key: eip, Value: hexutil.EncodeBig(transition.block)})
+		}
+	}
+	engine := orderedObject{}
+	if config.Parlia != nil {
+		engine = append(engine, orderedField{Key: "parlia", Value: orderedObject{{Key: "params", Value: orderedObject{
+			{Key: "period", Value: config.Parlia.Period},
+			{Key: "epoch", Value: config.Parlia.Epoch},
+		}}}})
+	}
+	if config.Clique != nil {
+		engine = append(engine, orderedField{Key: "clique", Value: orderedObject{{Key: "params", Value: orderedObject{
+			{Key: "period", Value: config.Clique.Period},
+			{Key: "epoch", Value: config.Clique.Epoch},
+		}}}})
+	}
+	accounts := streamedAlloc{
+		genesis: genesis,
+		key:     func(address common.Address) string { return address.Hex() },
+		account: func(account core.GenesisAccount) interface{} { return formatAllocAccount(account, false) },
+	}
+	return orderedObject{
+		{Key: "name", Value: fmt.Sprintf("bas-%s", config.ChainID)},
+		{Key: "engine", Value: engine},
+		{Key: "params", Value: params},
+		{Key: "genesis", Value: orderedObject{
+			{Key: "seal", Value: orderedObject{{Key: "ethereum", Value: orderedObject{
+				{Key: "nonce", Value: hexutil.Encode(common.LeftPadBytes(new(big.Int).SetUint64(genesis.Nonce).Bytes(), 8))},
+				{Key: "mixHash", Value: genesis.Mixhash.Hex()},
+			}}}},
+			{Key: "difficulty", Value: hexutil.EncodeBig(genesis.Difficulty)},
+			{Key: "author", Value: genesis.Coinbase.Hex()},
+			{Key: "timestamp", Value: hexutil.EncodeUint64(genesis.Timestamp)},
+			{Key: "parentHash", Value: genesis.ParentHash.Hex()},
+			{Key: "extraData", Value: hexutil.Encode(genesis.ExtraData)},
+			{Key: "gasLimit", Value: hexutil.EncodeUint64(genesis.GasLimit)},
+		}},
+		{Key: "accounts", Value: accounts},
+	}, nil
+}
@@ -0,0 +1,91 @@
+package main
+
+import (
+	"encoding/json"
+	"math/big"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/params"
+)
+
+func TestGenesisFormats(t *testing.T) {
+	address := common.HexToAddress("0x00000000000000000000000000000000000aBcDe")
+	slot, value := common.HexToHash("0x01"), common.HexToHash("0x02")
+	genesis := defaultGenesisConfig(14000)
+	genesis.Config.Parlia = &params.ParliaConfig{Period: 3, Epoch: 200}
+	genesis.ExtraData = []byte{0x01, 0x02}
+	genesis.Alloc = core.GenesisAlloc{
+		address: {Balance: big.NewInt(1), Code: []byte{0x60, 0x00}, Storage: map[common.Hash]common.Hash{slot: value}},
+	}
+	// alloc keys are lower case hex with prefix in geth, without prefix in besu and checksummed in nethermind
+	gethAccount := "alloc.0x00000000000000000000000000000000000abcde."
+	besuAccount := "alloc.00000000000000000000000000000000000abcde."
+	nethermindAccount := "accounts." + address.Hex() + "."
+	decoded := make(map[string]map[string]interface{})
+	for _, format := range genesisFormatNames() {
+		formatted, err := formatGenesis(genesis, format)
+		if err != nil {
+			t.Fatalf("%s: %s", format, err)
+		}
+		data, err := json.Marshal(formatted)
+		if err != nil {
+			t.Fatalf("%s: %s", format, err)
+		}
+		var result map[string]interface{}
+		if err := json.Unmarshal(data, &result); err != nil {
+			t.Fatalf("%s: %s", format, err)
+		}
+		decoded[format] = result
+	}
+	for _, test := range []struct {
+		format   string
+		path     string
+		expected interface{}
+	}{
+		{"geth", "config.chainId", float64(14000)},
+		{"geth", "config.istanbulBlock", float64(0)},
+		{"geth", "config.parlia.period", float64(3)},
+		{"geth", "extraData", "0x0102"},
+		{"geth", gethAccount + "code", "0x6000"},
+		{"geth", gethAccount + "storage." + slot.Hex(), value.Hex()},
+		{"erigon", "config.chainId", float64(14000)},
+		{"erigon", "config.consensus", "parlia"},
+		{"erigon", "extraData", "0x0102"},
+		{"besu", "config.chainId", float64(14000)},
+		{"besu", "config.istanbulBlock", float64(0)},
+		{"besu", "extraData", "0x0102"},
+		{"besu", besuAccount + "balance", "0x1"},
+		{"besu", besuAccount + "code", "0x6000"},
+		{"besu", besuAccount + "storage." + slot.Hex()[2:], value.Hex()},
+		{"nethermind", "params.chainID", "0x36b0"},
+		{"nethermind", "params.eip1108Transition", "0x0"},
+		{"nethermind", "params.maximumExtraDataSize", "0x2"},
+		{"nethermind", "engine.parlia.params.period", float64(3)},
+		{"nethermind", "engine.parlia.params.epoch", float64(200)},
+		{"nethermind", "genesis.extraData", "0x0102"},
+		{"nethermind", nethermindAccount + "code", "0x6000"},
+		{"nethermind", nethermindAccount + "storage." + slot.Hex(), value.Hex()},
+	} {
+		if actual := jsonPathValue(decoded[test.format], test.path); actual != test.expected {
+			t.Errorf("%s: %s is %v, expected %v", test.format, test.path, actual, test.expected)
+		}
+	}
+	if _, err := formatGenesis(genesis, "parity"); err == nil {
+		t.Fatal("unknown format is accepted")
+	}
+}
+
+// jsonPathValue returns value of the decoded JSON by dot separated keys (keys must not contain dots)
+func jsonPathValue(value interface{}, path string) interface{} {
+	for _, key := range strings.Split(path, ".") {
+		object, ok := value.(map[string]interface{})
+		if !ok {
+			return nil
+		}
+		value = object[key]
+	}
+	return value
+}
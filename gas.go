@@ -0,0 +1,34 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/core/vm"
+)
+
+// defaultSimulationGas is gas given to every simulated constructor and call unless gas is metered against the
+// genesis gas limit
+const defaultSimulationGas = 10_000_000
+
+// withGasLimit adds the gas budget to out of gas errors, so it's clear whether the contract is broken or the
+// budget is too small
+func (s *systemContractSimulator) withGasLimit(err error) error {
+	if errors.Is(err, vm.ErrOutOfGas) || errors.Is(err, vm.ErrCodeStoreOutOfGas) {
+		return fmt.Errorf("%w (gas limit is %d)", err, s.gasLimit)
+	}
+	return err
+}
+
+// verifyBlockGas fails if init calls of system contracts don't fit into one block, Parlia executes all of them
+// in block 1 under the genesis gas limit
+func verifyBlockGas(report *deploymentReport, gasLimit uint64) error {
+	var total uint64
+	for _, contract := range report.Contracts {
+		total += contract.InitGasUsed
+	}
+	if total > gasLimit {
+		return fmt.Errorf("init calls of system contracts use %d gas, but genesis gas limit is %d", total, gasLimit)
+	}
+	return nil
+}
@@ -0,0 +1,24 @@
+package main
+
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common/math"
+)
+
+func TestMeteredGas(t *testing.T) {
+	options := defaultGenerateOptions()
+	options.MeterGas = true
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := createGenesisConfig(devNetConfig, outputFile, options); err != nil {
+		t.Fatalf("devnet doesn't fit into default gas limit: %s", err)
+	}
+	config := devNetConfig
+	gasLimit := math.HexOrDecimal64(1_000_000)
+	config.Header = &headerConfig{GasLimit: &gasLimit}
+	if err := createGenesisConfig(config, outputFile, options); err == nil || !strings.Contains(err.Error(), "gas limit is 1000000") {
+		t.Fatalf("constructors exceeding block gas limit are accepted: %v", err)
+	}
+}
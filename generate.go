@@ -0,0 +1,131 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/common"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+// generateGenesisJson builds genesis from config JSON in memory, it's the entry point of wasm, C ABI and gen serve,
+// so nothing is read from or written to the filesystem and system contracts are simulated sequentially (see
+// parseForeignConfig for what config may contain)
+func generateGenesisJson(configJson []byte) ([]byte, error) {
+	genesis, _, err := generateGenesisWithReport(configJson)
+	return genesis, err
+}
+
+// generateGenesisWithReport is generateGenesisJson that also returns deployment report JSON
+func generateGenesisWithReport(configJson []byte) (genesis []byte, report []byte, err error) {
+	config, err := parseForeignConfig(configJson)
+	if err != nil {
+		return nil, nil, err
+	}
+	genesisOutput, reportOutput := &bytes.Buffer{}, &bytes.Buffer{}
+	options := defaultGenerateOptions()
+	options.Parallelism = 1
+	options.Output = genesisOutput
+	options.ReportOutput = reportOutput
+	if err := createGenesisConfig(*config, "", options); err != nil {
+		return nil, nil, err
+	}
+	return genesisOutput.Bytes(), reportOutput.Bytes(), nil
+}
+
+// loadConfigJson validates config JSON and returns it as the generator sees it (migrated, with durations
+// converted to block counts and CREATE2 addresses resolved)
+func loadConfigJson(configJson []byte) ([]byte, error) {
+	config, err := parseForeignConfig(configJson)
+	if err != nil {
+		return nil, err
+	}
+	if err := config.resolveCreate2Addresses(); err != nil {
+		return nil, err
+	}
+	if err := config.validate(); err != nil {
+		return nil, err
+	}
+	return json.Marshal(config)
+}
+
+// parseForeignConfig parses config submitted to in-memory entry points, it comes from another process or host,
+// so ${NAME} placeholders aren't expanded (environment belongs to the generator, not to the caller) and fields
+// referring to files are rejected, configs built by programs usually have lower case addresses, so only wrong
+// checksums are rejected
+func parseForeignConfig(configJson []byte) (*genesisConfig, error) {
+	var violations configViolations
+	for _, placeholder := range configPlaceholder.FindAll(configJson, -1) {
+		violations.add("config", "placeholder %s isn't supported, expand variables before submitting the config", placeholder)
+	}
+	if len(violations) > 0 {
+		return nil, violations
+	}
+	config, err := decodeGenesisConfig(configJson, true)
+	if err != nil {
+		return nil, err
+	}
+	for i, contract := range config.ExtraContracts {
+		if contract.Artifact != "" {
+			violations.add(fmt.Sprintf("extraContracts[%d].artifact", i), "refers to a file, it isn't supported without the filesystem")
+		}
+		if contract.Abi != "" {
+			violations.add(fmt.Sprintf("extraContracts[%d].abi", i), "refers to a file, it isn't supported without the filesystem")
+		}
+	}
+	for i, token := range config.Tokens {
+		if token.Artifact != "" {
+			violations.add(fmt.Sprintf("tokens[%d].artifact", i), "refers to a file, it isn't supported without the filesystem")
+		}
+	}
+	for i, validator := range config.Validators {
+		if validator.Keystore != "" {
+			violations.add(fmt.Sprintf("validators[%d].keystore", i), "refers to a file, it isn't supported without the filesystem")
+		}
+	}
+	if len(violations) > 0 {
+		return nil, violations
+	}
+	return config, nil
+}
+
+// inspectGenesisJson decodes storage of the system contract in genesis JSON with the embedded storage layout,
+// it's the inspect command with --json
+func inspectGenesisJson(genesisJson []byte, contractName string, raw bool) ([]byte, error) {
+	genesis, err := bgenesis.Parse(genesisJson)
+	if err != nil {
+		return nil, err
+	}
+	name, address, err := resolveSystemContract(contractName)
+	if err != nil {
+		return nil, err
+	}
+	layout, err := loadStorageLayout(name, "", "")
+	if err != nil {
+		return nil, err
+	}
+	var knownAddresses []common.Address
+	for account := range genesis.Alloc {
+		knownAddresses = append(knownAddresses, account)
+	}
+	// init of system contracts is executed against precompiles of the process
+	unlock := lockSimulation(nil)
+	variables, err := decodeContractStorage(genesis, address, layout, raw, knownAddresses)
+	unlock()
+	if err != nil {
+		return nil, err
+	}
+	return json.Marshal(variables)
+}
+
+// forForeignCaller converts result for callers through the C ABI, the output is either the result with code 0
+// or the error object of --error-format json with exit code of the error category
+func forForeignCaller(result []byte, err error) ([]byte, int) {
+	if err != nil {
+		output, _ := json.Marshal(newErrorOutput(err))
+		return output, errorExitCode(err)
+	}
+	return result, 0
+}
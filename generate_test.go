@@ -0,0 +1,81 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestGenerateGenesisJsonMatchesFile(t *testing.T) {
+	configJson, err := json.Marshal(devNetConfig)
+	if err != nil {
+		t.Fatal(err)
+	}
+	genesis, err := generateGenesisJson(configJson)
+	if err != nil {
+		t.Fatal(err)
+	}
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := createGenesisConfig(devNetConfig, outputFile, defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+	expected, err := os.ReadFile(outputFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !bytes.Equal(bytes.TrimSpace(genesis), bytes.TrimSpace(expected)) {
+		t.Fatalf("in-memory genesis differs from the written one")
+	}
+	if _, err := generateGenesisJson([]byte(`{"chainId": "x"}`)); err == nil {
+		t.Fatal("invalid config is accepted")
+	}
+}
+
+func TestForeignCallerError(t *testing.T) {
+	config := devNetConfig
+	config.Safe = &safeConfig{}
+	configJson, err := json.Marshal(config)
+	if err != nil {
+		t.Fatal(err)
+	}
+	output, code := forForeignCaller(generateGenesisJson(configJson))
+	if code != exitCodeConfig {
+		t.Fatalf("expected config error, got code %d: %s", code, output)
+	}
+	result := &errorOutput{}
+	if err := json.Unmarshal(output, result); err != nil || result.Category != "config" || len(result.Violations) == 0 {
+		t.Fatalf("unexpected error output %s (%v)", output, err)
+	}
+}
+
+func TestLoadAndInspectJson(t *testing.T) {
+	configJson, err := json.Marshal(devNetConfig)
+	if err != nil {
+		t.Fatal(err)
+	}
+	loaded, err := loadConfigJson(configJson)
+	if err != nil {
+		t.Fatal(err)
+	}
+	config := &genesisConfig{}
+	if err := json.Unmarshal(loaded, config); err != nil || config.ChainId != devNetConfig.ChainId {
+		t.Fatalf("unexpected loaded config %s (%v)", loaded, err)
+	}
+	genesis, err := generateGenesisJson(configJson)
+	if err != nil {
+		t.Fatal(err)
+	}
+	output, err := inspectGenesisJson(genesis, "staking", false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	var variables []*decodedVariable
+	if err := json.Unmarshal(output, &variables); err != nil || len(variables) == 0 {
+		t.Fatalf("no variables decoded: %s (%v)", output, err)
+	}
+	if _, code := forForeignCaller(inspectGenesisJson(genesis, "unknown", false)); code != exitCodeFailure {
+		t.Fatalf("unexpected exit code %d", code)
+	}
+}
@@ -5,6 +5,8 @@ go 1.16
 require (
 	github.com/ethereum/go-ethereum v1.10.17
 	github.com/prometheus/common v0.6.0
+	golang.org/x/crypto v0.0.0-20210322153248-0c34fe9e7dc2
+	gopkg.in/yaml.v2 v2.3.0
 )
 
 replace github.com/ethereum/go-ethereum v1.10.17 => github.com/ankr-network/bas-template-bsc v0.0.0-20220404131216-e5ac3a4a037a
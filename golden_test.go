@@ -0,0 +1,67 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+// set UPDATE_GOLDEN=1 to regenerate fixtures after intentional changes of artifacts or serialization
+const updateGoldenEnv = "UPDATE_GOLDEN"
+
+var goldenPresets = map[string]genesisConfig{
+	"localnet": localNetConfig,
+	"devnet":   devNetConfig,
+}
+
+func TestGenesisGolden(t *testing.T) {
+	for name, config := range goldenPresets {
+		t.Run(name, func(t *testing.T) {
+			outputFile := filepath.Join(t.TempDir(), name+".json")
+			if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+				t.Fatalf("failed to generate genesis: %s", err)
+			}
+			actual, err := os.ReadFile(outputFile)
+			if err != nil {
+				t.Fatal(err)
+			}
+			goldenFile := filepath.Join("testdata", "golden", name+".json")
+			if os.Getenv(updateGoldenEnv) != "" {
+				if err := os.MkdirAll(filepath.Dir(goldenFile), 0755); err != nil {
+					t.Fatal(err)
+				}
+				if err := os.WriteFile(goldenFile, actual, 0644); err != nil {
+					t.Fatal(err)
+				}
+				return
+			}
+			expected, err := os.ReadFile(goldenFile)
+			if err != nil {
+				t.Fatalf("failed to read golden file (run with %s=1 to create it): %s", updateGoldenEnv, err)
+			}
+			if !bytes.Equal(expected, actual) {
+				t.Fatalf("genesis doesn't match %s (run with %s=1 to update it):\n%s", goldenFile, updateGoldenEnv, firstDifference(expected, actual))
+			}
+		})
+	}
+}
+
+// firstDifference returns lines around the first mismatch, full genesis files are too big to print
+func firstDifference(expected, actual []byte) string {
+	expectedLines, actualLines := bytes.Split(expected, []byte("\n")), bytes.Split(actual, []byte("\n"))
+	for i := 0; i < len(expectedLines) || i < len(actualLines); i++ {
+		var want, have []byte
+		if i < len(expectedLines) {
+			want = expectedLines[i]
+		}
+		if i < len(actualLines) {
+			have = actualLines[i]
+		}
+		if !bytes.Equal(want, have) {
+			return fmt.Sprintf("line %d:\n  want: %.200s\n  have: %.200s", i+1, want, have)
+		}
+	}
+	return ""
+}
@@ -0,0 +1,54 @@
+package main
+
+import (
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/math"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/params"
+)
+
+const minGenesisGasLimit = params.MinGasLimit
+
+// headerConfig overrides fields of the genesis block header, missing fields keep defaults
+type headerConfig struct {
+	// unix seconds, RFC3339 datetime with timezone or "now"
+	Timestamp  string                `json:"timestamp,omitempty"`
+	GasLimit   *math.HexOrDecimal64  `json:"gasLimit,omitempty"`
+	Difficulty *math.HexOrDecimal256 `json:"difficulty,omitempty"`
+	MixHash    *common.Hash          `json:"mixHash,omitempty"`
+	Coinbase   *common.Address       `json:"coinbase,omitempty"`
+	Nonce      *math.HexOrDecimal64  `json:"nonce,omitempty"`
+}
+
+// applyHeaderConfig writes header fields into genesis, --timestamp and other overrides are already applied
+// to the config
+func applyHeaderConfig(genesis *core.Genesis, config *headerConfig) error {
+	if config == nil {
+		config = &headerConfig{}
+	}
+	if config.Timestamp != "" {
+		value, err := parseTimestamp(config.Timestamp)
+		if err != nil {
+			return err
+		}
+		genesis.Timestamp = value
+	}
+	if config.GasLimit != nil {
+		genesis.GasLimit = uint64(*config.GasLimit)
+	}
+	if config.Difficulty != nil {
+		genesis.Difficulty = new(big.Int).Set((*big.Int)(config.Difficulty))
+	}
+	if config.MixHash != nil {
+		genesis.Mixhash = *config.MixHash
+	}
+	if config.Coinbase != nil {
+		genesis.Coinbase = *config.Coinbase
+	}
+	if config.Nonce != nil {
+		genesis.Nonce = uint64(*config.Nonce)
+	}
+	return nil
+}
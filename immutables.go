@@ -0,0 +1,168 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"sort"
+	"strconv"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// immutableValue is a Solidity immutable variable with the value its constructor wrote into deployed code
+type immutableValue struct {
+	// variable name from the artifact's AST, or ast#<id> if the artifact has no AST
+	Name string `json:"name"`
+	// Solidity type (only if the artifact has AST)
+	Type  string        `json:"type,omitempty"`
+	Value hexutil.Bytes `json:"value"`
+}
+
+// immutablesReport lists immutables of one contract
+type immutablesReport struct {
+	Contract string            `json:"contract"`
+	Address  checksumAddress   `json:"address"`
+	Values   []*immutableValue `json:"values"`
+}
+
+// immutableSource is a deployed contract with the artifact it was built from
+type immutableSource struct {
+	name        string
+	address     common.Address
+	rawArtifact []byte
+}
+
+type immutablesArtifact struct {
+	ImmutableReferences map[string][]immutableReference `json:"immutableReferences"`
+	Ast                 json.RawMessage                 `json:"ast"`
+}
+
+type astVariable struct {
+	name       string
+	typeString string
+}
+
+// findAstVariables collects variable declarations by AST id, the tree is walked generically, so artifacts of any
+// solc version work
+func findAstVariables(node interface{}, result map[int64]*astVariable) {
+	switch value := node.(type) {
+	case map[string]interface{}:
+		if value["nodeType"] == "VariableDeclaration" {
+			id, idOk := value["id"].(float64)
+			name, nameOk := value["name"].(string)
+			if idOk && nameOk {
+				variable := &astVariable{name: name}
+				if descriptions, ok := value["typeDescriptions"].(map[string]interface{}); ok {
+					variable.typeString, _ = descriptions["typeString"].(string)
+				}
+				result[int64(id)] = variable
+			}
+		}
+		for _, child := range value {
+			findAstVariables(child, result)
+		}
+	case []interface{}:
+		for _, child := range value {
+			findAstVariables(child, result)
+		}
+	}
+}
+
+// readImmutables returns values of immutables from deployed code, the first reference of each variable is used
+// (all of them hold the same value)
+func readImmutables(rawArtifact []byte, code []byte) ([]*immutableValue, error) {
+	artifact := &immutablesArtifact{}
+	if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+		return nil, err
+	}
+	if len(artifact.ImmutableReferences) == 0 {
+		return nil, nil
+	}
+	variables := make(map[int64]*astVariable)
+	if len(artifact.Ast) > 0 {
+		var ast interface{}
+		if err := json.Unmarshal(artifact.Ast, &ast); err == nil {
+			findAstVariables(ast, variables)
+		}
+	}
+	var ids []int64
+	for key := range artifact.ImmutableReferences {
+		id, err := strconv.ParseInt(key, 10, 64)
+		if err != nil {
+			return nil, fmt.Errorf("invalid immutable reference id %q", key)
+		}
+		ids = append(ids, id)
+	}
+	sort.Slice(ids, func(i, j int) bool { return ids[i] < ids[j] })
+	var result []*immutableValue
+	for _, id := range ids {
+		references := artifact.ImmutableReferences[strconv.FormatInt(id, 10)]
+		if len(references) == 0 {
+			continue
+		}
+		reference := references[0]
+		if reference.Start < 0 || reference.Length < 0 || reference.Start+reference.Length > len(code) {
+			return nil, fmt.Errorf("immutable reference %d+%d is out of code", reference.Start, reference.Length)
+		}
+		value := &immutableValue{Name: fmt.Sprintf("ast#%d", id), Value: common.CopyBytes(code[reference.Start : reference.Start+reference.Length])}
+		if variable, ok := variables[id]; ok {
+			value.Name, value.Type = variable.name, variable.typeString
+		}
+		result = append(result, value)
+	}
+	return result, nil
+}
+
+// collectImmutables reports immutables of all contracts that have them, unreadable artifacts are skipped, they
+// are already checked by deployment
+func collectImmutables(genesis *core.Genesis, sources []immutableSource) []*immutablesReport {
+	var result []*immutablesReport
+	for _, source := range sources {
+		values, err := readImmutables(source.rawArtifact, genesis.Alloc[source.address].Code)
+		if err != nil || len(values) == 0 {
+			continue
+		}
+		result = append(result, &immutablesReport{Contract: source.name, Address: checksumAddress(source.address), Values: values})
+	}
+	return result
+}
+
+// immutableSources returns deployed system contracts, tokens, vesting and extra contracts (with JSON artifacts)
+func immutableSources(config genesisConfig, deployments []*systemContractDeployment, artifacts artifactSet) []immutableSource {
+	var result []immutableSource
+	for _, deployment := range deployments {
+		result = append(result, immutableSource{name: deployment.name, address: deployment.address, rawArtifact: artifacts[deployment.name]})
+	}
+	for _, contract := range config.ExtraContracts {
+		if rawArtifact, err := os.ReadFile(contract.Artifact); err == nil {
+			result = append(result, immutableSource{name: contract.name(), address: contract.Address, rawArtifact: rawArtifact})
+		}
+	}
+	for _, token := range config.Tokens {
+		if rawArtifact, err := token.rawArtifact(); err == nil {
+			result = append(result, immutableSource{name: token.name(), address: token.Address, rawArtifact: rawArtifact})
+		}
+	}
+	if config.Vesting != nil {
+		result = append(result, immutableSource{name: "GenesisVesting", address: config.Vesting.address(), rawArtifact: genesisVestingRawArtifact})
+	}
+	return result
+}
+
+func printImmutables(w io.Writer, reports []*immutablesReport) {
+	fmt.Fprintf(w, "immutable variable(s) of %d contract(s):\n", len(reports))
+	for _, report := range reports {
+		fmt.Fprintf(w, " + %s at %s\n", report.Contract, report.Address.Hex())
+		for _, value := range report.Values {
+			rendered := value.Value.String()
+			if value.Type == "address" && len(value.Value) == common.HashLength {
+				rendered = common.BytesToAddress(value.Value).Hex()
+			}
+			fmt.Fprintf(w, "   %s %s = %s\n", value.Type, value.Name, rendered)
+		}
+	}
+}
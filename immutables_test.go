@@ -0,0 +1,45 @@
+package main
+
+import (
+	"bytes"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+func TestReadImmutables(t *testing.T) {
+	ast := `{"nodeType": "SourceUnit", "nodes": [{"nodeType": "ContractDefinition", "nodes": [
+		{"nodeType": "VariableDeclaration", "id": 7, "name": "owner", "typeDescriptions": {"typeString": "address"}},
+		{"nodeType": "VariableDeclaration", "id": 9, "name": "balance", "typeDescriptions": {"typeString": "uint256"}}]}]}`
+	artifact := []byte(`{"immutableReferences": {"7": [{"start": 1, "length": 32}, {"start": 33, "length": 32}], "3": [{"start": 65, "length": 1}]}, "ast": ` + ast + `}`)
+	owner := common.HexToAddress("0x00000000000000000000000000000000000000aa")
+	code := append([]byte{0x60}, common.LeftPadBytes(owner.Bytes(), 32)...)
+	code = append(code, common.LeftPadBytes(owner.Bytes(), 32)...)
+	code = append(code, 0x2a)
+	values, err := readImmutables(artifact, code)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(values) != 2 || values[0].Name != "ast#3" || values[0].Value.String() != "0x2a" {
+		t.Fatalf("unexpected values %+v", values)
+	}
+	if values[1].Name != "owner" || values[1].Type != "address" || common.BytesToAddress(values[1].Value) != owner {
+		t.Fatalf("unexpected owner %+v", values[1])
+	}
+	if _, err := readImmutables(artifact, code[:40]); err == nil {
+		t.Fatal("reference out of code is accepted")
+	}
+	buffer := &bytes.Buffer{}
+	printImmutables(buffer, []*immutablesReport{{Contract: "Token", Address: checksumAddress(owner), Values: values}})
+	if !strings.Contains(buffer.String(), "address owner = "+owner.Hex()) {
+		t.Fatalf("owner isn't printed as address:\n%s", buffer.String())
+	}
+}
+
+func TestReadImmutablesWithoutReferences(t *testing.T) {
+	if values, err := readImmutables([]byte(`{"deployedBytecode": "0x00"}`), hexutil.MustDecode("0x00")); err != nil || values != nil {
+		t.Fatalf("unexpected values %v (%v)", values, err)
+	}
+}
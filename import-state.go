@@ -0,0 +1,215 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"math/big"
+	"os"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/common/math"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/log"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+// dumpedAccount is an account of geth state dump (`geth dump` JSONL or debug_dumpBlock result)
+type dumpedAccount struct {
+	Balance string                 `json:"balance"`
+	Nonce   uint64                 `json:"nonce"`
+	Code    hexutil.Bytes          `json:"code,omitempty"`
+	Storage map[common.Hash]string `json:"storage,omitempty"`
+	// address is missing if the node doesn't have preimages of the state trie keys
+	Address *common.Address `json:"address,omitempty"`
+	Key     hexutil.Bytes   `json:"key,omitempty"`
+}
+
+// stateDumpLine is one JSON value of the dump, `geth dump` writes root line followed by account lines and
+// debug_dumpBlock returns a single object with accounts keyed by address
+type stateDumpLine struct {
+	dumpedAccount
+	Root     *common.Hash                      `json:"root,omitempty"`
+	Accounts map[common.Address]*dumpedAccount `json:"accounts,omitempty"`
+}
+
+func (a *dumpedAccount) genesisAccount() (core.GenesisAccount, error) {
+	balance := big.NewInt(0)
+	if a.Balance != "" {
+		var ok bool
+		if balance, ok = math.ParseBig256(a.Balance); !ok {
+			return core.GenesisAccount{}, fmt.Errorf("invalid balance %q", a.Balance)
+		}
+	}
+	result := core.GenesisAccount{Balance: balance, Nonce: a.Nonce, Code: a.Code}
+	if len(a.Storage) > 0 {
+		result.Storage = make(map[common.Hash]common.Hash, len(a.Storage))
+		for key, value := range a.Storage {
+			// geth trims leading zeros of storage values
+			if !strings.HasPrefix(value, "0x") || len(value) > 2+2*common.HashLength {
+				return core.GenesisAccount{}, fmt.Errorf("invalid storage value %q of slot %s", value, key.Hex())
+			}
+			result.Storage[key] = common.HexToHash(value)
+		}
+	}
+	return result, nil
+}
+
+// readStateDump reads accounts of the state dump, all broken accounts are reported at once
+func readStateDump(path string) (core.GenesisAlloc, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+	result := make(core.GenesisAlloc)
+	var failures []string
+	add := func(address common.Address, account *dumpedAccount) {
+		genesisAccount, err := account.genesisAccount()
+		if err != nil {
+			failures = append(failures, fmt.Sprintf("%s: %s", address.Hex(), err))
+			return
+		}
+		if _, ok := result[address]; ok {
+			failures = append(failures, fmt.Sprintf("%s: duplicate account", address.Hex()))
+			return
+		}
+		result[address] = genesisAccount
+	}
+	decoder := json.NewDecoder(file)
+	for index := 1; ; index++ {
+		line := &stateDumpLine{}
+		if err := decoder.Decode(line); errors.Is(err, io.EOF) {
+			break
+		} else if err != nil {
+			return nil, fmt.Errorf("%s: value %d: %w", path, index, err)
+		}
+		switch {
+		case line.Accounts != nil:
+			for address, account := range line.Accounts {
+				add(address, account)
+			}
+		case line.Address != nil:
+			add(*line.Address, &line.dumpedAccount)
+		case line.Root != nil:
+			// root line of `geth dump`
+		default:
+			failures = append(failures, fmt.Sprintf("value %d: account without address (key %s), dump the state with preimages", index, line.Key))
+		}
+	}
+	if len(failures) > 0 {
+		return nil, fmt.Errorf("state dump %s has %d invalid account(s):\n  %s", path, len(failures), strings.Join(failures, "\n  "))
+	}
+	return result, nil
+}
+
+// mergeStateDump adds dumped accounts to genesis alloc, balances of plain genesis accounts (faucet, validators) are
+// summed, accounts with code or storage in genesis (system contracts, predeploys) can't be replaced
+func mergeStateDump(genesis *core.Genesis, dump core.GenesisAlloc) error {
+	var addresses []common.Address
+	for address := range dump {
+		addresses = append(addresses, address)
+	}
+	sort.Slice(addresses, func(i, j int) bool {
+		return bytes.Compare(addresses[i][:], addresses[j][:]) < 0
+	})
+	var conflicts []string
+	for _, address := range addresses {
+		account := dump[address]
+		existing, ok := genesis.Alloc[address]
+		if !ok {
+			genesis.Alloc[address] = account
+			continue
+		}
+		if len(existing.Code) > 0 || len(existing.Storage) > 0 || len(account.Code) > 0 || len(account.Storage) > 0 {
+			conflicts = append(conflicts, address.Hex())
+			continue
+		}
+		if existing.Balance == nil {
+			existing.Balance = new(big.Int)
+		}
+		existing.Balance = new(big.Int).Add(existing.Balance, account.Balance)
+		if account.Nonce > existing.Nonce {
+			existing.Nonce = account.Nonce
+		}
+		genesis.Alloc[address] = existing
+	}
+	if len(conflicts) > 0 {
+		return fmt.Errorf("%d dumped account(s) collide with contracts in genesis:\n  %s", len(conflicts), strings.Join(conflicts, "\n  "))
+	}
+	return nil
+}
+
+// runGenImportStateCommand merges geth state dump into existing genesis, so chain can be relaunched with user
+// balances, contracts and their storage preserved
+func runGenImportStateCommand(args []string) error {
+	flags := flag.NewFlagSet("gen import-state", flag.ContinueOnError)
+	dumpFile := flags.String("dump", "", "geth state dump (JSONL of geth dump or JSON result of debug_dumpBlock)")
+	genesisFile := flags.String("genesis", "", "generated genesis file the state is merged into")
+	outputFile := flags.String("output", "", "output file, .gz suffix enables gzip compression (default is the genesis file)")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if *dumpFile == "" || *genesisFile == "" || flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen import-state --dump <state.jsonl> --genesis <genesis.json> [--output <file>]")
+	}
+	if *outputFile == "" {
+		*outputFile = *genesisFile
+	}
+	genesis, err := bgenesis.Load(*genesisFile)
+	if err != nil {
+		return err
+	}
+	dump, err := readStateDump(*dumpFile)
+	if err != nil {
+		return err
+	}
+	if err := mergeStateDump(genesis.Genesis, dump); err != nil {
+		return err
+	}
+	total := big.NewInt(0)
+	for _, account := range dump {
+		total.Add(total, account.Balance)
+	}
+	log.Info("Imported state", "accounts", len(dump), "balance", formatAmount(total))
+	formatted, err := formatGenesis(genesis.Genesis, "geth")
+	if err != nil {
+		return err
+	}
+	if formatted, err = withOriginalConfig(formatted, *genesisFile); err != nil {
+		return err
+	}
+	return writeFormattedOutput(*outputFile, formatted, "json")
+}
+
+// withOriginalConfig keeps chain config of the input genesis verbatim, it may contain fields unknown to the built-in
+// node (e.g. fork blocks of newer BSC releases)
+func withOriginalConfig(formatted interface{}, genesisFile string) (interface{}, error) {
+	data, err := os.ReadFile(genesisFile)
+	if err != nil {
+		return nil, err
+	}
+	original := &struct {
+		Config json.RawMessage `json:"config"`
+	}{}
+	if err := json.Unmarshal(data, original); err != nil {
+		return nil, err
+	}
+	if len(original.Config) == 0 {
+		return formatted, nil
+	}
+	result := formatted.(orderedObject)
+	for i, field := range result {
+		if field.Key == "config" {
+			result[i].Value = original.Config
+		}
+	}
+	return result, nil
+}
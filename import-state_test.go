@@ -0,0 +1,45 @@
+package main
+
+import (
+	"math/big"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestImportStateDump(t *testing.T) {
+	dump := `{"root": "0x5e7c0e8ea7e0a3ab30e6bf3db3d72b6e5a4b6b7f9e5b7f4b5e5e5e5e5e5e5e5e"}
+{"balance": "1000", "nonce": 3, "root": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421", "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470", "address": "0x00a601f45688dba8a070722073b015277cf36725", "key": "0x01"}
+{"balance": "0", "nonce": 1, "root": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421", "codeHash": "0x01", "code": "0x6000", "storage": {"0x0000000000000000000000000000000000000000000000000000000000000000": "0x2a"}, "address": "0x0000000000000000000000000000000000abcdef", "key": "0x02"}
+`
+	path := filepath.Join(t.TempDir(), "state.jsonl")
+	if err := os.WriteFile(path, []byte(dump), 0644); err != nil {
+		t.Fatal(err)
+	}
+	alloc, err := readStateDump(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	holder := common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725")
+	contract := common.HexToAddress("0x0000000000000000000000000000000000abcdef")
+	if len(alloc) != 2 || alloc[contract].Storage[common.Hash{}] != common.BigToHash(big.NewInt(42)) {
+		t.Fatalf("unexpected alloc: %v", alloc)
+	}
+	genesis := &core.Genesis{Alloc: core.GenesisAlloc{
+		holder:         {Balance: big.NewInt(500)},
+		stakingAddress: {Balance: big.NewInt(0), Code: []byte{0x60, 0x00}},
+	}}
+	if err := mergeStateDump(genesis, alloc); err != nil {
+		t.Fatal(err)
+	}
+	if balance := genesis.Alloc[holder].Balance; balance.Int64() != 1500 || genesis.Alloc[holder].Nonce != 3 {
+		t.Fatalf("unexpected merged holder: %v", genesis.Alloc[holder])
+	}
+	alloc[stakingAddress] = core.GenesisAccount{Balance: big.NewInt(1)}
+	if err := mergeStateDump(genesis, alloc); err == nil {
+		t.Fatal("system contract is overwritten by dump")
+	}
+}
@@ -0,0 +1,135 @@
+package main
+
+import (
+	"encoding/json"
+	"flag"
+	"fmt"
+	"os"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+// runInspectCommand decodes storage of the system contract from genesis file into named variables
+func runInspectCommand(args []string) error {
+	flags := flag.NewFlagSet("inspect", flag.ContinueOnError)
+	contractName := flags.String("contract", "", "system contract name (e.g. staking) or address")
+	layoutPath := flags.String("layout", "", "path to solc storage layout JSON (default is layout from the artifact)")
+	artifactsPath := flags.String("artifacts", "", "path to directory with contract artifacts (default is embedded)")
+	raw := flags.Bool("raw", false, "decode storage as it is in genesis, without running init of system contracts")
+	jsonOutput := flags.Bool("json", false, "print result as JSON")
+	annotated := flags.Bool("annotated", false, "list every storage slot of the genesis alloc with variables stored in it")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if flags.NArg() != 1 || *contractName == "" {
+		return fmt.Errorf("usage: inspect --contract <name> [--layout <file>] [--raw] [--annotated] [--json] <genesis.json>")
+	}
+	genesis, err := bgenesis.Load(flags.Arg(0))
+	if err != nil {
+		return err
+	}
+	name, address, err := resolveSystemContract(*contractName)
+	if err != nil {
+		return err
+	}
+	layout, err := loadStorageLayout(name, *layoutPath, *artifactsPath)
+	if err != nil {
+		return err
+	}
+	var knownAddresses []common.Address
+	for account := range genesis.Alloc {
+		knownAddresses = append(knownAddresses, account)
+	}
+	// slots are annotated as they are in genesis, init changes aren't part of the alloc
+	if *annotated {
+		slots, err := annotateStorage(layout, genesis.Alloc[address].Storage, knownAddresses)
+		if err != nil {
+			return err
+		}
+		if *jsonOutput {
+			result, _ := json.MarshalIndent(slots, "", "  ")
+			fmt.Println(string(result))
+			return nil
+		}
+		fmt.Printf("%s (%s):\n", name, address.Hex())
+		for _, slot := range slots {
+			variables := "unknown"
+			if len(slot.Variables) > 0 {
+				variables = strings.Join(slot.Variables, ", ")
+			}
+			fmt.Printf("  %s = %s: %s\n", slot.Slot.Hex(), slot.Value.Hex(), variables)
+		}
+		return nil
+	}
+	variables, err := decodeContractStorage(genesis, address, layout, *raw, knownAddresses)
+	if err != nil {
+		return err
+	}
+	if *jsonOutput {
+		result, _ := json.MarshalIndent(variables, "", "  ")
+		fmt.Println(string(result))
+		return nil
+	}
+	fmt.Printf("%s (%s):\n", name, address.Hex())
+	for _, v := range variables {
+		fmt.Printf("  %s (%s) = %s [slot %s offset %d]\n", v.Label, v.Type, v.Value, v.Slot.Hex(), v.Offset)
+	}
+	return nil
+}
+
+// decodeContractStorage decodes storage of the contract into named variables, unless raw is set init of system
+// contracts is executed first
+func decodeContractStorage(genesis *bgenesis.Genesis, address common.Address, layout *storageLayout, raw bool, knownAddresses []common.Address) ([]*decodedVariable, error) {
+	statedb, err := newStateFromGenesis(genesis.Genesis)
+	if err != nil {
+		return nil, err
+	}
+	// stakes and validators are written by init function, node calls it in the first block
+	if !raw {
+		if err := initSystemContracts(newGenesisEVM(genesis.Genesis, statedb)); err != nil {
+			return nil, err
+		}
+	}
+	return decodeStorageLayout(layout, func(slot common.Hash) common.Hash {
+		return statedb.GetState(address, slot)
+	}, knownAddresses)
+}
+
+// resolveSystemContract finds system contract by case-insensitive name or by address
+func resolveSystemContract(nameOrAddress string) (string, common.Address, error) {
+	for name, address := range bgenesis.SystemContracts {
+		if strings.EqualFold(name, nameOrAddress) || common.IsHexAddress(nameOrAddress) && common.HexToAddress(nameOrAddress) == address {
+			return name, address, nil
+		}
+	}
+	return "", common.Address{}, fmt.Errorf("unknown system contract: %s", nameOrAddress)
+}
+
+func loadStorageLayout(contractName, layoutPath, artifactsPath string) (*storageLayout, error) {
+	if layoutPath != "" {
+		data, err := os.ReadFile(layoutPath)
+		if err != nil {
+			return nil, err
+		}
+		layout := &storageLayout{}
+		if err := json.Unmarshal(data, layout); err != nil {
+			return nil, fmt.Errorf("failed to parse storage layout %s: %w", layoutPath, err)
+		}
+		return layout, nil
+	}
+	artifacts, err := loadArtifactSet(artifactsPath)
+	if err != nil {
+		return nil, err
+	}
+	layouts, err := loadStorageLayouts(artifacts, artifactsPath)
+	if err != nil {
+		return nil, err
+	}
+	if layouts[contractName] == nil {
+		return nil, fmt.Errorf("artifact of %s doesn't contain storage layout, pass it with --layout or put %s%s next to the artifact", contractName, contractName, storageLayoutSuffix)
+	}
+	return layouts[contractName], nil
+}
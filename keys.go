@@ -0,0 +1,181 @@
+package main
+
+import (
+	"encoding/hex"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/keystore"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+// generatedValidator is a validator with freshly generated keys, its files are in Dir
+type generatedValidator struct {
+	Dir          string
+	Address      common.Address
+	KeystoreFile string
+	// public key of the p2p node key (enode id)
+	NodeId       string
+	BlsPublicKey []byte
+}
+
+// readPasswords reads password file, it has either one line (same password for all keys) or one line per key
+func readPasswords(path string, count int) ([]string, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	lines := strings.Split(strings.TrimRight(string(data), "\r\n"), "\n")
+	for i := range lines {
+		lines[i] = strings.TrimRight(lines[i], "\r")
+	}
+	if len(lines) == 1 {
+		for len(lines) < count {
+			lines = append(lines, lines[0])
+		}
+	}
+	if len(lines) != count {
+		return nil, fmt.Errorf("password file %s must have 1 or %d line(s), got %d", path, count, len(lines))
+	}
+	return lines, nil
+}
+
+// generateValidatorKeys writes encrypted validator key (geth keystore), unencrypted p2p node key and optionally
+// EIP-2335 BLS vote key into the directory, files are placed as geth expects them in the data directory
+func generateValidatorKeys(dir, password string, withBls, lightKdf bool) (*generatedValidator, error) {
+	scryptN, scryptP := keystore.StandardScryptN, keystore.StandardScryptP
+	if lightKdf {
+		scryptN, scryptP = keystore.LightScryptN, keystore.LightScryptP
+	}
+	account, err := keystore.NewKeyStore(filepath.Join(dir, "keystore"), scryptN, scryptP).NewAccount(password)
+	if err != nil {
+		return nil, err
+	}
+	result := &generatedValidator{Dir: dir, Address: account.Address, KeystoreFile: account.URL.Path}
+	nodeKey, err := crypto.GenerateKey()
+	if err != nil {
+		return nil, err
+	}
+	if err := os.MkdirAll(filepath.Join(dir, "geth"), 0700); err != nil {
+		return nil, err
+	}
+	if err := crypto.SaveECDSA(filepath.Join(dir, "geth", "nodekey"), nodeKey); err != nil {
+		return nil, err
+	}
+	result.NodeId = hex.EncodeToString(crypto.FromECDSAPub(&nodeKey.PublicKey)[1:])
+	if !withBls {
+		return result, nil
+	}
+	secret, publicKey, err := generateBlsKey()
+	if err != nil {
+		return nil, err
+	}
+	blsKey, err := encryptBlsKey(secret, publicKey, password, scryptN)
+	if err != nil {
+		return nil, err
+	}
+	blsKeyJson, _ := json.MarshalIndent(blsKey, "", "  ")
+	if err := os.MkdirAll(filepath.Join(dir, "bls", "keystore"), 0700); err != nil {
+		return nil, err
+	}
+	if err := os.WriteFile(filepath.Join(dir, "bls", "keystore", "keystore-"+blsKey.Uuid+".json"), blsKeyJson, 0600); err != nil {
+		return nil, err
+	}
+	result.BlsPublicKey = publicKey
+	return result, nil
+}
+
+// genesisConfigSkeleton returns config with generated validators and local net defaults, it's a starting point
+// to be edited by the operator
+func genesisConfigSkeleton(chainId int64, validators []*generatedValidator) orderedObject {
+	params := localNetConfig.ConsensusParams
+	params.ActiveValidatorsLength = uint32(len(validators))
+	var validatorEntries []interface{}
+	initialStakes := orderedObject{}
+	withBls := false
+	for _, validator := range validators {
+		entry := orderedObject{{Key: "address", Value: validator.Address.Hex()}}
+		if len(validator.BlsPublicKey) > 0 {
+			entry = append(entry, orderedField{Key: "blsPublicKey", Value: hexutil.Encode(validator.BlsPublicKey)})
+			withBls = true
+		}
+		validatorEntries = append(validatorEntries, entry)
+		initialStakes = append(initialStakes, orderedField{Key: validator.Address.Hex(), Value: "1000 ether"})
+	}
+	result := orderedObject{
+		{Key: "version", Value: currentConfigVersion},
+		{Key: "chainId", Value: chainId},
+		{Key: "deployers", Value: []string{validators[0].Address.Hex()}},
+		{Key: "validators", Value: validatorEntries},
+		{Key: "systemTreasury", Value: validators[0].Address.Hex()},
+		{Key: "consensusParams", Value: params},
+		{Key: "votingPeriod", Value: localNetConfig.VotingPeriod},
+		{Key: "initialStakes", Value: initialStakes},
+		{Key: "faucet", Value: orderedObject{}},
+		{Key: "validatorOperationalBalance", Value: "10 ether"},
+	}
+	if withBls {
+		result = append(result, orderedField{Key: "extraData", Value: orderedObject{{Key: "layout", Value: "luban"}}})
+	}
+	return result
+}
+
+func runGenKeysCommand(args []string) error {
+	flags := flag.NewFlagSet("gen keys", flag.ContinueOnError)
+	count := flags.Int("validators", 1, "number of validators to generate")
+	outputDir := flags.String("out", "keys", "output directory, keys of each validator go to node<N> sub-directory")
+	passwordFile := flags.String("password", "", "file with keystore password (one line) or passwords (one line per validator)")
+	withBls := flags.Bool("bls", false, "generate BLS vote keys as well")
+	lightKdf := flags.Bool("light-kdf", false, "use light scrypt parameters (faster, only for test networks)")
+	chainId := flags.Int64("chain-id", localNetConfig.ChainId, "chain id of the genesis config skeleton")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if *count <= 0 {
+		return fmt.Errorf("number of validators must be positive")
+	}
+	if *passwordFile == "" {
+		return fmt.Errorf("--password is required")
+	}
+	validators, err := generateValidators(*outputDir, *passwordFile, *count, *withBls, *lightKdf)
+	if err != nil {
+		return err
+	}
+	skeletonFile := filepath.Join(*outputDir, "genesis-config.json")
+	skeleton, _ := encodeJson(genesisConfigSkeleton(*chainId, validators))
+	if err := writeOutput(skeletonFile, append(skeleton, '\n')); err != nil {
+		return err
+	}
+	for _, validator := range validators {
+		fmt.Printf(" + %s: %s\n", validator.Dir, validator.Address.Hex())
+	}
+	fmt.Printf("genesis config skeleton is written to %s\n", skeletonFile)
+	return nil
+}
+
+// generateValidators generates keys of count validators in node1..nodeN sub-directories
+func generateValidators(outputDir, passwordFile string, count int, withBls, lightKdf bool) ([]*generatedValidator, error) {
+	passwords, err := readPasswords(passwordFile, count)
+	if err != nil {
+		return nil, err
+	}
+	var result []*generatedValidator
+	for i := 0; i < count; i++ {
+		dir := filepath.Join(outputDir, fmt.Sprintf("node%d", i+1))
+		if _, err := os.Stat(dir); err == nil {
+			return nil, fmt.Errorf("%s already exists, keys are never overwritten", dir)
+		}
+		validator, err := generateValidatorKeys(dir, passwords[i], withBls, lightKdf)
+		if err != nil {
+			return nil, err
+		}
+		result = append(result, validator)
+	}
+	return result, nil
+}
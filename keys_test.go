@@ -0,0 +1,54 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestGeneratedConfigSkeletonIsValid(t *testing.T) {
+	dir := t.TempDir()
+	passwordFile := filepath.Join(dir, "password.txt")
+	if err := os.WriteFile(passwordFile, []byte("secret\n"), 0600); err != nil {
+		t.Fatal(err)
+	}
+	validators, err := generateValidators(filepath.Join(dir, "keys"), passwordFile, 2, true, true)
+	if err != nil {
+		t.Fatal(err)
+	}
+	skeleton, err := encodeJson(genesisConfigSkeleton(1337, validators))
+	if err != nil {
+		t.Fatal(err)
+	}
+	configFile := filepath.Join(dir, "genesis-config.json")
+	if err := os.WriteFile(configFile, skeleton, 0644); err != nil {
+		t.Fatal(err)
+	}
+	config, err := readGenesisConfig(configFile, false, nil)
+	if err != nil {
+		t.Fatalf("skeleton can't be parsed: %s", err)
+	}
+	if err := config.validate(); err != nil {
+		t.Fatalf("skeleton is invalid: %s", err)
+	}
+	if len(config.Validators) != 2 || config.Validators[1].Address != validators[1].Address {
+		t.Fatalf("wrong validators in skeleton: %v", config.Validators)
+	}
+	if _, err := generateValidators(filepath.Join(dir, "keys"), passwordFile, 1, false, true); err == nil {
+		t.Fatalf("existing keys are overwritten")
+	}
+}
+
+func TestReadPasswords(t *testing.T) {
+	passwordFile := filepath.Join(t.TempDir(), "password.txt")
+	if err := os.WriteFile(passwordFile, []byte("a\r\nb\n"), 0600); err != nil {
+		t.Fatal(err)
+	}
+	passwords, err := readPasswords(passwordFile, 2)
+	if err != nil || passwords[0] != "a" || passwords[1] != "b" {
+		t.Fatalf("wrong passwords %q (%v)", passwords, err)
+	}
+	if _, err := readPasswords(passwordFile, 3); err == nil {
+		t.Fatalf("password count mismatch is accepted")
+	}
+}
@@ -0,0 +1,112 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/json"
+	"fmt"
+	"os"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+// generatorVersion is overridden at build time with -ldflags "-X main.generatorVersion=..."
+var generatorVersion = "dev"
+
+const genesisLockVersion = 1
+
+// genesisLock captures exact inputs of the generation, with --frozen the next generation must have the same inputs
+type genesisLock struct {
+	Version          int                    `json:"version"`
+	GeneratorVersion string                 `json:"generatorVersion"`
+	ConfigHash       common.Hash            `json:"configHash"`
+	Artifacts        map[string]common.Hash `json:"artifacts"`
+	Flags            map[string]string      `json:"flags"`
+}
+
+func newGenesisLock(rawConfig []byte, artifacts artifactSet, flags map[string]string) *genesisLock {
+	lock := &genesisLock{
+		Version:          genesisLockVersion,
+		GeneratorVersion: generatorVersion,
+		ConfigHash:       sha256.Sum256(rawConfig),
+		Artifacts:        make(map[string]common.Hash),
+		Flags:            flags,
+	}
+	for name, rawArtifact := range artifacts {
+		lock.Artifacts[name] = sha256.Sum256(rawArtifact)
+	}
+	return lock
+}
+
+func readGenesisLock(path string) (*genesisLock, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	lock := &genesisLock{}
+	if err := json.Unmarshal(data, lock); err != nil {
+		return nil, fmt.Errorf("failed to parse lockfile %s: %w", path, err)
+	}
+	if lock.Version != genesisLockVersion {
+		return nil, fmt.Errorf("unsupported lockfile version %d in %s", lock.Version, path)
+	}
+	return lock, nil
+}
+
+func (l *genesisLock) writeTo(path string) error {
+	data, _ := json.MarshalIndent(l, "", "  ")
+	return writeOutput(path, append(data, '\n'))
+}
+
+// diff returns human-readable list of inputs that are different in the other lock
+func (l *genesisLock) diff(other *genesisLock) []string {
+	var result []string
+	if l.GeneratorVersion != other.GeneratorVersion {
+		result = append(result, fmt.Sprintf("generator version: %s != %s", l.GeneratorVersion, other.GeneratorVersion))
+	}
+	if l.ConfigHash != other.ConfigHash {
+		result = append(result, fmt.Sprintf("config hash: %s != %s", l.ConfigHash.Hex(), other.ConfigHash.Hex()))
+	}
+	diffMaps := func(kind string, locked, actual map[string]string) {
+		keys := make(map[string]bool)
+		for key := range locked {
+			keys[key] = true
+		}
+		for key := range actual {
+			keys[key] = true
+		}
+		var sortedKeys []string
+		for key := range keys {
+			sortedKeys = append(sortedKeys, key)
+		}
+		sort.Strings(sortedKeys)
+		for _, key := range sortedKeys {
+			if locked[key] != actual[key] {
+				result = append(result, fmt.Sprintf("%s %s: %q != %q", kind, key, locked[key], actual[key]))
+			}
+		}
+	}
+	artifactHashes := func(artifacts map[string]common.Hash) map[string]string {
+		hashes := make(map[string]string)
+		for name, hash := range artifacts {
+			hashes[name] = hash.Hex()
+		}
+		return hashes
+	}
+	diffMaps("artifact", artifactHashes(l.Artifacts), artifactHashes(other.Artifacts))
+	diffMaps("flag", l.Flags, other.Flags)
+	return result
+}
+
+// verifyGenesisLock fails if actual inputs don't match the lockfile exactly
+func verifyGenesisLock(path string, actual *genesisLock) error {
+	locked, err := readGenesisLock(path)
+	if err != nil {
+		return err
+	}
+	if differences := locked.diff(actual); len(differences) > 0 {
+		return fmt.Errorf("inputs don't match lockfile %s (locked != actual):\n  %s", path, strings.Join(differences, "\n  "))
+	}
+	return nil
+}
@@ -0,0 +1,55 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"os"
+
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// logFormats are formats of log records, logs always go to stderr because stdout can carry generated genesis
+var logFormats = map[string]func() log.Format{
+	"text":   func() log.Format { return utcFormat(log.TerminalFormat(false)) },
+	"logfmt": func() log.Format { return utcFormat(log.LogfmtFormat()) },
+	"json":   func() log.Format { return utcFormat(log.JSONFormat()) },
+}
+
+// utcFormat stamps records in UTC like all other timestamps the tool emits, geth formats use local time
+func utcFormat(format log.Format) log.Format {
+	return log.FormatFunc(func(r *log.Record) []byte {
+		record := *r
+		record.Time = r.Time.UTC()
+		return format.Format(&record)
+	})
+}
+
+func setupLogging(level log.Lvl, format string) error {
+	newFormat, ok := logFormats[format]
+	if !ok {
+		return fmt.Errorf("unknown log format %s, supported formats are: text, logfmt, json", format)
+	}
+	log.Root().SetHandler(log.LvlFilterHandler(level, log.StreamHandler(os.Stderr, newFormat())))
+	return nil
+}
+
+// registerLoggingFlags adds verbosity flags (-q, -v, -vv) and --log-format, returned function applies them
+// after flags are parsed
+func registerLoggingFlags(flags *flag.FlagSet) func() error {
+	quiet := flags.Bool("q", false, "log only warnings and errors")
+	verbose := flags.Bool("v", false, "log debug details (constructor calls, gas usage)")
+	veryVerbose := flags.Bool("vv", false, "log everything including trace records")
+	format := flags.String("log-format", "text", "format of log records: text, logfmt or json")
+	return func() error {
+		level := log.LvlInfo
+		switch {
+		case *veryVerbose:
+			level = log.LvlTrace
+		case *verbose:
+			level = log.LvlDebug
+		case *quiet:
+			level = log.LvlWarn
+		}
+		return setupLogging(level, *format)
+	}
+}
@@ -0,0 +1,22 @@
+package main
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/ethereum/go-ethereum/log"
+)
+
+func TestLogFormatsUseUtc(t *testing.T) {
+	record := &log.Record{Time: time.Date(2021, 4, 15, 12, 0, 0, 0, time.FixedZone("UTC+3", 3*3600)), Lvl: log.LvlInfo, Msg: "test"}
+	for name, newFormat := range logFormats {
+		output := string(newFormat().Format(record))
+		if !strings.Contains(output, "09:00:00") || strings.Contains(output, "12:00:00") {
+			t.Errorf("%s format doesn't stamp records in UTC: %s", name, output)
+		}
+	}
+	if record.Time.Location().String() != "UTC+3" {
+		t.Fatal("formatting changes the record")
+	}
+}
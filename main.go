@@ -0,0 +1,27 @@
+//go:build !js
+// +build !js
+
+package main
+
+import (
+	"errors"
+	"flag"
+	"os"
+
+	"github.com/ethereum/go-ethereum/log"
+)
+
+func main() {
+	// go-ethereum discards logs by default, commands with flags can change level and format
+	if err := setupLogging(log.LvlInfo, "text"); err != nil {
+		exitWithError(err, "text")
+	}
+	errorFormat, args, err := extractErrorFormat(os.Args[1:])
+	if err != nil {
+		exitWithError(err, "text")
+	}
+	// -h prints usage of the command, it isn't a failure
+	if err := runCommand(args); err != nil && !errors.Is(err, flag.ErrHelp) {
+		exitWithError(err, errorFormat)
+	}
+}
@@ -0,0 +1,109 @@
+package main
+
+import (
+	"bytes"
+	"encoding/binary"
+	"fmt"
+	"io"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+const (
+	codeMetadataKeep  = "keep"
+	codeMetadataStrip = "strip"
+	codeMetadataZero  = "zero"
+)
+
+var codeMetadataModes = []string{codeMetadataKeep, codeMetadataStrip, codeMetadataZero}
+
+func isCodeMetadataMode(mode string) bool {
+	for _, known := range codeMetadataModes {
+		if mode == known {
+			return true
+		}
+	}
+	return false
+}
+
+// metadataReport lists CBOR metadata sections removed from deployed code by normalizeCodeMetadata
+type metadataReport struct {
+	Mode      string                    `json:"mode"`
+	Contracts []*metadataContractReport `json:"contracts"`
+}
+
+type metadataContractReport struct {
+	Address checksumAddress `json:"address"`
+	// removed (or zeroed) section including two trailing length bytes
+	Metadata hexutil.Bytes `json:"metadata"`
+}
+
+// codeMetadataLength returns length of solc CBOR metadata appended to the code (including two length bytes),
+// zero if the code doesn't end with metadata
+func codeMetadataLength(code []byte) int {
+	if len(code) < 2 {
+		return 0
+	}
+	length := int(binary.BigEndian.Uint16(code[len(code)-2:])) + 2
+	if length > len(code) {
+		return 0
+	}
+	metadata := code[len(code)-length : len(code)-2]
+	// solc emits CBOR map with 1-5 keys, known keys make false positives on arbitrary code unlikely
+	if len(metadata) == 0 || metadata[0] < 0xa1 || metadata[0] > 0xa5 {
+		return 0
+	}
+	for _, key := range []string{"ipfs", "bzzr0", "bzzr1", "solc", "experimental"} {
+		if bytes.Contains(metadata, []byte(key)) {
+			return length
+		}
+	}
+	return 0
+}
+
+// normalizeCodeMetadata strips or zeroes metadata of all contracts in alloc, so code doesn't depend on source
+// paths and compiler settings of the build machine, "zero" keeps code size and offsets intact
+func normalizeCodeMetadata(genesis *core.Genesis, mode string) (*metadataReport, error) {
+	if mode != codeMetadataStrip && mode != codeMetadataZero {
+		return nil, fmt.Errorf("unknown code metadata mode %q, available are: %s", mode, strings.Join(codeMetadataModes, ", "))
+	}
+	report := &metadataReport{Mode: mode, Contracts: []*metadataContractReport{}}
+	for _, address := range sortedAlloc(genesis) {
+		account := genesis.Alloc[address]
+		length := codeMetadataLength(account.Code)
+		if length == 0 {
+			continue
+		}
+		offset := len(account.Code) - length
+		report.Contracts = append(report.Contracts, &metadataContractReport{
+			Address:  checksumAddress(address),
+			Metadata: common.CopyBytes(account.Code[offset:]),
+		})
+		if mode == codeMetadataStrip {
+			account.Code = common.CopyBytes(account.Code[:offset])
+		} else {
+			account.Code = append(common.CopyBytes(account.Code[:offset]), make([]byte, length)...)
+		}
+		genesis.Alloc[address] = account
+	}
+	return report, nil
+}
+
+// updateCodeHashes refreshes code size and hash of reported contracts after alloc code is changed
+func (r *deploymentReport) updateCodeHashes(genesis *core.Genesis) {
+	for _, contract := range r.Contracts {
+		code := genesis.Alloc[common.Address(contract.Address)].Code
+		contract.CodeSize, contract.CodeHash = len(code), crypto.Keccak256Hash(code)
+	}
+}
+
+func (r *metadataReport) print(w io.Writer) {
+	fmt.Fprintf(w, "code metadata (%s) of %d contract(s):\n", r.Mode, len(r.Contracts))
+	for _, contract := range r.Contracts {
+		fmt.Fprintf(w, " - %s: %d byte(s)\n", contract.Address.Hex(), len(contract.Metadata))
+	}
+}
@@ -0,0 +1,50 @@
+package main
+
+import (
+	"bytes"
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestNormalizeCodeMetadata(t *testing.T) {
+	// {"ipfs": <34 bytes multihash>, "solc": 0.8.17} followed by its length, as solc appends it
+	metadata := hexutil.MustDecode("0xa264697066735822" + "1220" + common.Hash{0x01}.Hex()[2:] + "64736f6c6343000811" + "0033")
+	runtime := hexutil.MustDecode("0x6080604052600080fdfe")
+	code := append(common.CopyBytes(runtime), metadata...)
+	if length := codeMetadataLength(code); length != len(metadata) {
+		t.Fatalf("metadata length is %d, expected %d", length, len(metadata))
+	}
+	if length := codeMetadataLength(runtime); length != 0 {
+		t.Fatalf("code without metadata has %d bytes of metadata", length)
+	}
+	contract, account := common.HexToAddress("0x0000000000000000000000000000000000001000"), common.HexToAddress("0x00000000000000000000000000000000000000aa")
+	newGenesis := func() *core.Genesis {
+		return &core.Genesis{Alloc: core.GenesisAlloc{
+			contract: {Code: common.CopyBytes(code), Balance: big.NewInt(0)},
+			account:  {Balance: big.NewInt(1)},
+		}}
+	}
+	genesis := newGenesis()
+	report, err := normalizeCodeMetadata(genesis, codeMetadataStrip)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !bytes.Equal(genesis.Alloc[contract].Code, runtime) {
+		t.Fatalf("stripped code is %x", genesis.Alloc[contract].Code)
+	}
+	if len(report.Contracts) != 1 || !bytes.Equal(report.Contracts[0].Metadata, metadata) {
+		t.Fatalf("unexpected report %+v", report.Contracts)
+	}
+	genesis = newGenesis()
+	if _, err := normalizeCodeMetadata(genesis, codeMetadataZero); err != nil {
+		t.Fatal(err)
+	}
+	zeroed := genesis.Alloc[contract].Code
+	if len(zeroed) != len(code) || !bytes.Equal(zeroed[:len(runtime)], runtime) || !bytes.Equal(zeroed[len(runtime):], make([]byte, len(metadata))) {
+		t.Fatalf("zeroed code is %x", zeroed)
+	}
+}
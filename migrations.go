@@ -0,0 +1,73 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// currentConfigVersion is the config schema this generator reads, configs without version are version 1
+const currentConfigVersion = 1
+
+// configMigration upgrades raw config object of version fromVersion to the next version
+type configMigration struct {
+	fromVersion int
+	description string
+	migrate     func(config map[string]json.RawMessage) error
+}
+
+// configMigrations are applied in order, a change of the schema that breaks existing configs (renamed or
+// reshaped field) bumps currentConfigVersion and adds a migration from the previous version here
+var configMigrations []configMigration
+
+// rawConfigVersion returns schema version declared by the raw config object
+func rawConfigVersion(config map[string]json.RawMessage) (int, error) {
+	raw, ok := config["version"]
+	if !ok {
+		return 1, nil
+	}
+	var version int
+	if err := json.Unmarshal(raw, &version); err != nil || version < 1 {
+		return 0, fmt.Errorf("config version must be positive integer, got %s", raw)
+	}
+	return version, nil
+}
+
+// migrateConfig upgrades config JSON of any older version to the current one in memory, the file itself is
+// left as is
+func migrateConfig(data []byte) ([]byte, error) {
+	return upgradeConfig(data, currentConfigVersion, configMigrations)
+}
+
+func upgradeConfig(data []byte, targetVersion int, migrations []configMigration) ([]byte, error) {
+	config := make(map[string]json.RawMessage)
+	if err := json.Unmarshal(data, &config); err != nil {
+		return nil, err
+	}
+	version, err := rawConfigVersion(config)
+	if err != nil {
+		return nil, err
+	}
+	if version > targetVersion {
+		return nil, fmt.Errorf("config version %d is newer than supported version %d, upgrade the generator", version, targetVersion)
+	}
+	if version == targetVersion {
+		return data, nil
+	}
+	for _, migration := range migrations {
+		if migration.fromVersion != version {
+			continue
+		}
+		if err := migration.migrate(config); err != nil {
+			return nil, fmt.Errorf("failed to migrate config from version %d: %w", version, err)
+		}
+		log.Warn("Config is migrated in memory, update the file to skip migration", "from", version, "to", version+1, "change", migration.description)
+		version++
+		config["version"] = json.RawMessage(fmt.Sprintf("%d", version))
+	}
+	if version != targetVersion {
+		return nil, fmt.Errorf("there is no migration of config version %d", version)
+	}
+	return json.Marshal(config)
+}
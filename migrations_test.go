@@ -0,0 +1,45 @@
+package main
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestUpgradeConfig(t *testing.T) {
+	// version 2 renamed treasury to systemTreasury
+	migrations := []configMigration{{
+		fromVersion: 1,
+		description: "treasury is renamed to systemTreasury",
+		migrate: func(config map[string]json.RawMessage) error {
+			config["systemTreasury"] = config["treasury"]
+			delete(config, "treasury")
+			return nil
+		},
+	}}
+	legacy := []byte(`{"chainId": 14000, "treasury": "0x00a601f45688dba8a070722073b015277cf36725"}`)
+	data, err := upgradeConfig(legacy, 2, migrations)
+	if err != nil {
+		t.Fatal(err)
+	}
+	config := &genesisConfig{}
+	if err := json.Unmarshal(data, config); err != nil {
+		t.Fatal(err)
+	}
+	if config.Version != 2 || config.SystemTreasury[common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725")] != treasuryShareDenominator {
+		t.Fatalf("config isn't migrated: %s", data)
+	}
+	// current configs are passed as is
+	current := []byte(`{"version": 2, "chainId": 14000}`)
+	if data, err := upgradeConfig(current, 2, migrations); err != nil || string(data) != string(current) {
+		t.Fatalf("current config is changed: %s (%v)", data, err)
+	}
+	if _, err := upgradeConfig([]byte(`{"version": 3}`), 2, migrations); err == nil || !strings.Contains(err.Error(), "newer") {
+		t.Fatalf("expected newer version error, got %v", err)
+	}
+	if _, err := upgradeConfig(legacy, 2, nil); err == nil {
+		t.Fatalf("missing migration is not reported")
+	}
+}
@@ -0,0 +1,248 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"errors"
+	"flag"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// networkProfile is a named pair of genesis config and artifact set registered in the user's networks file
+type networkProfile struct {
+	Config    string
+	Artifacts string
+	Output    string
+}
+
+type networkRegistry map[string]*networkProfile
+
+// networksFilePath returns location of the user-level registry (~/.config/bas-genesis/networks.toml)
+func networksFilePath() (string, error) {
+	configDir := os.Getenv("XDG_CONFIG_HOME")
+	if configDir == "" {
+		homeDir, err := os.UserHomeDir()
+		if err != nil {
+			return "", err
+		}
+		configDir = filepath.Join(homeDir, ".config")
+	}
+	return filepath.Join(configDir, "bas-genesis", "networks.toml"), nil
+}
+
+// readNetworkRegistry parses the registry file, it's a restricted TOML: only [networks.<name>] tables with
+// config, artifacts and output keys, values are basic ("...") or literal ('...') strings, comments start with #
+func readNetworkRegistry(path string) (networkRegistry, error) {
+	result := make(networkRegistry)
+	file, err := os.Open(path)
+	if errors.Is(err, fs.ErrNotExist) {
+		return result, nil
+	} else if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+	var current *networkProfile
+	scanner := bufio.NewScanner(file)
+	for lineNumber := 1; scanner.Scan(); lineNumber++ {
+		line := strings.TrimSpace(scanner.Text())
+		if line == "" || strings.HasPrefix(line, "#") {
+			continue
+		}
+		if strings.HasPrefix(line, "[") {
+			table := strings.TrimSpace(line[1:])
+			if !strings.HasPrefix(table, "networks.") {
+				return nil, fmt.Errorf("%s:%d: unknown table (%s)", path, lineNumber, line)
+			}
+			name, rest, err := parseRegistryKey(strings.TrimPrefix(table, "networks."))
+			if err != nil {
+				return nil, fmt.Errorf("%s:%d: %w", path, lineNumber, err)
+			}
+			if rest = strings.TrimSpace(rest); !strings.HasPrefix(rest, "]") || !isRegistryLineEnd(rest[1:]) {
+				return nil, fmt.Errorf("%s:%d: malformed table header", path, lineNumber)
+			}
+			current = &networkProfile{}
+			result[name] = current
+			continue
+		}
+		parts := strings.SplitN(line, "=", 2)
+		if len(parts) != 2 || current == nil {
+			return nil, fmt.Errorf("%s:%d: malformed line", path, lineNumber)
+		}
+		value, rest, err := parseRegistryString(strings.TrimSpace(parts[1]))
+		if err != nil {
+			return nil, fmt.Errorf("%s:%d: %w", path, lineNumber, err)
+		}
+		if !isRegistryLineEnd(rest) {
+			return nil, fmt.Errorf("%s:%d: unexpected text after the value", path, lineNumber)
+		}
+		switch key := strings.TrimSpace(parts[0]); key {
+		case "config":
+			current.Config = value
+		case "artifacts":
+			current.Artifacts = value
+		case "output":
+			current.Output = value
+		default:
+			return nil, fmt.Errorf("%s:%d: unknown key (%s)", path, lineNumber, key)
+		}
+	}
+	return result, scanner.Err()
+}
+
+// parseRegistryKey parses bare or quoted key at the start of text, returns the key and text after it
+func parseRegistryKey(text string) (string, string, error) {
+	if strings.HasPrefix(text, "\"") || strings.HasPrefix(text, "'") {
+		return parseRegistryString(text)
+	}
+	end := strings.IndexFunc(text, func(r rune) bool {
+		return !(r >= 'A' && r <= 'Z' || r >= 'a' && r <= 'z' || r >= '0' && r <= '9' || r == '_' || r == '-')
+	})
+	if end == 0 {
+		return "", "", fmt.Errorf("network name is missing")
+	} else if end < 0 {
+		end = len(text)
+	}
+	return text[:end], text[end:], nil
+}
+
+// parseRegistryString parses basic or literal string at the start of text, returns the value and text after it
+func parseRegistryString(text string) (string, string, error) {
+	if strings.HasPrefix(text, "'") {
+		end := strings.Index(text[1:], "'")
+		if end < 0 {
+			return "", "", fmt.Errorf("unterminated literal string")
+		}
+		return text[1 : end+1], text[end+2:], nil
+	}
+	if strings.HasPrefix(text, "\"") {
+		for i := 1; i < len(text); i++ {
+			if text[i] == '\\' {
+				i++
+			} else if text[i] == '"' {
+				value, err := strconv.Unquote(text[:i+1])
+				if err != nil {
+					return "", "", fmt.Errorf("invalid string: %w", err)
+				}
+				return value, text[i+1:], nil
+			}
+		}
+		return "", "", fmt.Errorf("unterminated string")
+	}
+	return "", "", fmt.Errorf("value must be a quoted string")
+}
+
+// isRegistryLineEnd reports whether only whitespace or a comment is left on the line
+func isRegistryLineEnd(rest string) bool {
+	rest = strings.TrimSpace(rest)
+	return rest == "" || strings.HasPrefix(rest, "#")
+}
+
+func (r networkRegistry) names() []string {
+	var names []string
+	for name := range r {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+func (r networkRegistry) writeTo(path string) error {
+	buf := &bytes.Buffer{}
+	buf.WriteString("# BAS genesis network profiles, managed by `create-genesis networks`\n")
+	buf.WriteString("# restricted TOML: [networks.<name>] tables with config, artifacts and output string keys\n")
+	for _, name := range r.names() {
+		profile := r[name]
+		fmt.Fprintf(buf, "\n[networks.%s]\n", strconv.Quote(name))
+		fmt.Fprintf(buf, "config = %s\n", strconv.Quote(profile.Config))
+		if profile.Artifacts != "" {
+			fmt.Fprintf(buf, "artifacts = %s\n", strconv.Quote(profile.Artifacts))
+		}
+		if profile.Output != "" {
+			fmt.Fprintf(buf, "output = %s\n", strconv.Quote(profile.Output))
+		}
+	}
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return err
+	}
+	return os.WriteFile(path, buf.Bytes(), 0644)
+}
+
+func lookupNetworkProfile(name string) (*networkProfile, error) {
+	path, err := networksFilePath()
+	if err != nil {
+		return nil, err
+	}
+	registry, err := readNetworkRegistry(path)
+	if err != nil {
+		return nil, err
+	}
+	profile, ok := registry[name]
+	if !ok {
+		return nil, fmt.Errorf("network profile is not found: %s (registered in %s)", name, path)
+	}
+	return profile, nil
+}
+
+func runNetworksCommand(args []string) error {
+	if len(args) == 0 {
+		return fmt.Errorf("usage: networks list|add|remove (profiles are kept in ~/.config/bas-genesis/networks.toml)")
+	}
+	path, err := networksFilePath()
+	if err != nil {
+		return err
+	}
+	registry, err := readNetworkRegistry(path)
+	if err != nil {
+		return err
+	}
+	switch args[0] {
+	case "list":
+		for _, name := range registry.names() {
+			profile := registry[name]
+			fmt.Printf("%s\tconfig=%s artifacts=%s output=%s\n", name, profile.Config, profile.Artifacts, profile.Output)
+		}
+		return nil
+	case "add":
+		flags := flag.NewFlagSet("networks add", flag.ContinueOnError)
+		configPath := flags.String("config", "", "path to genesis config file")
+		artifactsPath := flags.String("artifacts", "", "path to directory with contract artifacts (optional)")
+		outputPath := flags.String("output", "", "default output file (optional)")
+		if len(args) < 2 {
+			return fmt.Errorf("usage: networks add <name> --config <file> [--artifacts <dir>] [--output <file>]")
+		}
+		if err := flags.Parse(args[2:]); err != nil {
+			return err
+		}
+		if *configPath == "" {
+			return fmt.Errorf("config path is required")
+		}
+		// store absolute paths, so profile works from any working directory
+		profile := &networkProfile{Output: *outputPath}
+		if profile.Config, err = filepath.Abs(*configPath); err != nil {
+			return err
+		}
+		if *artifactsPath != "" {
+			if profile.Artifacts, err = filepath.Abs(*artifactsPath); err != nil {
+				return err
+			}
+		}
+		registry[args[1]] = profile
+		return registry.writeTo(path)
+	case "remove":
+		if len(args) != 2 {
+			return fmt.Errorf("usage: networks remove <name>")
+		}
+		if _, ok := registry[args[1]]; !ok {
+			return fmt.Errorf("network profile is not found: %s", args[1])
+		}
+		delete(registry, args[1])
+		return registry.writeTo(path)
+	}
+	return fmt.Errorf("unknown networks command: %s", args[0])
+}
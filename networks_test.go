@@ -0,0 +1,45 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestReadNetworkRegistry(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "networks.toml")
+	data := `# profiles
+[networks.dev] # local chain
+config = "/tmp/dev.json" # comment with "quotes"
+artifacts = 'C:\artifacts'
+
+[networks."test net"]
+config = "/tmp/test \"net\".json"
+`
+	if err := os.WriteFile(path, []byte(data), 0644); err != nil {
+		t.Fatal(err)
+	}
+	registry, err := readNetworkRegistry(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if dev := registry["dev"]; dev == nil || dev.Config != "/tmp/dev.json" || dev.Artifacts != `C:\artifacts` {
+		t.Fatalf("unexpected dev profile %+v", registry["dev"])
+	}
+	if test := registry["test net"]; test == nil || test.Config != `/tmp/test "net".json` {
+		t.Fatalf("unexpected test profile %+v", registry["test net"])
+	}
+	// written registry is read back as is
+	if err := registry.writeTo(path); err != nil {
+		t.Fatal(err)
+	}
+	if reread, err := readNetworkRegistry(path); err != nil || len(reread) != 2 || *reread["test net"] != *registry["test net"] {
+		t.Fatalf("registry doesn't survive writing: %v", err)
+	}
+	if err := os.WriteFile(path, []byte("[networks.dev]\nconfig = \"a\" b\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := readNetworkRegistry(path); err == nil {
+		t.Fatal("text after the value is accepted")
+	}
+}
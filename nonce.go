@@ -0,0 +1,56 @@
+package main
+
+import (
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+// policies of the nonce written into genesis alloc for system contract accounts
+const (
+	// contracts are written with nonce 0 like genesis files generated before the policy was introduced
+	contractNonceZero = "0"
+	// nonce of the contract created by a transaction after EIP-161
+	contractNonceOne = "1"
+	// nonce the contract has after constructor and follow-up calls (1 plus number of contracts it created)
+	contractNonceFromSimulation = "from-simulation"
+)
+
+var contractNoncePolicies = []string{contractNonceZero, contractNonceOne, contractNonceFromSimulation}
+
+func (c genesisConfig) systemContractNonce() string {
+	if c.SystemContractNonce == "" {
+		return contractNonceZero
+	}
+	return c.SystemContractNonce
+}
+
+// contractNonce returns nonce of the system contract account in genesis alloc, clients load any nonce from
+// genesis, but contract with nonce 0 creates its first child at the address the simulation gave to nonce 1
+func contractNonce(policy string, simulated uint64) (uint64, error) {
+	switch policy {
+	case contractNonceZero, "":
+		return 0, nil
+	case contractNonceOne:
+		return 1, nil
+	case contractNonceFromSimulation:
+		return simulated, nil
+	}
+	return 0, fmt.Errorf("unknown system contract nonce policy %q, expected one of %v", policy, contractNoncePolicies)
+}
+
+// applyContractNonce sets nonce of the deployed system contract in genesis alloc, simulated state keeps the
+// nonce given by the EVM, so next constructors see the same state regardless of the policy
+func (s *systemContractSimulator) applyContractNonce(address common.Address, result *simulationResult) error {
+	nonce, err := contractNonce(s.contractNonce, result.Nonce)
+	if err != nil {
+		return err
+	}
+	account, ok := s.genesis.Alloc[address]
+	if !ok {
+		return nil
+	}
+	account.Nonce = nonce
+	s.genesis.Alloc[address] = account
+	return nil
+}
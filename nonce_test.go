@@ -0,0 +1,45 @@
+package main
+
+import (
+	"path/filepath"
+	"testing"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+func TestSystemContractNonce(t *testing.T) {
+	expected := map[string]func(nonce uint64) bool{
+		"":                          func(nonce uint64) bool { return nonce == 0 },
+		contractNonceOne:            func(nonce uint64) bool { return nonce == 1 },
+		contractNonceFromSimulation: func(nonce uint64) bool { return nonce >= 1 },
+	}
+	for policy, check := range expected {
+		config := devNetConfig
+		config.SystemContractNonce = policy
+		outputFile := filepath.Join(t.TempDir(), "genesis.json")
+		if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+			t.Fatalf("failed to generate genesis (policy %q): %s", policy, err)
+		}
+		genesis, err := bgenesis.Load(outputFile)
+		if err != nil {
+			t.Fatal(err)
+		}
+		for _, address := range systemContractAddresses {
+			account, ok := genesis.Alloc[address]
+			if !ok {
+				continue
+			}
+			if !check(account.Nonce) {
+				t.Fatalf("unexpected nonce %d of %s (policy %q)", account.Nonce, address.Hex(), policy)
+			}
+		}
+	}
+}
+
+func TestUnknownSystemContractNonce(t *testing.T) {
+	config := devNetConfig
+	config.SystemContractNonce = "2"
+	if err := config.validate(); err == nil {
+		t.Fatal("unknown nonce policy is accepted")
+	}
+}
@@ -0,0 +1,106 @@
+package main
+
+import (
+	"flag"
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common/math"
+)
+
+// configOverride is a config field that can be set by environment variable (BAS_<FLAG>) or CLI flag, the
+// precedence is config file < environment < flag
+type configOverride struct {
+	flag  string
+	usage string
+	apply func(config *genesisConfig, value string) error
+}
+
+func (o configOverride) env() string {
+	return "BAS_" + strings.ToUpper(strings.ReplaceAll(o.flag, "-", "_"))
+}
+
+func (c *genesisConfig) header() *headerConfig {
+	if c.Header == nil {
+		c.Header = &headerConfig{}
+	}
+	return c.Header
+}
+
+var configOverrides = []configOverride{
+	{flag: "chain-id", usage: "chain id", apply: func(config *genesisConfig, value string) (err error) {
+		config.ChainId, err = strconv.ParseInt(value, 0, 64)
+		return err
+	}},
+	{flag: "timestamp", usage: "genesis timestamp: unix seconds, RFC3339 datetime or \"now\"", apply: func(config *genesisConfig, value string) error {
+		if _, err := parseTimestamp(value); err != nil {
+			return err
+		}
+		config.header().Timestamp = value
+		return nil
+	}},
+	{flag: "gas-limit", usage: "genesis block gas limit", apply: func(config *genesisConfig, value string) error {
+		gasLimit, ok := math.ParseUint64(value)
+		if !ok {
+			return fmt.Errorf("invalid gas limit %q", value)
+		}
+		config.header().GasLimit = (*math.HexOrDecimal64)(&gasLimit)
+		return nil
+	}},
+	{flag: "system-treasury", usage: "address of the system treasury or comma separated <address>:<share> pairs (basis points)", apply: func(config *genesisConfig, value string) error {
+		treasury, err := parseTreasury(value)
+		config.SystemTreasury = treasury
+		return err
+	}},
+	{flag: "voting-period", usage: "governance voting period in blocks", apply: func(config *genesisConfig, value string) error {
+		period, err := strconv.ParseInt(value, 0, 64)
+		config.VotingPeriod = votingPeriod(period)
+		return err
+	}},
+	{flag: "commission-rate", usage: "commission rate of genesis validators in basis points", apply: func(config *genesisConfig, value string) error {
+		rate, err := strconv.ParseInt(value, 0, 64)
+		config.CommissionRate = commissionRate(rate)
+		return err
+	}},
+	{flag: "block-period", usage: "block period in seconds", apply: func(config *genesisConfig, value string) (err error) {
+		config.ConsensusParams.BlockPeriodSeconds, err = strconv.ParseUint(value, 0, 64)
+		return err
+	}},
+	{flag: "epoch-block-interval", usage: "epoch length in blocks", apply: func(config *genesisConfig, value string) error {
+		interval, err := strconv.ParseUint(value, 0, 32)
+		config.ConsensusParams.EpochBlockInterval = uint32(interval)
+		return err
+	}},
+}
+
+// registerConfigOverrides adds flag of every override, values are available after flags are parsed
+func registerConfigOverrides(flags *flag.FlagSet) map[string]*string {
+	result := make(map[string]*string)
+	for _, override := range configOverrides {
+		result[override.flag] = flags.String(override.flag, "", fmt.Sprintf("override %s (env %s)", override.usage, override.env()))
+	}
+	return result
+}
+
+// applyConfigOverrides applies environment variables and then flags, applied values are returned by flag name,
+// so they can be locked together with other inputs
+func applyConfigOverrides(config *genesisConfig, flagValues map[string]*string) (map[string]string, error) {
+	applied := make(map[string]string)
+	for _, override := range configOverrides {
+		if value, ok := os.LookupEnv(override.env()); ok && value != "" {
+			if err := override.apply(config, value); err != nil {
+				return nil, fmt.Errorf("%s: %w", override.env(), err)
+			}
+			applied[override.flag] = value
+		}
+		if value := flagValues[override.flag]; value != nil && *value != "" {
+			if err := override.apply(config, *value); err != nil {
+				return nil, fmt.Errorf("--%s: %w", override.flag, err)
+			}
+			applied[override.flag] = *value
+		}
+	}
+	return applied, nil
+}
@@ -0,0 +1,32 @@
+package main
+
+import (
+	"os"
+	"testing"
+)
+
+func TestConfigOverridesPrecedence(t *testing.T) {
+	os.Setenv("BAS_CHAIN_ID", "17")
+	os.Setenv("BAS_TIMESTAMP", "2022-04-01T00:00:00Z")
+	defer os.Unsetenv("BAS_CHAIN_ID")
+	defer os.Unsetenv("BAS_TIMESTAMP")
+	config := localNetConfig
+	chainIdFlag, emptyFlag := "18", ""
+	applied, err := applyConfigOverrides(&config, map[string]*string{"chain-id": &chainIdFlag, "timestamp": &emptyFlag})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if config.ChainId != 18 || applied["chain-id"] != "18" {
+		t.Fatalf("flag doesn't override env: chainId=%d, applied=%q", config.ChainId, applied["chain-id"])
+	}
+	if config.Header == nil || config.Header.Timestamp != "2022-04-01T00:00:00Z" || applied["timestamp"] == "" {
+		t.Fatalf("env doesn't override config: header=%+v", config.Header)
+	}
+	if localNetConfig.Header != nil || localNetConfig.ChainId != 1337 {
+		t.Fatalf("preset is modified")
+	}
+	os.Setenv("BAS_CHAIN_ID", "chain")
+	if _, err := applyConfigOverrides(&config, nil); err == nil {
+		t.Fatalf("invalid env value is accepted")
+	}
+}
@@ -0,0 +1,289 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"runtime"
+	"sort"
+	"sync"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// deploymentLevels groups ordered deployments by depth in the dependency graph, contracts of one level don't
+// depend on each other and keep their order
+func deploymentLevels(deployments []*systemContractDeployment) [][]*systemContractDeployment {
+	depth := make(map[common.Address]int)
+	var levels [][]*systemContractDeployment
+	for _, deployment := range deployments {
+		level := 0
+		for _, dependency := range deployment.dependsOn {
+			if depth[dependency]+1 > level {
+				level = depth[dependency] + 1
+			}
+		}
+		depth[deployment.address] = level
+		for len(levels) <= level {
+			levels = append(levels, nil)
+		}
+		levels[level] = append(levels[level], deployment)
+	}
+	return levels
+}
+
+func cloneAlloc(alloc core.GenesisAlloc) core.GenesisAlloc {
+	result := make(core.GenesisAlloc, len(alloc))
+	for address, account := range alloc {
+		if account.Storage != nil {
+			storage := make(map[common.Hash]common.Hash, len(account.Storage))
+			for key, value := range account.Storage {
+				storage[key] = value
+			}
+			account.Storage = storage
+		}
+		if account.Balance != nil {
+			account.Balance = new(big.Int).Set(account.Balance)
+		}
+		result[address] = account
+	}
+	return result
+}
+
+func accountsEqual(a, b core.GenesisAccount) bool {
+	if a.Nonce != b.Nonce || !bytes.Equal(a.Code, b.Code) || len(a.Storage) != len(b.Storage) {
+		return false
+	}
+	if (a.Balance == nil) != (b.Balance == nil) || (a.Balance != nil && a.Balance.Cmp(b.Balance) != 0) {
+		return false
+	}
+	for key, value := range a.Storage {
+		if other, ok := b.Storage[key]; !ok || other != value {
+			return false
+		}
+	}
+	return true
+}
+
+// fork returns simulator with copies of the state and genesis alloc, forks don't share mutable data, so they
+// can be executed concurrently
+func (s *systemContractSimulator) fork() (*systemContractSimulator, error) {
+	s.statedb.Finalise(false)
+	genesis := *s.genesis
+	genesis.Alloc = cloneAlloc(s.genesis.Alloc)
+	statedb := s.statedb.Copy()
+	evm, tracer, err := newSimulationEvm(s.evm.Context, s.evm.TxContext, statedb, genesis.Config)
+	if err != nil {
+		return nil, err
+	}
+	tracer.traceCalls = s.tracer.traceCalls
+	return &systemContractSimulator{genesis: &genesis, statedb: statedb, evm: evm, tracer: tracer, traceDir: s.traceDir, contractNonce: s.contractNonce, gasLimit: s.gasLimit, precompiles: s.precompiles}, nil
+}
+
+// allocChangeSet is the difference between alloc of the simulator and the alloc it started from, accounts are
+// complete (not only changed fields), self-destructed accounts aren't in the alloc anymore, so they are listed
+// separately
+type allocChangeSet struct {
+	accounts core.GenesisAlloc
+	deleted  []common.Address
+}
+
+// allocChanges returns accounts of the fork alloc that differ from the alloc it was forked from
+func (s *systemContractSimulator) allocChanges(base core.GenesisAlloc) *allocChangeSet {
+	result := &allocChangeSet{accounts: make(core.GenesisAlloc)}
+	for address, account := range s.genesis.Alloc {
+		if before, ok := base[address]; ok && accountsEqual(before, account) {
+			continue
+		}
+		result.accounts[address] = account
+	}
+	for address := range base {
+		if _, ok := s.genesis.Alloc[address]; !ok {
+			result.deleted = append(result.deleted, address)
+		}
+	}
+	sort.Slice(result.deleted, func(i, j int) bool {
+		return bytes.Compare(result.deleted[i][:], result.deleted[j][:]) < 0
+	})
+	return result
+}
+
+// addresses returns changed and deleted accounts
+func (c *allocChangeSet) addresses() []common.Address {
+	result := append([]common.Address{}, c.deleted...)
+	for address := range c.accounts {
+		result = append(result, address)
+	}
+	return result
+}
+
+// applyAllocChanges writes accounts into genesis alloc and simulated state, so next contracts see them, slots and
+// code the account no longer has are cleared and deleted accounts are removed from both
+func (s *systemContractSimulator) applyAllocChanges(changes *allocChangeSet) {
+	for address, account := range changes.accounts {
+		previous := s.genesis.Alloc[address]
+		if account.Balance != nil {
+			s.statedb.SetBalance(address, account.Balance)
+		}
+		s.statedb.SetNonce(address, account.Nonce)
+		if len(account.Code) > 0 || len(previous.Code) > 0 {
+			s.statedb.SetCode(address, account.Code)
+		}
+		for key := range previous.Storage {
+			if _, ok := account.Storage[key]; !ok {
+				s.statedb.SetState(address, key, common.Hash{})
+			}
+		}
+		for key, value := range account.Storage {
+			s.statedb.SetState(address, key, value)
+		}
+		s.genesis.Alloc[address] = account
+	}
+	if len(changes.deleted) == 0 {
+		return
+	}
+	for _, address := range changes.deleted {
+		log.Debug("Removing self-destructed account from genesis", "address", address)
+		s.statedb.Suicide(address)
+		delete(s.genesis.Alloc, address)
+	}
+	// suicided objects are removed from the state by finalisation
+	s.statedb.Finalise(false)
+}
+
+// forkedDeployment is a constructor executed against its own fork of the state
+type forkedDeployment struct {
+	deployment *systemContractDeployment
+	fork       *systemContractSimulator
+	result     *simulationResult
+	err        error
+	// call frames of the constructor and follow-up calls (only if tracing is enabled)
+	traces []json.RawMessage
+}
+
+// simulateDeployments simulates system contracts level by level, constructors of one level run concurrently
+// against forks of the state, then their alloc changes are merged and init functions are called in deployment
+// order, so generated genesis doesn't depend on parallelism
+func simulateDeployments(simulator *systemContractSimulator, cache *simulationCache, deployments []*systemContractDeployment, artifacts artifactSet, parallelism int, report *deploymentReport) deploymentFailures {
+	if parallelism <= 0 {
+		parallelism = runtime.NumCPU()
+	}
+	var failures deploymentFailures
+	failed := make(map[common.Address]string)
+	index := 0
+	for _, level := range deploymentLevels(deployments) {
+		var ready []*systemContractDeployment
+		for _, deployment := range level {
+			index++
+			log.Info("Simulating contract", "name", deployment.name, "address", deployment.address, "index", index, "total", len(deployments))
+			if dependency := failedDependency(deployment, failed); dependency != "" {
+				failures.add(deployment.name, deployment.address, fmt.Errorf("skipped, depends on failed %s", dependency))
+				failed[deployment.address] = deployment.name
+				continue
+			}
+			ready = append(ready, deployment)
+		}
+		if parallelism == 1 || len(ready) < 2 {
+			for _, deployment := range ready {
+				simulator.tracer.takeTraces()
+				result, err := simulateDeployment(simulator, cache, deployment, artifacts[deployment.name])
+				simulator.writeTrace(deployment.name, nil)
+				if err != nil {
+					failures.add(deployment.name, deployment.address, err)
+					failed[deployment.address] = deployment.name
+					continue
+				}
+				report.addContract(deployment, simulator.genesis.Alloc[deployment.address], result)
+			}
+			continue
+		}
+		base := cloneAlloc(simulator.genesis.Alloc)
+		forked, err := runForks(simulator, cache, ready, artifacts, parallelism)
+		if err != nil {
+			for _, deployment := range ready {
+				failures.add(deployment.name, deployment.address, err)
+				failed[deployment.address] = deployment.name
+			}
+			continue
+		}
+		// accounts changed by constructors of this level, two independent constructors must not touch one account
+		changedBy := make(map[common.Address]string)
+		for _, f := range forked {
+			deployment := f.deployment
+			simulator.tracer.takeTraces()
+			err := mergeFork(simulator, f, base, changedBy)
+			simulator.writeTrace(deployment.name, f.traces)
+			if err != nil {
+				failures.add(deployment.name, deployment.address, err)
+				failed[deployment.address] = deployment.name
+				continue
+			}
+			warnNonstandardPrecompiles(deployment.name, f.result.PrecompileCalls)
+			report.addContract(deployment, simulator.genesis.Alloc[deployment.address], f.result)
+		}
+	}
+	return failures
+}
+
+// mergeFork applies alloc changes made by the constructor executed in the fork and calls init function
+func mergeFork(simulator *systemContractSimulator, f *forkedDeployment, base core.GenesisAlloc, changedBy map[common.Address]string) error {
+	if f.err != nil {
+		return f.err
+	}
+	changes := f.fork.allocChanges(base)
+	addresses := changes.addresses()
+	if conflict := conflictingAccount(addresses, changedBy); conflict != (common.Address{}) {
+		return fmt.Errorf("account %s is also modified by %s, declare dependency between them", conflict.Hex(), changedBy[conflict])
+	}
+	for _, address := range addresses {
+		changedBy[address] = f.deployment.name
+	}
+	simulator.applyAllocChanges(changes)
+	if err := simulator.applyContractNonce(f.deployment.address, f.result); err != nil {
+		return err
+	}
+	return simulator.initSystemContract(f.deployment.address, f.result)
+}
+
+// runForks executes constructors of independent deployments concurrently, results keep order of deployments
+func runForks(simulator *systemContractSimulator, cache *simulationCache, deployments []*systemContractDeployment, artifacts artifactSet, parallelism int) ([]*forkedDeployment, error) {
+	forked := make([]*forkedDeployment, len(deployments))
+	for i, deployment := range deployments {
+		fork, err := simulator.fork()
+		if err != nil {
+			return nil, err
+		}
+		forked[i] = &forkedDeployment{deployment: deployment, fork: fork}
+	}
+	var wg sync.WaitGroup
+	slots := make(chan struct{}, parallelism)
+	for _, f := range forked {
+		wg.Add(1)
+		slots <- struct{}{}
+		go func(f *forkedDeployment) {
+			defer wg.Done()
+			defer func() { <-slots }()
+			f.result, f.err = cache.invokeConstructor(f.fork, f.deployment, artifacts[f.deployment.name])
+			f.traces = f.fork.tracer.takeTraces()
+		}(f)
+	}
+	wg.Wait()
+	return forked, nil
+}
+
+// conflictingAccount returns the first changed account (in address order) that is already changed by another contract
+func conflictingAccount(addresses []common.Address, changedBy map[common.Address]string) common.Address {
+	var conflict common.Address
+	for _, address := range addresses {
+		if _, ok := changedBy[address]; !ok {
+			continue
+		}
+		if conflict == (common.Address{}) || bytes.Compare(address[:], conflict[:]) < 0 {
+			conflict = address
+		}
+	}
+	return conflict
+}
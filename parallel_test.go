@@ -0,0 +1,78 @@
+package main
+
+import (
+	"bytes"
+	"math/big"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestDeploymentLevels(t *testing.T) {
+	a, b, c := common.HexToAddress("0xa"), common.HexToAddress("0xb"), common.HexToAddress("0xc")
+	levels := deploymentLevels([]*systemContractDeployment{
+		{name: "A", address: a},
+		{name: "C", address: c},
+		{name: "B", address: b, dependsOn: []common.Address{a}},
+	})
+	if len(levels) != 2 || len(levels[0]) != 2 || levels[0][0].name != "A" || levels[0][1].name != "C" || levels[1][0].name != "B" {
+		t.Fatalf("unexpected levels: %v", levels)
+	}
+}
+
+func TestParallelSimulationMatchesSequential(t *testing.T) {
+	var outputs [][]byte
+	for _, parallelism := range []int{1, 4} {
+		options := defaultGenerateOptions()
+		options.Parallelism = parallelism
+		outputFile := filepath.Join(t.TempDir(), "genesis.json")
+		if err := createGenesisConfig(devNetConfig, outputFile, options); err != nil {
+			t.Fatalf("failed to generate genesis (parallelism %d): %s", parallelism, err)
+		}
+		output, err := os.ReadFile(outputFile)
+		if err != nil {
+			t.Fatal(err)
+		}
+		outputs = append(outputs, output)
+	}
+	if !bytes.Equal(outputs[0], outputs[1]) {
+		t.Fatalf("parallel simulation changes genesis:\n%s", firstDifference(outputs[0], outputs[1]))
+	}
+}
+
+func TestForkChangesDeleteAccountsAndSlots(t *testing.T) {
+	destructed, changed := common.HexToAddress("0x0000000000000000000000000000000000abcdef"), common.HexToAddress("0x0000000000000000000000000000000000abcdf0")
+	slot, kept := common.HexToHash("0x01"), common.HexToHash("0x02")
+	genesis := defaultGenesisConfig(14000)
+	genesis.Alloc = core.GenesisAlloc{
+		destructed: {Balance: big.NewInt(0), Code: []byte{0x60, 0x00}},
+		changed:    {Balance: big.NewInt(0), Code: []byte{0x60, 0x00}, Storage: map[common.Hash]common.Hash{slot: slot, kept: kept}},
+	}
+	simulator, err := newSystemContractSimulator(genesis)
+	if err != nil {
+		t.Fatal(err)
+	}
+	base := cloneAlloc(simulator.genesis.Alloc)
+	fork, err := simulator.fork()
+	if err != nil {
+		t.Fatal(err)
+	}
+	// the same as selfdestruct in one account and recreation of another one with part of the storage
+	delete(fork.genesis.Alloc, destructed)
+	fork.genesis.Alloc[changed] = core.GenesisAccount{Balance: big.NewInt(0), Code: []byte{0x60, 0x00}, Storage: map[common.Hash]common.Hash{kept: kept}}
+	changes := fork.allocChanges(base)
+	if len(changes.deleted) != 1 || changes.deleted[0] != destructed || len(changes.accounts) != 1 {
+		t.Fatalf("unexpected changes: %+v", changes)
+	}
+	// cached entries are replayed through the same function
+	simulator.applyAllocChanges(changes)
+	if _, ok := simulator.genesis.Alloc[destructed]; ok || simulator.statedb.Exist(destructed) {
+		t.Fatal("self-destructed account is kept")
+	}
+	if simulator.statedb.GetState(changed, slot) != (common.Hash{}) || simulator.statedb.GetState(changed, kept) != kept {
+		t.Fatal("storage of the changed account isn't replaced")
+	}
+}
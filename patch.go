@@ -0,0 +1,87 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"regexp"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// alloc addresses and storage keys are written without 0x prefix by geth, patch can use any form
+var hexKey = regexp.MustCompile(`^(0x|0X)?([0-9a-fA-F]{40}|[0-9a-fA-F]{64})$`)
+
+func normalizeJsonKey(key string) string {
+	if match := hexKey.FindStringSubmatch(key); match != nil {
+		return strings.ToLower(match[2])
+	}
+	return key
+}
+
+// mergeJsonPatch applies JSON merge patch (RFC 7386), objects are merged recursively and null removes the key
+func mergeJsonPatch(target, patch interface{}) interface{} {
+	patchObject, ok := patch.(orderedObject)
+	if !ok {
+		return patch
+	}
+	targetObject, ok := target.(orderedObject)
+	if !ok {
+		targetObject = orderedObject{}
+	}
+	result := append(orderedObject{}, targetObject...)
+	for _, field := range patchObject {
+		index := -1
+		for i, existing := range result {
+			if normalizeJsonKey(existing.Key) == normalizeJsonKey(field.Key) {
+				index = i
+				break
+			}
+		}
+		switch {
+		case field.Value == nil && index >= 0:
+			result = append(result[:index], result[index+1:]...)
+		case field.Value == nil:
+			continue
+		case index >= 0:
+			result[index].Value = mergeJsonPatch(result[index].Value, field.Value)
+		default:
+			result = append(result, orderedField{Key: field.Key, Value: mergeJsonPatch(nil, field.Value)})
+		}
+	}
+	return result
+}
+
+// applyGenesisPatch deep-merges patch file over the genesis, so operators can customize it without forking generator
+func applyGenesisPatch(genesis *core.Genesis, patchFile string) error {
+	rawPatch, err := os.ReadFile(patchFile)
+	if err != nil {
+		return err
+	}
+	patch, err := decodeOrderedJson(rawPatch)
+	if err != nil {
+		return fmt.Errorf("failed to parse patch %s: %w", patchFile, err)
+	}
+	if _, ok := patch.(orderedObject); !ok {
+		return fmt.Errorf("patch %s must be JSON object", patchFile)
+	}
+	rawGenesis, err := json.Marshal(genesis)
+	if err != nil {
+		return err
+	}
+	target, err := decodeOrderedJson(rawGenesis)
+	if err != nil {
+		return err
+	}
+	patched, err := json.Marshal(mergeJsonPatch(target, patch))
+	if err != nil {
+		return err
+	}
+	result := &core.Genesis{}
+	if err := json.Unmarshal(patched, result); err != nil {
+		return fmt.Errorf("patched genesis is invalid: %w", err)
+	}
+	*genesis = *result
+	return nil
+}
@@ -0,0 +1,74 @@
+package main
+
+import (
+	"encoding/json"
+	"math/big"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestMergeJsonPatch(t *testing.T) {
+	target, err := decodeOrderedJson([]byte(`{"a": 1, "b": {"c": 2, "d": 3}, "e": [1, 2], "0xABCDEF0000000000000000000000000000000001": {"f": 4}}`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	// scalars and arrays are replaced, objects are merged, null removes the key and hex keys match in any form
+	patch, err := decodeOrderedJson([]byte(`{"a": "x", "b": {"c": null, "g": 5}, "e": [3], "h": {"i": null, "j": 6}, "abcdef0000000000000000000000000000000001": {"f": 7}}`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	result, err := json.Marshal(mergeJsonPatch(target, patch))
+	if err != nil {
+		t.Fatal(err)
+	}
+	expected := `{"a":"x","b":{"d":3,"g":5},"e":[3],"0xABCDEF0000000000000000000000000000000001":{"f":7},"h":{"j":6}}`
+	if string(result) != expected {
+		t.Fatalf("unexpected merge result %s", result)
+	}
+}
+
+func TestApplyGenesisPatch(t *testing.T) {
+	contract, added := common.HexToAddress("0x0000000000000000000000000000000000001000"), common.HexToAddress("0x00000000000000000000000000000000000000aa")
+	removed, kept := common.HexToHash("0x01"), common.HexToHash("0x02")
+	genesis := defaultGenesisConfig(14000)
+	genesis.Alloc = core.GenesisAlloc{
+		contract: {Balance: big.NewInt(1), Code: []byte{0x60, 0x00}, Storage: map[common.Hash]common.Hash{removed: removed, kept: kept}},
+	}
+	patchFile := filepath.Join(t.TempDir(), "patch.json")
+	patch := `{
+		"gasLimit": "0x1000",
+		"config": {"chainId": 15},
+		"alloc": {
+			"0x0000000000000000000000000000000000001000": {"balance": "0x5", "storage": {"0x0000000000000000000000000000000000000000000000000000000000000001": null}},
+			"00000000000000000000000000000000000000AA": {"balance": "0x1"}
+		}
+	}`
+	if err := os.WriteFile(patchFile, []byte(patch), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := applyGenesisPatch(genesis, patchFile); err != nil {
+		t.Fatal(err)
+	}
+	if genesis.GasLimit != 0x1000 || genesis.Config.ChainID.Int64() != 15 || genesis.Config.Parlia == nil || genesis.Config.IstanbulBlock == nil {
+		t.Fatalf("unexpected patched header or config: %d %+v", genesis.GasLimit, genesis.Config)
+	}
+	account := genesis.Alloc[contract]
+	if account.Balance.Int64() != 5 || len(account.Code) != 2 || len(account.Storage) != 1 || account.Storage[kept] != kept {
+		t.Fatalf("unexpected patched account %+v", account)
+	}
+	if genesis.Alloc[added].Balance.Int64() != 1 {
+		t.Fatalf("account isn't added by patch")
+	}
+	for _, invalid := range []string{`[1]`, `{"gasLimit": "not a number"}`, `{`} {
+		if err := os.WriteFile(patchFile, []byte(invalid), 0644); err != nil {
+			t.Fatal(err)
+		}
+		if err := applyGenesisPatch(genesis, patchFile); err == nil {
+			t.Fatalf("patch %s is accepted", invalid)
+		}
+	}
+}
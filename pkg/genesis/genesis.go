@@ -0,0 +1,206 @@
+// Package genesis provides typed access to generated BAS genesis files, so tools don't need to re-parse
+// alloc section and compute storage slots manually.
+package genesis
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"os"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+// SystemContracts maps system contract names to their genesis addresses
+var SystemContracts = map[string]common.Address{
+	"Staking":           common.HexToAddress("0x0000000000000000000000000000000000001000"),
+	"SlashingIndicator": common.HexToAddress("0x0000000000000000000000000000000000001001"),
+	"SystemReward":      common.HexToAddress("0x0000000000000000000000000000000000001002"),
+	"StakingPool":       common.HexToAddress("0x0000000000000000000000000000000000007001"),
+	"Governance":        common.HexToAddress("0x0000000000000000000000000000000000007002"),
+	"ChainConfig":       common.HexToAddress("0x0000000000000000000000000000000000007003"),
+	"RuntimeUpgrade":    common.HexToAddress("0x0000000000000000000000000000000000007004"),
+	"DeployerProxy":     common.HexToAddress("0x0000000000000000000000000000000000007005"),
+}
+
+// Account is an alloc entry together with its address (and name for system contracts)
+type Account struct {
+	Name    string
+	Address common.Address
+	core.GenesisAccount
+}
+
+// Genesis wraps geth's genesis model with query helpers
+type Genesis struct {
+	*core.Genesis
+}
+
+// Load reads genesis file from the disk
+func Load(path string) (*Genesis, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	return Parse(data)
+}
+
+// Parse decodes genesis from JSON
+func Parse(data []byte) (*Genesis, error) {
+	result := &core.Genesis{}
+	if err := json.Unmarshal(data, result); err != nil {
+		return nil, err
+	}
+	return &Genesis{result}, nil
+}
+
+// Account returns alloc entry by address
+func (g *Genesis) Account(address common.Address) (*Account, bool) {
+	account, ok := g.Alloc[address]
+	if !ok {
+		return nil, false
+	}
+	return &Account{Name: contractName(address), Address: address, GenesisAccount: account}, true
+}
+
+// SystemContracts returns all system contracts present in alloc ordered by address
+func (g *Genesis) SystemContracts() []*Account {
+	return g.Accounts(func(account *Account) bool {
+		return account.Name != ""
+	})
+}
+
+// AllocationsAbove returns accounts with balance greater than or equal to the threshold ordered by address
+func (g *Genesis) AllocationsAbove(threshold *big.Int) []*Account {
+	return g.Accounts(func(account *Account) bool {
+		return account.Balance != nil && account.Balance.Cmp(threshold) >= 0
+	})
+}
+
+// Accounts iterates over alloc in address order and returns accounts accepted by the filter
+func (g *Genesis) Accounts(filter func(account *Account) bool) []*Account {
+	var addresses []common.Address
+	for address := range g.Alloc {
+		addresses = append(addresses, address)
+	}
+	sort.Slice(addresses, func(i, j int) bool {
+		return bytes.Compare(addresses[i].Bytes(), addresses[j].Bytes()) < 0
+	})
+	var result []*Account
+	for _, address := range addresses {
+		account, _ := g.Account(address)
+		if filter == nil || filter(account) {
+			result = append(result, account)
+		}
+	}
+	return result
+}
+
+// StorageAt returns raw storage slot value (zero if slot is not set)
+func (g *Genesis) StorageAt(address common.Address, slot common.Hash) common.Hash {
+	return g.Alloc[address].Storage[slot]
+}
+
+// StorageByPath returns storage value for the Solidity variable path, see StorageSlot for path syntax
+func (g *Genesis) StorageByPath(address common.Address, path string) (common.Hash, error) {
+	slot, err := StorageSlot(path)
+	if err != nil {
+		return common.Hash{}, err
+	}
+	return g.StorageAt(address, slot), nil
+}
+
+// StorageSlot computes storage slot using Solidity layout rules. Path starts with variable's base slot number
+// and continues with accessors:
+//   [key]  mapping value (key is an address, 0x-prefixed 32-byte word or decimal number)
+//   {i}    element of dynamic array
+//   +n     n-th slot of the struct
+// For example "5[0x00a601f45688dba8a070722073b015277cf36725]+2" is the third field of the struct stored in
+// mapping at slot 5.
+func StorageSlot(path string) (common.Hash, error) {
+	end := strings.IndexAny(path, "[{+")
+	if end < 0 {
+		end = len(path)
+	}
+	base, ok := new(big.Int).SetString(path[:end], 0)
+	if !ok || base.Sign() < 0 || base.BitLen() > 256 {
+		return common.Hash{}, fmt.Errorf("invalid base slot in path: %s", path)
+	}
+	slot := common.BigToHash(base)
+	for rest := path[end:]; rest != ""; {
+		var closing byte
+		switch rest[0] {
+		case '[':
+			closing = ']'
+		case '{':
+			closing = '}'
+		case '+':
+			next := strings.IndexAny(rest[1:], "[{+")
+			if next < 0 {
+				next = len(rest) - 1
+			}
+			offset, err := strconv.ParseUint(rest[1:next+1], 10, 64)
+			if err != nil {
+				return common.Hash{}, fmt.Errorf("invalid struct offset in path: %s", path)
+			}
+			slot = common.BigToHash(new(big.Int).Add(slot.Big(), new(big.Int).SetUint64(offset)))
+			rest = rest[next+1:]
+			continue
+		default:
+			return common.Hash{}, fmt.Errorf("unexpected character (%c) in path: %s", rest[0], path)
+		}
+		end := strings.IndexByte(rest, closing)
+		if end < 0 {
+			return common.Hash{}, fmt.Errorf("unterminated accessor in path: %s", path)
+		}
+		key := rest[1:end]
+		if rest[0] == '[' {
+			word, err := mappingKey(key)
+			if err != nil {
+				return common.Hash{}, err
+			}
+			slot = crypto.Keccak256Hash(word.Bytes(), slot.Bytes())
+		} else {
+			index, ok := new(big.Int).SetString(key, 10)
+			if !ok || index.Sign() < 0 || index.BitLen() > 256 {
+				return common.Hash{}, fmt.Errorf("invalid array index (%s) in path: %s", key, path)
+			}
+			slot = common.BigToHash(new(big.Int).Add(crypto.Keccak256Hash(slot.Bytes()).Big(), index))
+		}
+		rest = rest[end+1:]
+	}
+	return slot, nil
+}
+
+func mappingKey(key string) (common.Hash, error) {
+	if common.IsHexAddress(key) && len(key) <= 42 {
+		return common.BytesToHash(common.HexToAddress(key).Bytes()), nil
+	}
+	if strings.HasPrefix(key, "0x") {
+		word, err := hexutil.Decode(key)
+		if err != nil || len(word) > 32 {
+			return common.Hash{}, fmt.Errorf("invalid mapping key: %s", key)
+		}
+		return common.BytesToHash(word), nil
+	}
+	value, ok := new(big.Int).SetString(key, 10)
+	if !ok || value.Sign() < 0 || value.BitLen() > 256 {
+		return common.Hash{}, fmt.Errorf("invalid mapping key: %s", key)
+	}
+	return common.BigToHash(value), nil
+}
+
+func contractName(address common.Address) string {
+	for name, systemAddress := range SystemContracts {
+		if systemAddress == address {
+			return name
+		}
+	}
+	return ""
+}
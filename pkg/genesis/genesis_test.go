@@ -0,0 +1,59 @@
+package genesis
+
+import (
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestStorageSlot(t *testing.T) {
+	for _, test := range []struct{ path, slot string }{
+		{"5", "0x0000000000000000000000000000000000000000000000000000000000000005"},
+		{"0x10", "0x0000000000000000000000000000000000000000000000000000000000000010"},
+		// keccak256(uint256(0)) is the data slot of dynamic array at slot 0
+		{"0{0}", "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"},
+		{"0{2}", "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e565"},
+		// keccak256(uint256(0) . uint256(0)) is the value of key 0 in mapping at slot 0
+		{"0[0]", "0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"},
+		{"0[0x0000000000000000000000000000000000000000]", "0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"},
+		{"0[0x0000000000000000000000000000000000000000]+1", "0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb6"},
+		{"3+2", "0x0000000000000000000000000000000000000000000000000000000000000005"},
+	} {
+		slot, err := StorageSlot(test.path)
+		if err != nil {
+			t.Fatalf("%s: %v", test.path, err)
+		}
+		if slot != common.HexToHash(test.slot) {
+			t.Fatalf("%s: expected slot %s, got %s", test.path, test.slot, slot.Hex())
+		}
+	}
+	for _, path := range []string{"-1", "0{-1}", "0x10000000000000000000000000000000000000000000000000000000000000000", "0[-1]", "0{1", "x"} {
+		if slot, err := StorageSlot(path); err == nil {
+			t.Fatalf("%s is accepted as %s", path, slot.Hex())
+		}
+	}
+}
+
+func TestAccountQueries(t *testing.T) {
+	genesis, err := Parse([]byte(`{"alloc": {
+		"0x0000000000000000000000000000000000001000": {"balance": "0x0", "code": "0x00"},
+		"0x0000000000000000000000000000000000007002": {"balance": "0x0", "code": "0x00"},
+		"0x00000000000000000000000000000000000000b0": {"balance": "0x64"},
+		"0x00000000000000000000000000000000000000a0": {"balance": "0x0a"}
+	}}`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	contracts := genesis.SystemContracts()
+	if len(contracts) != 2 || contracts[0].Name != "Staking" || contracts[1].Name != "Governance" {
+		t.Fatalf("unexpected system contracts %+v", contracts)
+	}
+	above := genesis.AllocationsAbove(big.NewInt(10))
+	if len(above) != 2 || above[0].Address != common.HexToAddress("0xa0") || above[1].Address != common.HexToAddress("0xb0") {
+		t.Fatalf("unexpected allocations %+v", above)
+	}
+	if above := genesis.AllocationsAbove(big.NewInt(11)); len(above) != 1 {
+		t.Fatalf("threshold isn't applied: %+v", above)
+	}
+}
@@ -0,0 +1,77 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core/vm"
+)
+
+// customPrecompileFactory creates chain specific precompile from params of the config entry, forks of the
+// generator register their precompiles with registerCustomPrecompile in init functions
+type customPrecompileFactory func(params json.RawMessage) (vm.PrecompiledContract, error)
+
+var customPrecompiles = map[string]customPrecompileFactory{
+	"static": newStaticPrecompile,
+}
+
+// registerCustomPrecompile makes the plugin available for precompiles.custom entries of the config
+func registerCustomPrecompile(name string, factory customPrecompileFactory) {
+	if _, ok := customPrecompiles[name]; ok {
+		panic(fmt.Sprintf("custom precompile %s is registered twice", name))
+	}
+	customPrecompiles[name] = factory
+}
+
+func customPrecompileNames() []string {
+	var result []string
+	for name := range customPrecompiles {
+		result = append(result, name)
+	}
+	sort.Strings(result)
+	return result
+}
+
+// customPrecompileConfig places the plugin precompile at the address
+type customPrecompileConfig struct {
+	Address common.Address  `json:"address"`
+	Plugin  string          `json:"plugin"`
+	Params  json.RawMessage `json:"params,omitempty"`
+}
+
+func (c customPrecompileConfig) create() (vm.PrecompiledContract, error) {
+	factory, ok := customPrecompiles[c.Plugin]
+	if !ok {
+		return nil, fmt.Errorf("unknown precompile plugin %q, registered plugins are: %s", c.Plugin, strings.Join(customPrecompileNames(), ", "))
+	}
+	return factory(c.Params)
+}
+
+// staticPrecompile returns the same output for any input, it's enough for constructors that only check
+// presence of the chain specific precompile
+type staticPrecompile struct {
+	Output hexutil.Bytes `json:"output"`
+	Gas    uint64        `json:"gas"`
+}
+
+func newStaticPrecompile(params json.RawMessage) (vm.PrecompiledContract, error) {
+	result := &staticPrecompile{}
+	if len(params) > 0 {
+		if err := json.Unmarshal(params, result); err != nil {
+			return nil, err
+		}
+	}
+	return result, nil
+}
+
+func (p *staticPrecompile) RequiredGas([]byte) uint64 {
+	return p.Gas
+}
+
+func (p *staticPrecompile) Run([]byte) ([]byte, error) {
+	return common.CopyBytes(p.Output), nil
+}
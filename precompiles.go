@@ -0,0 +1,189 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"math/big"
+	"sort"
+	"strings"
+	"sync"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core/vm"
+	"github.com/ethereum/go-ethereum/log"
+	"github.com/ethereum/go-ethereum/params"
+)
+
+const (
+	// precompiles of the node the genesis is generated for (BSC adds light client precompiles)
+	precompileSetNode = "node"
+	// only Ethereum precompiles 0x01-0x09, for chains that can be run by stock geth
+	precompileSetStandard = "standard"
+)
+
+// maxStandardPrecompile is the last precompile address defined by Ethereum up to Istanbul (blake2f)
+var maxStandardPrecompile = common.BytesToAddress([]byte{9})
+
+// precompileConfig changes precompiles available for constructors during simulation, it must match
+// precompiles of the client the chain runs on
+type precompileConfig struct {
+	// EVM fork of the target client (default is the fork activated by chain config)
+	Fork string `json:"fork,omitempty"`
+	// node (default) or standard
+	Set string `json:"set,omitempty"`
+	// precompiles removed from the set
+	Disable []common.Address `json:"disable,omitempty"`
+	// precompiles moved to another address (from -> to)
+	Remap map[common.Address]common.Address `json:"remap,omitempty"`
+	// chain specific precompiles provided by registered plugins
+	Custom []customPrecompileConfig `json:"custom,omitempty"`
+}
+
+func (c *precompileConfig) set() string {
+	if c == nil || c.Set == "" {
+		return precompileSetNode
+	}
+	return c.Set
+}
+
+// activePrecompiles returns the map the EVM uses for the rules (same selection as vm.EVM does)
+func activePrecompiles(rules params.Rules) map[common.Address]vm.PrecompiledContract {
+	switch {
+	case rules.IsBerlin:
+		return vm.PrecompiledContractsBerlin
+	case rules.IsIstanbul:
+		return vm.PrecompiledContractsIstanbul
+	case rules.IsByzantium:
+		return vm.PrecompiledContractsByzantium
+	default:
+		return vm.PrecompiledContractsHomestead
+	}
+}
+
+// precompileForks are precompile sets of EVM forks supported by go-ethereum (blake2f appears at Istanbul,
+// modexp is repriced at Berlin)
+var precompileForks = map[string]map[common.Address]vm.PrecompiledContract{
+	"homestead": vm.PrecompiledContractsHomestead,
+	"byzantium": vm.PrecompiledContractsByzantium,
+	"istanbul":  vm.PrecompiledContractsIstanbul,
+	"berlin":    vm.PrecompiledContractsBerlin,
+}
+
+func precompileForkNames() []string {
+	var result []string
+	for name := range precompileForks {
+		result = append(result, name)
+	}
+	sort.Strings(result)
+	return result
+}
+
+// precompilesForFork returns a copy of the precompile set exposed by the fork
+func precompilesForFork(fork string) (map[common.Address]vm.PrecompiledContract, error) {
+	precompiles, ok := precompileForks[strings.ToLower(fork)]
+	if !ok {
+		return nil, fmt.Errorf("unknown EVM fork %q, supported forks are: %s", fork, strings.Join(precompileForkNames(), ", "))
+	}
+	result := make(map[common.Address]vm.PrecompiledContract, len(precompiles))
+	for address, contract := range precompiles {
+		result[address] = contract
+	}
+	return result, nil
+}
+
+// simulationLock guards precompile sets shared by all EVMs of the process (gen serve and the C ABI run
+// generations concurrently): generations with precompile config hold the write lock from applyPrecompileConfig
+// until the sets are restored, the rest of simulations share the read lock
+var simulationLock sync.RWMutex
+
+// lockSimulation takes simulationLock for a simulation with the precompile config and returns the unlock function
+func lockSimulation(config *precompileConfig) func() {
+	if config != nil {
+		simulationLock.Lock()
+		return simulationLock.Unlock
+	}
+	simulationLock.RLock()
+	return simulationLock.RUnlock
+}
+
+// applyPrecompileConfig modifies the precompile set of the EVM, sets are global in go-ethereum, so returned
+// function must be called to restore the original one after simulation (simulationLock must be held for writing
+// if config isn't nil)
+func applyPrecompileConfig(config *precompileConfig, chainConfig *params.ChainConfig) (func(), error) {
+	if config == nil {
+		// nothing is modified, so concurrent simulations holding the read lock aren't disturbed
+		return func() {}, nil
+	}
+	precompiles := activePrecompiles(chainConfig.Rules(big.NewInt(0), false))
+	original := make(map[common.Address]vm.PrecompiledContract, len(precompiles))
+	for address, contract := range precompiles {
+		original[address] = contract
+	}
+	restore := func() {
+		for address := range precompiles {
+			delete(precompiles, address)
+		}
+		for address, contract := range original {
+			precompiles[address] = contract
+		}
+	}
+	if config.Fork != "" {
+		// the EVM picks the set by chain rules, so contents of that set are replaced by the set of the fork
+		forkPrecompiles, err := precompilesForFork(config.Fork)
+		if err != nil {
+			return nil, err
+		}
+		for address := range precompiles {
+			delete(precompiles, address)
+		}
+		for address, contract := range forkPrecompiles {
+			precompiles[address] = contract
+		}
+	}
+	selected := make(map[common.Address]vm.PrecompiledContract, len(precompiles))
+	for address, contract := range precompiles {
+		selected[address] = contract
+	}
+	switch config.set() {
+	case precompileSetNode:
+	case precompileSetStandard:
+		for address := range precompiles {
+			if bytes.Compare(address[:], maxStandardPrecompile[:]) > 0 {
+				delete(precompiles, address)
+			}
+		}
+	default:
+		restore()
+		return nil, fmt.Errorf("unknown precompile set %q, supported sets are: %s, %s", config.Set, precompileSetNode, precompileSetStandard)
+	}
+	for _, address := range config.Disable {
+		delete(precompiles, address)
+	}
+	// remove all sources first, so precompiles can be swapped
+	for from := range config.Remap {
+		if _, ok := selected[from]; !ok {
+			restore()
+			return nil, fmt.Errorf("there is no precompile at %s to remap", from.Hex())
+		}
+		delete(precompiles, from)
+	}
+	for from, to := range config.Remap {
+		precompiles[to] = selected[from]
+	}
+	for _, custom := range config.Custom {
+		contract, err := custom.create()
+		if err != nil {
+			restore()
+			return nil, fmt.Errorf("custom precompile %s: %w", custom.Address.Hex(), err)
+		}
+		precompiles[custom.Address] = contract
+	}
+	// constructors relying on ecrecover or pairing checks behave differently than on chain without them
+	for i := byte(1); i <= maxStandardPrecompile[common.AddressLength-1]; i++ {
+		if address := common.BytesToAddress([]byte{i}); precompiles[address] == nil && selected[address] != nil {
+			log.Warn("Standard precompile is not available during simulation", "address", address)
+		}
+	}
+	log.Info("Simulation precompiles", "fork", config.Fork, "set", config.set(), "count", len(precompiles), "disabled", len(config.Disable), "remapped", len(config.Remap), "custom", len(config.Custom))
+	return restore, nil
+}
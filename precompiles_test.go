@@ -0,0 +1,127 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"sync"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestPrecompileConfigRestoresSet(t *testing.T) {
+	genesis := defaultGenesisConfig(1337)
+	precompiles := activePrecompiles(genesis.Config.Rules(common.Big0, false))
+	size := len(precompiles)
+	ecrecover, sha256, moved := common.BytesToAddress([]byte{1}), common.BytesToAddress([]byte{2}), common.BytesToAddress([]byte{0x20})
+	restore, err := applyPrecompileConfig(&precompileConfig{
+		Set:     precompileSetStandard,
+		Disable: []common.Address{ecrecover},
+		Remap:   map[common.Address]common.Address{sha256: moved},
+	}, genesis.Config)
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, hasEcrecover := precompiles[ecrecover]
+	_, hasSha256 := precompiles[sha256]
+	_, hasMoved := precompiles[moved]
+	if hasEcrecover || hasSha256 || !hasMoved || len(precompiles) > 8 {
+		t.Fatalf("unexpected precompiles: %v", precompiles)
+	}
+	restore()
+	if _, ok := precompiles[ecrecover]; !ok || len(precompiles) != size {
+		t.Fatalf("precompiles are not restored")
+	}
+}
+
+func TestStandardPrecompilesAtCanonicalAddresses(t *testing.T) {
+	genesis := defaultGenesisConfig(1337)
+	precompiles := activePrecompiles(genesis.Config.Rules(common.Big0, false))
+	// ecrecover, sha256, ripemd160, identity, modexp, bn256 add/mul/pairing and blake2f
+	for i := byte(1); i <= 9; i++ {
+		if _, ok := precompiles[common.BytesToAddress([]byte{i})]; !ok {
+			t.Errorf("precompile 0x%02x is not available for constructors", i)
+		}
+	}
+}
+
+func TestPrecompilesForFork(t *testing.T) {
+	blake2f := common.BytesToAddress([]byte{9})
+	byzantium, err := precompilesForFork("byzantium")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, ok := byzantium[blake2f]; ok {
+		t.Fatalf("blake2f is available before Istanbul")
+	}
+	istanbul, err := precompilesForFork("Istanbul")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, ok := istanbul[blake2f]; !ok {
+		t.Fatalf("blake2f is not available at Istanbul")
+	}
+	if _, err := precompilesForFork("cancun"); err == nil {
+		t.Fatalf("unsupported fork is accepted")
+	}
+}
+
+func TestCustomPrecompilePlugin(t *testing.T) {
+	genesis := defaultGenesisConfig(1337)
+	address := common.HexToAddress("0x0000000000000000000000000000000000000100")
+	restore, err := applyPrecompileConfig(&precompileConfig{
+		Custom: []customPrecompileConfig{{Address: address, Plugin: "static", Params: []byte(`{"output":"0x01","gas":15}`)}},
+	}, genesis.Config)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer restore()
+	contract, ok := activePrecompiles(genesis.Config.Rules(common.Big0, false))[address]
+	if !ok {
+		t.Fatalf("custom precompile is not registered")
+	}
+	output, err := contract.Run(nil)
+	if err != nil || len(output) != 1 || output[0] != 1 || contract.RequiredGas(nil) != 15 {
+		t.Fatalf("unexpected custom precompile: output=%x, err=%v", output, err)
+	}
+}
+
+func TestConcurrentSimulationsWithPrecompileConfig(t *testing.T) {
+	// run with -race, simulations with changed precompiles must not overlap with the rest of simulations
+	custom := devNetConfig
+	custom.Precompiles = &precompileConfig{
+		Custom: []customPrecompileConfig{{Address: common.HexToAddress("0x0100"), Plugin: "static", Params: []byte(`{"output":"0x01"}`)}},
+	}
+	var configs, expected [][]byte
+	for _, config := range []genesisConfig{devNetConfig, custom} {
+		configJson, err := json.Marshal(config)
+		if err != nil {
+			t.Fatal(err)
+		}
+		genesis, err := generateGenesisJson(configJson)
+		if err != nil {
+			t.Fatal(err)
+		}
+		configs, expected = append(configs, configJson), append(expected, genesis)
+	}
+	var wg sync.WaitGroup
+	errs := make(chan error, 8)
+	for i := 0; i < cap(errs); i++ {
+		wg.Add(1)
+		go func(i int) {
+			defer wg.Done()
+			genesis, err := generateGenesisJson(configs[i%len(configs)])
+			if err != nil {
+				errs <- err
+			} else if !bytes.Equal(genesis, expected[i%len(configs)]) {
+				errs <- fmt.Errorf("generation %d differs from the sequential one", i)
+			}
+		}(i)
+	}
+	wg.Wait()
+	close(errs)
+	for err := range errs {
+		t.Error(err)
+	}
+}
@@ -0,0 +1,71 @@
+package main
+
+import (
+	"fmt"
+	"math/big"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// predeployContract is a well-known utility contract placed into genesis at its standard address, the runtime
+// bytecode is copied from canonical deployments as is (compiling sources with our solc settings would give
+// different bytecode at the well-known address)
+type predeployContract struct {
+	address common.Address
+	code    []byte
+}
+
+var predeployCatalog = map[string]*predeployContract{
+	// Arachnid's deterministic deployment proxy, used by foundry and hardhat for CREATE2 deployments
+	"create2deployer": {
+		address: common.HexToAddress("0x4e59b44847b379578588920cA78FbF26c0B4956C"),
+		code:    hexutil.MustDecode("0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3"),
+	},
+}
+
+func predeployNames() []string {
+	var result []string
+	for name := range predeployCatalog {
+		result = append(result, name)
+	}
+	sort.Strings(result)
+	return result
+}
+
+// parsePredeployList parses comma separated list of catalog names
+func parsePredeployList(value string) ([]string, error) {
+	var result []string
+	seen := make(map[string]bool)
+	for _, name := range strings.Split(value, ",") {
+		name = strings.ToLower(strings.TrimSpace(name))
+		if name == "" || seen[name] {
+			continue
+		}
+		if _, ok := predeployCatalog[name]; !ok {
+			return nil, fmt.Errorf("unknown predeploy %s, available are: %s", name, strings.Join(predeployNames(), ", "))
+		}
+		seen[name] = true
+		result = append(result, name)
+	}
+	return result, nil
+}
+
+func deployPredeploys(simulator *systemContractSimulator, names []string) deploymentFailures {
+	var failures deploymentFailures
+	for _, name := range names {
+		predeploy := predeployCatalog[name]
+		if account, ok := simulator.genesis.Alloc[predeploy.address]; ok && len(account.Code) > 0 {
+			failures.add(name, predeploy.address, fmt.Errorf("address already has code"))
+			continue
+		}
+		simulator.statedb.SetCode(predeploy.address, predeploy.code)
+		simulator.genesis.Alloc[predeploy.address] = core.GenesisAccount{Code: predeploy.code, Balance: big.NewInt(0)}
+		log.Info("Predeployed contract", "name", name, "address", predeploy.address)
+	}
+	return failures
+}
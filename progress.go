@@ -0,0 +1,42 @@
+package main
+
+import (
+	"time"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// progressLogInterval limits progress records of long loops (same interval as geth uses for import stats)
+const progressLogInterval = 8 * time.Second
+
+// generationPhase reports start and duration of the generation phase, loops inside of the phase report
+// progress not more often than progressLogInterval
+type generationPhase struct {
+	name    string
+	started time.Time
+	logged  time.Time
+}
+
+func startPhase(name string) *generationPhase {
+	log.Info("Phase started", "phase", name)
+	now := time.Now()
+	return &generationPhase{name: name, started: now, logged: now}
+}
+
+// progress logs number of processed items, total is zero if it's not known in advance
+func (p *generationPhase) progress(done, total int) {
+	if time.Since(p.logged) < progressLogInterval {
+		return
+	}
+	p.logged = time.Now()
+	if total > 0 {
+		log.Info("Phase progress", "phase", p.name, "done", done, "total", total, "elapsed", common.PrettyDuration(time.Since(p.started)))
+	} else {
+		log.Info("Phase progress", "phase", p.name, "done", done, "elapsed", common.PrettyDuration(time.Since(p.started)))
+	}
+}
+
+func (p *generationPhase) finish() {
+	log.Info("Phase finished", "phase", p.name, "elapsed", common.PrettyDuration(time.Since(p.started)))
+}
@@ -0,0 +1,62 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+// generatorCommit is git commit of the generator, set at build time with -ldflags "-X main.generatorCommit=..."
+var generatorCommit = "unknown"
+
+const provenanceVersion = 1
+
+// genesisProvenance records which inputs produced the genesis file, validators verify the genesis they boot
+// against it
+type genesisProvenance struct {
+	Version          int                    `json:"version"`
+	GeneratorVersion string                 `json:"generatorVersion"`
+	GeneratorCommit  string                 `json:"generatorCommit"`
+	ConfigHash       common.Hash            `json:"configHash"`
+	Artifacts        map[string]common.Hash `json:"artifacts"`
+	Flags            map[string]string      `json:"flags,omitempty"`
+	ConfigVersion    int                    `json:"configVersion"`
+	// file name and sha256 of the genesis file exactly as written (compressed if it has .gz suffix)
+	Genesis     string      `json:"genesis"`
+	GenesisHash common.Hash `json:"genesisHash"`
+}
+
+// writeGenesisAttestation writes <output>.sha256 (sha256sum format) and <output>.provenance.json next to the
+// generated genesis file
+func writeGenesisAttestation(outputFile string, lock *genesisLock) error {
+	if outputFile == "stdout" || outputFile == "stderr" {
+		return nil
+	}
+	data, err := os.ReadFile(outputFile)
+	if err != nil {
+		return err
+	}
+	hash := common.Hash(sha256.Sum256(data))
+	fileName := filepath.Base(outputFile)
+	checksum := fmt.Sprintf("%x  %s\n", hash, fileName)
+	if err := os.WriteFile(outputFile+".sha256", []byte(checksum), 0644); err != nil {
+		return err
+	}
+	provenance := &genesisProvenance{
+		Version:          provenanceVersion,
+		GeneratorVersion: lock.GeneratorVersion,
+		GeneratorCommit:  generatorCommit,
+		ConfigHash:       lock.ConfigHash,
+		Artifacts:        lock.Artifacts,
+		Flags:            lock.Flags,
+		ConfigVersion:    currentConfigVersion,
+		Genesis:          fileName,
+		GenesisHash:      hash,
+	}
+	provenanceJson, _ := json.MarshalIndent(provenance, "", "  ")
+	return os.WriteFile(outputFile+".provenance.json", append(provenanceJson, '\n'), 0644)
+}
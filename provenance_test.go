@@ -0,0 +1,39 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestGenesisAttestation(t *testing.T) {
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := os.WriteFile(outputFile, []byte("{}"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	lock := newGenesisLock([]byte(`{"chainId":14000}`), defaultArtifactSet(), map[string]string{"format": "geth"})
+	if err := writeGenesisAttestation(outputFile, lock); err != nil {
+		t.Fatal(err)
+	}
+	checksum, err := os.ReadFile(outputFile + ".sha256")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if expected := fmt.Sprintf("%x  genesis.json\n", sha256.Sum256([]byte("{}"))); string(checksum) != expected {
+		t.Fatalf("unexpected checksum file: %q", checksum)
+	}
+	data, err := os.ReadFile(outputFile + ".provenance.json")
+	if err != nil {
+		t.Fatal(err)
+	}
+	provenance := &genesisProvenance{}
+	if err := json.Unmarshal(data, provenance); err != nil {
+		t.Fatal(err)
+	}
+	if provenance.ConfigHash != lock.ConfigHash || len(provenance.Artifacts) != len(lock.Artifacts) || provenance.Genesis != "genesis.json" {
+		t.Fatalf("provenance doesn't match inputs: %s", data)
+	}
+}
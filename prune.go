@@ -0,0 +1,53 @@
+package main
+
+import (
+	"fmt"
+	"io"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// pruneReport lists what was removed from genesis alloc by pruneAlloc
+type pruneReport struct {
+	// zero-valued storage entries (they aren't stored in the state trie anyway)
+	StorageSlots int `json:"storageSlots"`
+	// accounts without balance, nonce, code and storage (EIP-161 empty accounts)
+	Accounts []checksumAddress `json:"accounts,omitempty"`
+}
+
+// pruneAlloc strips zero storage values and drops empty accounts left by constructors, accounts listed in keep
+// (system accounts required by consensus engine) stay in alloc even if they are empty
+func pruneAlloc(genesis *core.Genesis, keep []common.Address) *pruneReport {
+	kept := make(map[common.Address]bool, len(keep))
+	for _, address := range keep {
+		kept[address] = true
+	}
+	report := &pruneReport{}
+	for _, address := range sortedAlloc(genesis) {
+		account := genesis.Alloc[address]
+		for key, value := range account.Storage {
+			if value == (common.Hash{}) {
+				delete(account.Storage, key)
+				report.StorageSlots++
+			}
+		}
+		if len(account.Storage) == 0 {
+			account.Storage = nil
+		}
+		genesis.Alloc[address] = account
+		empty := account.Nonce == 0 && len(account.Code) == 0 && len(account.Storage) == 0 && (account.Balance == nil || account.Balance.Sign() == 0)
+		if empty && !kept[address] {
+			delete(genesis.Alloc, address)
+			report.Accounts = append(report.Accounts, checksumAddress(address))
+		}
+	}
+	return report
+}
+
+func (r *pruneReport) print(w io.Writer) {
+	fmt.Fprintf(w, "pruned %d zero storage slot(s) and %d empty account(s)\n", r.StorageSlots, len(r.Accounts))
+	for _, address := range r.Accounts {
+		fmt.Fprintf(w, " - %s\n", address.Hex())
+	}
+}
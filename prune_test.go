@@ -0,0 +1,34 @@
+package main
+
+import (
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestPruneAlloc(t *testing.T) {
+	contract := common.HexToAddress("0x0000000000000000000000000000000000001000")
+	empty := common.HexToAddress("0x00000000000000000000000000000000000000ff")
+	genesis := &core.Genesis{Alloc: core.GenesisAlloc{
+		contract: {
+			Balance: big.NewInt(0),
+			Code:    []byte{0x60, 0x00},
+			Storage: map[common.Hash]common.Hash{
+				common.HexToHash("0x01"): {},
+				common.HexToHash("0x02"): common.HexToHash("0x2a"),
+			},
+		},
+		empty:                     {Balance: big.NewInt(0)},
+		intermediarySystemAddress: {Balance: big.NewInt(0)},
+		{}:                        {Balance: big.NewInt(0), Nonce: 1},
+	}}
+	report := pruneAlloc(genesis, []common.Address{intermediarySystemAddress})
+	if report.StorageSlots != 1 || len(report.Accounts) != 1 || report.Accounts[0] != checksumAddress(empty) {
+		t.Fatalf("unexpected report: %+v", report)
+	}
+	if len(genesis.Alloc) != 3 || len(genesis.Alloc[contract].Storage) != 1 {
+		t.Fatalf("unexpected alloc after pruning: %v", genesis.Alloc)
+	}
+}
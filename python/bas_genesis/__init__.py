@@ -0,0 +1,84 @@
+"""Python bindings of the BAS genesis generator.
+
+The module calls libbasgenesis.so built with ``make cshared``, the library is looked up in $BAS_GENESIS_LIB,
+next to this package and in ../build of the repository.
+"""
+
+import ctypes
+import json
+import os
+
+__all__ = ["BasGenesisError", "load_config", "generate", "inspect"]
+
+
+class BasGenesisError(Exception):
+    """Failure reported by the generator, fields are the same as of --error-format json."""
+
+    def __init__(self, output):
+        super().__init__(output.get("message", "unknown error"))
+        self.category = output.get("category")
+        self.exit_code = output.get("exitCode")
+        self.violations = output.get("violations", [])
+        self.failures = output.get("failures", [])
+
+
+def _library_path():
+    if os.environ.get("BAS_GENESIS_LIB"):
+        return os.environ["BAS_GENESIS_LIB"]
+    here = os.path.dirname(os.path.abspath(__file__))
+    for candidate in (os.path.join(here, "libbasgenesis.so"), os.path.join(here, "..", "..", "build", "libbasgenesis.so")):
+        if os.path.exists(candidate):
+            return candidate
+    raise OSError("libbasgenesis.so isn't found, build it with `make cshared` or set BAS_GENESIS_LIB")
+
+
+_lib = None
+
+
+def _library():
+    global _lib
+    if _lib is None:
+        lib = ctypes.CDLL(_library_path())
+        out = ctypes.POINTER(ctypes.c_void_p)
+        lib.bas_genesis_generate.argtypes = [ctypes.c_char_p, out]
+        lib.bas_genesis_load_config.argtypes = [ctypes.c_char_p, out]
+        lib.bas_genesis_inspect.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.c_int, out]
+        lib.bas_genesis_free.argtypes = [ctypes.c_void_p]
+        _lib = lib
+    return _lib
+
+
+def _call(function, *args):
+    lib = _library()
+    output = ctypes.c_void_p()
+    code = getattr(lib, function)(*args, ctypes.byref(output))
+    try:
+        result = json.loads(ctypes.string_at(output.value).decode())
+    finally:
+        lib.bas_genesis_free(output)
+    if code != 0:
+        raise BasGenesisError(result)
+    return result
+
+
+def _encode(value):
+    if isinstance(value, (dict, list)):
+        value = json.dumps(value)
+    if isinstance(value, str):
+        value = value.encode()
+    return value
+
+
+def load_config(config):
+    """Validates config (dict or JSON) and returns it as the generator sees it."""
+    return _call("bas_genesis_load_config", _encode(config))
+
+
+def generate(config):
+    """Generates genesis from config (dict or JSON), returns genesis in geth layout."""
+    return _call("bas_genesis_generate", _encode(config))
+
+
+def inspect(genesis, contract, raw=False):
+    """Decodes storage of the system contract (name or address) in genesis (dict or JSON) into named variables."""
+    return _call("bas_genesis_inspect", _encode(genesis), contract.encode(), 1 if raw else 0)
@@ -0,0 +1,92 @@
+package main
+
+import (
+	"encoding/json"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+const replayBundleVersion = 1
+
+// replayTransaction is a system transaction (sender is the zero address) that reproduces a part of the genesis
+// state, deployments place init code result at the fixed address, so they can't be regular signed transactions
+type replayTransaction struct {
+	Contract string `json:"contract"`
+	// deploy, call (follow-up call) or init
+	Kind  string         `json:"kind"`
+	From  common.Address `json:"from"`
+	To    common.Address `json:"to"`
+	Data  hexutil.Bytes  `json:"data"`
+	Value *hexutil.Big   `json:"value"`
+	// gas used in simulation (0 for follow-up calls, they are not metered separately)
+	GasUsed hexutil.Uint64 `json:"gasUsed"`
+}
+
+// replayBundle lists transactions that initialize system contracts at block 1 instead of pre-allocated state,
+// they are executed in the listed order
+type replayBundle struct {
+	Version      int                  `json:"version"`
+	ChainId      int64                `json:"chainId"`
+	Transactions []*replayTransaction `json:"transactions"`
+}
+
+// newReplayBundle encodes deployments of system contracts with their follow-up and init calls
+func newReplayBundle(chainId int64, deployments []*systemContractDeployment, artifacts artifactSet, report *deploymentReport) (*replayBundle, error) {
+	gasUsed := make(map[common.Address]*contractReport)
+	for _, contract := range report.Contracts {
+		gasUsed[common.Address(contract.Address)] = contract
+	}
+	result := &replayBundle{Version: replayBundleVersion, ChainId: chainId}
+	for _, deployment := range deployments {
+		rawArtifact := artifacts[deployment.name]
+		artifact := &artifactData{}
+		if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+			return nil, err
+		}
+		ctor, err := encodeConstructor(deployment, rawArtifact)
+		if err != nil {
+			return nil, err
+		}
+		bytecode, err := hexutil.Decode(artifact.Bytecode)
+		if err != nil {
+			return nil, err
+		}
+		var deployGas, initGas uint64
+		if contract, ok := gasUsed[deployment.address]; ok {
+			deployGas, initGas = contract.GasUsed, contract.InitGasUsed
+		}
+		result.Transactions = append(result.Transactions, &replayTransaction{
+			Contract: deployment.name,
+			Kind:     "deploy",
+			To:       deployment.address,
+			Data:     append(bytecode, ctor...),
+			Value:    (*hexutil.Big)(deployment.initialBalance()),
+			GasUsed:  hexutil.Uint64(deployGas),
+		})
+		for _, call := range deployment.followUpCalls {
+			value := call.value
+			if value == nil {
+				value = big.NewInt(0)
+			}
+			result.Transactions = append(result.Transactions, &replayTransaction{
+				Contract: deployment.name,
+				Kind:     "call",
+				From:     call.from,
+				To:       deployment.address,
+				Data:     call.input,
+				Value:    (*hexutil.Big)(value),
+			})
+		}
+		result.Transactions = append(result.Transactions, &replayTransaction{
+			Contract: deployment.name,
+			Kind:     "init",
+			To:       deployment.address,
+			Data:     hexutil.MustDecode("0xe1c7392a"),
+			Value:    (*hexutil.Big)(big.NewInt(0)),
+			GasUsed:  hexutil.Uint64(initGas),
+		})
+	}
+	return result, nil
+}
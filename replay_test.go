@@ -0,0 +1,61 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"math/big"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/core/vm"
+
+	bgenesis "github.com/Ankr-network/bas-genesis-config/pkg/genesis"
+)
+
+func TestReplayBundleReproducesContracts(t *testing.T) {
+	dir := t.TempDir()
+	options := defaultGenerateOptions()
+	options.TransactionsFile = filepath.Join(dir, "transactions.json")
+	outputFile := filepath.Join(dir, "genesis.json")
+	if err := createGenesisConfig(devNetConfig, outputFile, options); err != nil {
+		t.Fatal(err)
+	}
+	genesis, err := bgenesis.Load(outputFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	data, err := os.ReadFile(options.TransactionsFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	bundle := &replayBundle{}
+	if err := json.Unmarshal(data, bundle); err != nil {
+		t.Fatal(err)
+	}
+	// execute the bundle against empty state, as a client initializing system contracts at block 1 would do
+	simulator, err := newSystemContractSimulator(defaultGenesisConfig(devNetConfig.ChainId))
+	if err != nil {
+		t.Fatal(err)
+	}
+	deployed := 0
+	for _, tx := range bundle.Transactions {
+		switch tx.Kind {
+		case "deploy":
+			if _, err := simulator.deploy(tx.To, tx.Data, (*big.Int)(tx.Value)); err != nil {
+				t.Fatalf("failed to deploy %s: %s", tx.Contract, err)
+			}
+			deployed++
+		default:
+			if _, _, err := simulator.evm.Call(vm.AccountRef(tx.From), tx.To, tx.Data, defaultSimulationGas, (*big.Int)(tx.Value)); err != nil {
+				t.Fatalf("%s call to %s failed: %s", tx.Kind, tx.Contract, err)
+			}
+		}
+		if tx.Kind == "deploy" && !bytes.Equal(simulator.genesis.Alloc[tx.To].Code, genesis.Alloc[tx.To].Code) {
+			t.Fatalf("replayed code of %s differs from genesis", tx.Contract)
+		}
+	}
+	if deployed != len(genesis.SystemContracts()) {
+		t.Fatalf("bundle deploys %d contracts, genesis has %d", deployed, len(genesis.SystemContracts()))
+	}
+}
@@ -0,0 +1,156 @@
+package main
+
+import (
+	"fmt"
+	"io"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/crypto"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+type constructorArgReport struct {
+	Type  string      `json:"type"`
+	Value interface{} `json:"value"`
+}
+
+type contractReport struct {
+	Name            string                 `json:"name"`
+	Address         checksumAddress        `json:"address"`
+	CodeSize        int                    `json:"codeSize"`
+	CodeHash        common.Hash            `json:"codeHash"`
+	StorageSlots    int                    `json:"storageSlots"`
+	ConstructorArgs []constructorArgReport `json:"constructorArgs"`
+	GasUsed         uint64                 `json:"gasUsed"`
+	InitGasUsed     uint64                 `json:"initGasUsed"`
+	PrecompileCalls []precompileCall       `json:"precompileCalls,omitempty"`
+	Events          []*eventReport         `json:"events,omitempty"`
+	// genesis storage labeled with variables of the storage layout (only if layout is available)
+	Storage []*annotatedSlot `json:"storage,omitempty"`
+}
+
+type validatorReport struct {
+	Address        checksumAddress `json:"address"`
+	Owner          checksumAddress `json:"owner"`
+	CommissionRate commissionRate  `json:"commissionRate"`
+	Moniker        string          `json:"moniker,omitempty"`
+	BlsPublicKey   hexutil.Bytes   `json:"blsPublicKey,omitempty"`
+}
+
+// deploymentReport summarizes deployed system contracts, it's written next to the genesis file
+type deploymentReport struct {
+	ChainId    int64                  `json:"chainId"`
+	Contracts  []*contractReport      `json:"contracts"`
+	Validators []*validatorReport     `json:"validators"`
+	Timing     *consensusTimingReport `json:"timing,omitempty"`
+	Supply     *supplyReport          `json:"supply,omitempty"`
+	Pruned     *pruneReport           `json:"pruned,omitempty"`
+	Metadata   *metadataReport        `json:"metadata,omitempty"`
+	Immutables []*immutablesReport    `json:"immutables,omitempty"`
+
+	storageLayouts map[string]*storageLayout
+}
+
+func (r *deploymentReport) addValidators(config genesisConfig) {
+	for _, validator := range config.Validators {
+		r.Validators = append(r.Validators, &validatorReport{
+			Address:        checksumAddress(validator.Address),
+			Owner:          checksumAddress(validator.owner()),
+			CommissionRate: validator.commissionRate(config.CommissionRate),
+			Moniker:        validator.Moniker,
+			BlsPublicKey:   validator.BlsPublicKey,
+		})
+	}
+}
+
+func (r *deploymentReport) addContract(deployment *systemContractDeployment, account core.GenesisAccount, result *simulationResult) {
+	contract := &contractReport{
+		Name:            deployment.name,
+		Address:         checksumAddress(deployment.address),
+		CodeSize:        len(account.Code),
+		CodeHash:        crypto.Keccak256Hash(account.Code),
+		StorageSlots:    len(account.Storage),
+		ConstructorArgs: []constructorArgReport{},
+		GasUsed:         result.GasUsed,
+		InitGasUsed:     result.InitGasUsed,
+		PrecompileCalls: result.PrecompileCalls,
+	}
+	for i, typeName := range deployment.typeNames {
+		contract.ConstructorArgs = append(contract.ConstructorArgs, constructorArgReport{Type: typeName, Value: deployment.params[i]})
+	}
+	if layout := r.storageLayouts[deployment.name]; layout != nil {
+		storage, err := annotateStorage(layout, account.Storage, systemContractAddresses)
+		if err != nil {
+			log.Warn("Failed to label storage slots", "contract", deployment.name, "err", err)
+		}
+		contract.Storage = storage
+	}
+	// events of other contracts (called by ctor) are reported with raw data
+	for _, emitted := range result.Logs {
+		contractAbi := result.contractAbi
+		if emitted.Address != deployment.address {
+			contractAbi = nil
+		}
+		contract.Events = append(contract.Events, decodeEvent(emitted, contractAbi))
+	}
+	r.Contracts = append(r.Contracts, contract)
+}
+
+func (r *deploymentReport) print(w io.Writer) {
+	fmt.Fprintf(w, "deployed %d system contract(s):\n", len(r.Contracts))
+	for _, c := range r.Contracts {
+		fmt.Fprintf(w, " + %s at %s\n", c.Name, c.Address.Hex())
+		fmt.Fprintf(w, "   code: %d bytes, hash %s\n", c.CodeSize, c.CodeHash.Hex())
+		fmt.Fprintf(w, "   storage: %d slot(s)\n", c.StorageSlots)
+		fmt.Fprintf(w, "   gas used: %d (ctor) + %d (init)\n", c.GasUsed, c.InitGasUsed)
+		for _, arg := range c.ConstructorArgs {
+			fmt.Fprintf(w, "   arg %s: %v\n", arg.Type, arg.Value)
+		}
+		for _, event := range c.Events {
+			fmt.Fprintf(w, "   event %s\n", event)
+		}
+		for _, call := range c.PrecompileCalls {
+			fmt.Fprintf(w, "   precompile %s: %d byte(s) input, %d gas\n", call.Address.Hex(), call.InputSize, call.GasUsed)
+		}
+	}
+	fmt.Fprintf(w, "genesis validator(s):\n")
+	for _, v := range r.Validators {
+		fmt.Fprintf(w, " + %s (owner %s, commission %d bp) %s\n", v.Address.Hex(), v.Owner.Hex(), v.CommissionRate, v.Moniker)
+		if len(v.BlsPublicKey) > 0 {
+			fmt.Fprintf(w, "   bls public key %s\n", v.BlsPublicKey)
+		}
+	}
+	if len(r.Immutables) > 0 {
+		printImmutables(w, r.Immutables)
+	}
+	if r.Timing != nil {
+		r.Timing.print(w)
+	}
+	if r.Supply != nil {
+		r.Supply.print(w)
+	}
+	if r.Metadata != nil {
+		r.Metadata.print(w)
+	}
+	if r.Pruned != nil {
+		r.Pruned.print(w)
+	}
+}
+
+// printFundedAccounts prints all accounts with non-zero balance in address order
+func printFundedAccounts(w io.Writer, genesis *core.Genesis) {
+	fmt.Fprintf(w, "funded account(s):\n")
+	for _, address := range sortedAlloc(genesis) {
+		account := genesis.Alloc[address]
+		if account.Balance == nil || account.Balance.Sign() == 0 {
+			continue
+		}
+		kind := "account"
+		if len(account.Code) > 0 {
+			kind = "contract"
+		}
+		fmt.Fprintf(w, " + %s %s (%s)\n", address.Hex(), formatAmount(account.Balance), kind)
+	}
+}
@@ -0,0 +1,95 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"math/big"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// panicSelector is the selector of Panic(uint256) raised by failed asserts and checked arithmetic
+var panicSelector = []byte{0x4e, 0x48, 0x7b, 0x71}
+
+// panicCodes are descriptions of Solidity panic codes
+var panicCodes = map[uint64]string{
+	0x00: "generic panic",
+	0x01: "assertion failed",
+	0x11: "arithmetic overflow or underflow",
+	0x12: "division or modulo by zero",
+	0x21: "invalid enum value",
+	0x22: "invalid storage byte array encoding",
+	0x31: "pop from empty array",
+	0x32: "array index out of bounds",
+	0x41: "out of memory",
+	0x51: "call to zero-initialized function",
+}
+
+func revertReason(result []byte) string {
+	return decodeRevert(result, nil)
+}
+
+// decodeRevert decodes Error(string), Panic(uint256) and custom errors declared in the ABI (optional),
+// unknown revert data is returned as hex
+func decodeRevert(result []byte, contractAbi *abi.ABI) string {
+	if reason, err := abi.UnpackRevert(result); err == nil {
+		return reason
+	}
+	if len(result) == 4+32 && bytes.Equal(result[:4], panicSelector) {
+		code := new(big.Int).SetBytes(result[4:])
+		description, ok := panicCodes[code.Uint64()]
+		if !ok || !code.IsUint64() {
+			description = "unknown panic code"
+		}
+		return fmt.Sprintf("panic 0x%x: %s", code, description)
+	}
+	if contractAbi != nil && len(result) >= 4 {
+		for _, customError := range contractAbi.Errors {
+			if !bytes.Equal(customError.ID[:4], result[:4]) {
+				continue
+			}
+			values, err := customError.Inputs.Unpack(result[4:])
+			if err != nil {
+				break
+			}
+			var args []string
+			for _, value := range values {
+				args = append(args, fmt.Sprintf("%v", value))
+			}
+			return fmt.Sprintf("%s(%s)", customError.Name, strings.Join(args, ", "))
+		}
+	}
+	return hexutil.Encode(result)
+}
+
+// revertError is a failed EVM call with its revert data, the data is decoded when the error is printed, so
+// callers that know the contract ABI can attach it to decode custom errors
+type revertError struct {
+	cause error
+	data  []byte
+	abi   *abi.ABI
+}
+
+func (e *revertError) Error() string {
+	return fmt.Sprintf("%s (%s)", e.cause, decodeRevert(e.data, e.abi))
+}
+
+func (e *revertError) Unwrap() error {
+	return e.cause
+}
+
+// withRevertAbi attaches contract ABI to the revert error, so custom errors of the contract are decoded
+func withRevertAbi(err error, rawAbi json.RawMessage) error {
+	var revert *revertError
+	if !errors.As(err, &revert) {
+		return err
+	}
+	if contractAbi, abiErr := abi.JSON(bytes.NewReader(rawAbi)); abiErr == nil {
+		revert.abi = &contractAbi
+	}
+	return err
+}
@@ -0,0 +1,32 @@
+package main
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+func TestDecodeRevert(t *testing.T) {
+	contractAbi, err := abi.JSON(strings.NewReader(`[{"type":"error","name":"InsufficientStake","inputs":[{"name":"required","type":"uint256"}]}]`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	customError := append(contractAbi.Errors["InsufficientStake"].ID[:4:4], make([]byte, 32)...)
+	customError[len(customError)-1] = 7
+	for _, test := range []struct {
+		data     string
+		abi      *abi.ABI
+		expected string
+	}{
+		{"0x08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000036261640000000000000000000000000000000000000000000000000000000000", nil, "bad"},
+		{"0x4e487b710000000000000000000000000000000000000000000000000000000000000011", nil, "panic 0x11: arithmetic overflow or underflow"},
+		{hexutil.Encode(customError), &contractAbi, "InsufficientStake(7)"},
+		{hexutil.Encode(customError), nil, hexutil.Encode(customError)},
+	} {
+		if reason := decodeRevert(hexutil.MustDecode(test.data), test.abi); reason != test.expected {
+			t.Errorf("decodeRevert(%s) = %q, want %q", test.data, reason, test.expected)
+		}
+	}
+}
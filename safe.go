@@ -0,0 +1,163 @@
+package main
+
+import (
+	"bytes"
+	_ "embed"
+	"encoding/json"
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+//go:embed build/contracts/GnosisSafeL2.json
+var safeSingletonRawArtifact []byte
+
+//go:embed build/contracts/GnosisSafeProxyFactory.json
+var safeProxyFactoryRawArtifact []byte
+
+//go:embed build/contracts/GnosisSafeProxy.json
+var safeProxyRawArtifact []byte
+
+// Safe contracts are compiled by truffle with the repo's solc, so their code differs from the canonical Safe
+// deployments and they're placed next to other genesis contracts instead of the canonical addresses
+var defaultSafeAddress = common.HexToAddress("0x0000000000000000000000000000000000007200")
+var safeSingletonAddress = common.HexToAddress("0x0000000000000000000000000000000000007201")
+var safeProxyFactoryAddress = common.HexToAddress("0x0000000000000000000000000000000000007202")
+
+// Safe keeps owners in a linked list, the sentinel can't be an owner
+var safeSentinelOwner = common.HexToAddress("0x0000000000000000000000000000000000000001")
+
+// safeConfig declares a multisig (Safe v1.3.0 proxy) initialized in genesis, e.g. to be the chain treasury
+type safeConfig struct {
+	// address of the Safe proxy (default is 0x0000000000000000000000000000000000007200)
+	Address   *common.Address  `json:"address,omitempty"`
+	Owners    []common.Address `json:"owners"`
+	Threshold uint64           `json:"threshold"`
+	// optional fallback handler (e.g. CompatibilityFallbackHandler deployed as an extra contract)
+	FallbackHandler *common.Address `json:"fallbackHandler,omitempty"`
+}
+
+func (c *safeConfig) address() common.Address {
+	if c.Address != nil {
+		return *c.Address
+	}
+	return defaultSafeAddress
+}
+
+func (c *safeConfig) validate(occupied map[common.Address]string, violations *configViolations) {
+	address := c.address()
+	for _, used := range []common.Address{address, safeSingletonAddress, safeProxyFactoryAddress} {
+		if owner, ok := occupied[used]; ok {
+			violations.add("safe.address", "%s is already used by %s", used.Hex(), owner)
+		} else {
+			occupied[used] = "safe"
+		}
+	}
+	if address == safeSingletonAddress || address == safeProxyFactoryAddress {
+		violations.add("safe.address", "%s is reserved for Safe singleton and proxy factory", address.Hex())
+	}
+	if len(c.Owners) == 0 {
+		violations.add("safe.owners", "at least one owner is required")
+	}
+	owners := make(map[common.Address]int)
+	for i, owner := range c.Owners {
+		path := fmt.Sprintf("safe.owners[%d]", i)
+		if owner == (common.Address{}) || owner == safeSentinelOwner || owner == address {
+			violations.add(path, "%s can't be an owner", owner.Hex())
+		} else if prev, ok := owners[owner]; ok {
+			violations.add(path, "duplicates safe.owners[%d] (%s)", prev, owner.Hex())
+		} else {
+			owners[owner] = i
+		}
+	}
+	if c.Threshold == 0 || c.Threshold > uint64(len(c.Owners)) {
+		violations.add("safe.threshold", "must be between 1 and number of owners (%d), got %d", len(c.Owners), c.Threshold)
+	}
+}
+
+// deploySafe deploys Safe singleton and proxy factory, then the proxy at the configured address, it's set up by
+// the zero address like any other account would do it with the freshly created proxy
+func deploySafe(simulator *systemContractSimulator, config *safeConfig) deploymentFailures {
+	if config == nil {
+		return nil
+	}
+	var failures deploymentFailures
+	address := config.address()
+	for _, contract := range []struct {
+		name        string
+		address     common.Address
+		rawArtifact []byte
+	}{
+		{"GnosisSafeL2", safeSingletonAddress, safeSingletonRawArtifact},
+		{"GnosisSafeProxyFactory", safeProxyFactoryAddress, safeProxyFactoryRawArtifact},
+	} {
+		if account, ok := simulator.genesis.Alloc[contract.address]; ok && len(account.Code) > 0 {
+			failures.add(contract.name, contract.address, fmt.Errorf("address already has code"))
+			return failures
+		}
+		artifact := &artifactData{}
+		if err := json.Unmarshal(contract.rawArtifact, artifact); err != nil {
+			failures.add(contract.name, contract.address, err)
+			return failures
+		}
+		if _, err := simulator.deploy(contract.address, hexutil.MustDecode(artifact.Bytecode), big.NewInt(0)); err != nil {
+			failures.add(contract.name, contract.address, err)
+			return failures
+		}
+	}
+	if account, ok := simulator.genesis.Alloc[address]; ok && len(account.Code) > 0 {
+		failures.add("Safe", address, fmt.Errorf("address already has code"))
+		return failures
+	}
+	proxyInitCode, err := safeProxyInitCode()
+	if err != nil {
+		failures.add("Safe", address, err)
+		return failures
+	}
+	setup, err := safeSetupInput(config)
+	if err != nil {
+		failures.add("Safe", address, err)
+		return failures
+	}
+	if _, err := simulator.deploy(address, proxyInitCode, big.NewInt(0)); err != nil {
+		failures.add("Safe", address, err)
+		return failures
+	}
+	if err := simulator.runFollowUpCalls(address, []followUpCall{{from: common.Address{}, input: setup}}); err != nil {
+		failures.add("Safe", address, err)
+		return failures
+	}
+	log.Info("Deployed Safe", "address", address, "owners", len(config.Owners), "threshold", config.Threshold, "singleton", safeSingletonAddress, "factory", safeProxyFactoryAddress)
+	return nil
+}
+
+// safeProxyInitCode returns proxy bytecode with the singleton address as ctor argument
+func safeProxyInitCode() ([]byte, error) {
+	artifact := &artifactData{}
+	if err := json.Unmarshal(safeProxyRawArtifact, artifact); err != nil {
+		return nil, err
+	}
+	return append(hexutil.MustDecode(artifact.Bytecode), common.LeftPadBytes(safeSingletonAddress.Bytes(), 32)...), nil
+}
+
+// safeSetupInput encodes setup(owners, threshold, to, data, fallbackHandler, paymentToken, payment, paymentReceiver)
+// without delegate call and refund
+func safeSetupInput(config *safeConfig) ([]byte, error) {
+	artifact := &artifactData{}
+	if err := json.Unmarshal(safeSingletonRawArtifact, artifact); err != nil {
+		return nil, err
+	}
+	safeAbi, err := abi.JSON(bytes.NewReader(artifact.Abi))
+	if err != nil {
+		return nil, err
+	}
+	var fallbackHandler common.Address
+	if config.FallbackHandler != nil {
+		fallbackHandler = *config.FallbackHandler
+	}
+	return safeAbi.Pack("setup", config.Owners, new(big.Int).SetUint64(config.Threshold), common.Address{}, []byte{}, fallbackHandler, common.Address{}, big.NewInt(0), common.Address{})
+}
@@ -0,0 +1,60 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"math/big"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestSafePredeploy(t *testing.T) {
+	config := devNetConfig
+	owners := []common.Address{common.HexToAddress("0x00000000000000000000000000000000000000a1"), common.HexToAddress("0x00000000000000000000000000000000000000a2")}
+	config.Safe = &safeConfig{Owners: owners, Threshold: 2}
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+	data, err := os.ReadFile(outputFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	genesis := &core.Genesis{}
+	if err := json.Unmarshal(data, genesis); err != nil {
+		t.Fatal(err)
+	}
+	for _, address := range []common.Address{defaultSafeAddress, safeSingletonAddress, safeProxyFactoryAddress} {
+		if len(genesis.Alloc[address].Code) == 0 {
+			t.Fatalf("no code at %s", address.Hex())
+		}
+	}
+	// Safe v1.3.0 layout: singleton at slot 0, ownerCount at slot 3 and threshold at slot 4
+	storage := genesis.Alloc[defaultSafeAddress].Storage
+	slot := func(index int64) common.Hash { return storage[common.BigToHash(big.NewInt(index))] }
+	if common.BytesToAddress(slot(0).Bytes()) != safeSingletonAddress {
+		t.Fatalf("proxy doesn't point to singleton: %v", storage)
+	}
+	if slot(3) != common.BigToHash(big.NewInt(2)) || slot(4) != common.BigToHash(big.NewInt(2)) {
+		t.Fatalf("unexpected owner count or threshold: %v", storage)
+	}
+}
+
+func TestSafeViolations(t *testing.T) {
+	config := devNetConfig
+	owner := common.HexToAddress("0x00000000000000000000000000000000000000a1")
+	// duplicated owner, sentinel owner and threshold above the number of owners
+	config.Safe = &safeConfig{Owners: []common.Address{owner, owner, safeSentinelOwner}, Threshold: 4}
+	var violations configViolations
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 3 {
+		t.Fatalf("expected 3 violations, got %v", err)
+	}
+	config.Safe = &safeConfig{Owners: []common.Address{owner}, Threshold: 1}
+	if err := config.validate(); err != nil {
+		t.Fatal(err)
+	}
+}
@@ -0,0 +1,172 @@
+package main
+
+import (
+	"encoding"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"reflect"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/common/math"
+)
+
+var (
+	addressSchema  = orderedObject{{Key: "type", Value: "string"}, {Key: "pattern", Value: "^0x[0-9a-fA-F]{40}$"}}
+	hashSchema     = orderedObject{{Key: "type", Value: "string"}, {Key: "pattern", Value: "^0x[0-9a-fA-F]{64}$"}}
+	bytesSchema    = orderedObject{{Key: "type", Value: "string"}, {Key: "pattern", Value: "^0x([0-9a-fA-F]{2})*$"}}
+	quantitySchema = orderedObject{{Key: "type", Value: "string"}, {Key: "pattern", Value: "^(0x[0-9a-fA-F]+|[0-9]+)$"}}
+	amountSchema   = orderedObject{
+		{Key: "type", Value: "string"},
+		{Key: "description", Value: "wei amount: decimal, 0x-prefixed hex or decimal with unit (e.g. \"1.5 ether\")"},
+	}
+)
+
+// treasury is a single address or shares of the accounts in basis points
+var treasurySchema = orderedObject{{Key: "oneOf", Value: []interface{}{addressSchema, orderedObject{
+	{Key: "type", Value: "object"},
+	{Key: "propertyNames", Value: addressSchema},
+	{Key: "additionalProperties", Value: orderedObject{{Key: "type", Value: "integer"}, {Key: "minimum", Value: 1}, {Key: "maximum", Value: treasuryShareDenominator}}},
+}}}}
+
+// schemaOverrides are types with custom JSON encoding, their schema can't be derived from the Go type
+var schemaOverrides = map[reflect.Type]orderedObject{
+	reflect.TypeOf(common.Address{}):       addressSchema,
+	reflect.TypeOf(common.Hash{}):          hashSchema,
+	reflect.TypeOf(hexutil.Bytes{}):        bytesSchema,
+	reflect.TypeOf(hexutil.Big{}):          quantitySchema,
+	reflect.TypeOf(hexutil.Uint64(0)):      quantitySchema,
+	reflect.TypeOf(math.HexOrDecimal256{}): quantitySchema,
+	reflect.TypeOf(math.HexOrDecimal64(0)): quantitySchema,
+	reflect.TypeOf(amountValue{}):          amountSchema,
+	reflect.TypeOf(json.RawMessage{}):      {},
+	reflect.TypeOf(treasuryConfig{}):      treasurySchema,
+}
+
+// validator is either a bare address or an object, schema of the object is derived from its fields
+func init() {
+	type plainValidatorConfig validatorConfig
+	schemaOverrides[reflect.TypeOf(validatorConfig{})] = orderedObject{
+		{Key: "oneOf", Value: []interface{}{addressSchema, typeSchema(reflect.TypeOf(plainValidatorConfig{}))}},
+	}
+}
+
+var (
+	jsonUnmarshalerType = reflect.TypeOf((*json.Unmarshaler)(nil)).Elem()
+	textUnmarshalerType = reflect.TypeOf((*encoding.TextUnmarshaler)(nil)).Elem()
+)
+
+// typeSchema derives JSON schema of the type the way encoding/json decodes it
+func typeSchema(t reflect.Type) orderedObject {
+	for t.Kind() == reflect.Ptr {
+		t = t.Elem()
+	}
+	if schema, ok := schemaOverrides[t]; ok {
+		return schema
+	}
+	pointer := reflect.PtrTo(t)
+	if pointer.Implements(jsonUnmarshalerType) {
+		return orderedObject{}
+	}
+	if pointer.Implements(textUnmarshalerType) {
+		return orderedObject{{Key: "type", Value: "string"}}
+	}
+	switch t.Kind() {
+	case reflect.Bool:
+		return orderedObject{{Key: "type", Value: "boolean"}}
+	case reflect.Int, reflect.Int8, reflect.Int16, reflect.Int32, reflect.Int64:
+		return orderedObject{{Key: "type", Value: "integer"}}
+	case reflect.Uint, reflect.Uint8, reflect.Uint16, reflect.Uint32, reflect.Uint64:
+		return orderedObject{{Key: "type", Value: "integer"}, {Key: "minimum", Value: 0}}
+	case reflect.Float32, reflect.Float64:
+		return orderedObject{{Key: "type", Value: "number"}}
+	case reflect.String:
+		return orderedObject{{Key: "type", Value: "string"}}
+	case reflect.Slice, reflect.Array:
+		return orderedObject{{Key: "type", Value: "array"}, {Key: "items", Value: typeSchema(t.Elem())}}
+	case reflect.Map:
+		schema := orderedObject{{Key: "type", Value: "object"}}
+		if t.Key() == reflect.TypeOf(common.Address{}) {
+			schema = append(schema, orderedField{Key: "propertyNames", Value: addressSchema})
+		}
+		return append(schema, orderedField{Key: "additionalProperties", Value: typeSchema(t.Elem())})
+	case reflect.Struct:
+		properties := orderedObject{}
+		collectSchemaProperties(t, &properties)
+		// unknown fields are ignored by the generator, so typos would silently fall back to defaults
+		return orderedObject{{Key: "type", Value: "object"}, {Key: "properties", Value: properties}, {Key: "additionalProperties", Value: false}}
+	}
+	return orderedObject{}
+}
+
+// collectSchemaProperties adds JSON fields of the struct, fields of embedded structs are promoted
+func collectSchemaProperties(t reflect.Type, properties *orderedObject) {
+	for i := 0; i < t.NumField(); i++ {
+		field := t.Field(i)
+		name := strings.Split(field.Tag.Get("json"), ",")[0]
+		if name == "-" {
+			continue
+		}
+		if field.Anonymous && name == "" {
+			collectSchemaProperties(field.Type, properties)
+			continue
+		}
+		if field.PkgPath != "" {
+			continue
+		}
+		if name == "" {
+			name = field.Name
+		}
+		*properties = append(*properties, orderedField{Key: name, Value: typeSchema(field.Type)})
+	}
+}
+
+// genesisConfigSchema returns JSON schema (draft-07) of the genesis config file
+func genesisConfigSchema() orderedObject {
+	schema := orderedObject{
+		{Key: "$schema", Value: "http://json-schema.org/draft-07/schema#"},
+		{Key: "title", Value: "BAS genesis config"},
+	}
+	schema = append(schema, typeSchema(reflect.TypeOf(genesisConfig{}))...)
+	// block and epoch counts can be written as durations, they are converted by resolveConfigDurations
+	durationSchema := orderedObject{{Key: "type", Value: "string"}, {Key: "description", Value: "duration, e.g. \"7 days\", \"12 hours\" or \"90m\""}}
+	allowDuration := func(field *orderedField) {
+		field.Value = orderedObject{{Key: "oneOf", Value: []interface{}{field.Value, durationSchema}}}
+	}
+	properties := schemaField(schema, "properties").Value.(orderedObject)
+	allowDuration(schemaField(properties, "votingPeriod"))
+	params := schemaField(schemaField(properties, "consensusParams").Value.(orderedObject), "properties").Value.(orderedObject)
+	for _, name := range []string{"epochBlockInterval", "validatorJailEpochLength", "undelegatePeriod"} {
+		allowDuration(schemaField(params, name))
+	}
+	return schema
+}
+
+// schemaField returns field of the schema object by key, it panics if there is no such field (schema is static)
+func schemaField(schema orderedObject, key string) *orderedField {
+	for i := range schema {
+		if schema[i].Key == key {
+			return &schema[i]
+		}
+	}
+	panic(fmt.Sprintf("there is no %s in schema", key))
+}
+
+// runGenSchemaCommand prints JSON schema of the config, editors use it for completion and CI for validation
+func runGenSchemaCommand(args []string) error {
+	flags := flag.NewFlagSet("gen schema", flag.ContinueOnError)
+	outputFile := flags.String("output", "stdout", "output file")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen schema [--output <file>]")
+	}
+	data, err := encodeJson(genesisConfigSchema())
+	if err != nil {
+		return err
+	}
+	return writeOutput(*outputFile, append(data, '\n'))
+}
@@ -0,0 +1,44 @@
+package main
+
+import (
+	"encoding/json"
+	"testing"
+)
+
+func TestGenesisConfigSchema(t *testing.T) {
+	data, err := encodeJson(genesisConfigSchema())
+	if err != nil {
+		t.Fatal(err)
+	}
+	var schema struct {
+		Properties map[string]json.RawMessage `json:"properties"`
+	}
+	if err := json.Unmarshal(data, &schema); err != nil {
+		t.Fatal(err)
+	}
+	for _, name := range []string{"version", "chainId", "validators", "faucet", "extraContracts", "tokens"} {
+		if _, ok := schema.Properties[name]; !ok {
+			t.Fatalf("property %s is missing in schema", name)
+		}
+	}
+	var validators struct {
+		Items struct {
+			OneOf []json.RawMessage `json:"oneOf"`
+		} `json:"items"`
+	}
+	if err := json.Unmarshal(schema.Properties["validators"], &validators); err != nil || len(validators.Items.OneOf) != 2 {
+		t.Fatalf("validator must be address or object: %s", schema.Properties["validators"])
+	}
+	// fields of the embedded extra contract config are promoted into token properties
+	var tokens struct {
+		Items struct {
+			Properties map[string]json.RawMessage `json:"properties"`
+		} `json:"items"`
+	}
+	if err := json.Unmarshal(schema.Properties["tokens"], &tokens); err != nil {
+		t.Fatal(err)
+	}
+	if _, ok := tokens.Items.Properties["artifact"]; !ok {
+		t.Fatalf("embedded fields are missing in token schema: %s", schema.Properties["tokens"])
+	}
+}
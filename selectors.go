@@ -0,0 +1,69 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core/vm"
+)
+
+// pushedConstants returns immediate values of all PUSH1-PUSH4 instructions of the code, solc's dispatcher
+// compares calldata selector with PUSH4 constants (or shorter pushes if selector has leading zero bytes)
+func pushedConstants(code []byte) map[uint32]bool {
+	result := make(map[uint32]bool)
+	for pc := 0; pc < len(code); pc++ {
+		op := vm.OpCode(code[pc])
+		if op < vm.PUSH1 || op > vm.PUSH32 {
+			continue
+		}
+		size := int(op-vm.PUSH1) + 1
+		if size <= 4 && pc+size < len(code) {
+			var value uint32
+			for _, b := range code[pc+1 : pc+1+size] {
+				value = value<<8 | uint32(b)
+			}
+			result[value] = true
+		}
+		pc += size
+	}
+	return result
+}
+
+// verifyAbiSelectors makes sure every function of the artifact's ABI is dispatched by its deployed bytecode,
+// artifacts with ABI and bytecode from different builds encode ctor params that the code can't decode
+func verifyAbiSelectors(rawArtifact []byte) error {
+	artifact := &artifactData{}
+	if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+		return err
+	}
+	// artifacts without deployed bytecode (e.g. hex files of extra contracts) have nothing to compare with
+	if artifact.DeployedBytecode == "" || artifact.DeployedBytecode == "0x" {
+		return nil
+	}
+	code, err := hexutil.Decode(artifact.DeployedBytecode)
+	if err != nil {
+		return fmt.Errorf("invalid deployed bytecode of %s: %w", artifact.ContractName, err)
+	}
+	contractAbi, err := abi.JSON(bytes.NewReader(artifact.Abi))
+	if err != nil {
+		return fmt.Errorf("failed to parse ABI of %s: %w", artifact.ContractName, err)
+	}
+	constants := pushedConstants(code)
+	var missing []string
+	for _, method := range contractAbi.Methods {
+		selector := uint32(method.ID[0])<<24 | uint32(method.ID[1])<<16 | uint32(method.ID[2])<<8 | uint32(method.ID[3])
+		if !constants[selector] {
+			missing = append(missing, method.Sig)
+		}
+	}
+	if len(missing) > 0 {
+		sort.Strings(missing)
+		return fmt.Errorf("ABI of %s is out of sync with its bytecode, selectors of %s are not found in deployed code", artifact.ContractName, strings.Join(missing, ", "))
+	}
+	return nil
+}
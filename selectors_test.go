@@ -0,0 +1,43 @@
+package main
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+)
+
+func TestPushedConstants(t *testing.T) {
+	// PUSH4 0x12345678, PUSH32 with 0xcafebabe inside its data, PUSH3 0x00abcd (selector with leading zero byte)
+	code := append([]byte{0x63, 0x12, 0x34, 0x56, 0x78, 0x7f}, make([]byte, 32)...)
+	copy(code[6:], []byte{0xca, 0xfe, 0xba, 0xbe})
+	code = append(code, 0x62, 0x00, 0xab, 0xcd)
+	constants := pushedConstants(code)
+	if !constants[0x12345678] || !constants[0xabcd] {
+		t.Fatalf("pushed constants are not found: %v", constants)
+	}
+	if constants[0xcafebabe] {
+		t.Fatalf("push data is decoded as instructions")
+	}
+}
+
+func TestVerifyAbiSelectors(t *testing.T) {
+	artifacts := defaultArtifactSet()
+	for _, name := range artifacts.names() {
+		if err := verifyAbiSelectors(artifacts[name]); err != nil {
+			t.Fatalf("embedded artifact %s: %s", name, err)
+		}
+	}
+	// ABI of staking with bytecode of another contract
+	staking, systemReward := &artifactData{}, &artifactData{}
+	if err := json.Unmarshal(artifacts["Staking"], staking); err != nil {
+		t.Fatal(err)
+	}
+	if err := json.Unmarshal(artifacts["SystemReward"], systemReward); err != nil {
+		t.Fatal(err)
+	}
+	staking.DeployedBytecode = systemReward.DeployedBytecode
+	mixed, _ := json.Marshal(staking)
+	if err := verifyAbiSelectors(mixed); err == nil || !strings.Contains(err.Error(), "out of sync") {
+		t.Fatalf("expected out of sync error, got %v", err)
+	}
+}
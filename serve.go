@@ -0,0 +1,177 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"net/http"
+	"runtime"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// genesisService generates genesis for configs submitted over HTTP, simulation is CPU bound, so the number of
+// concurrent generations is limited and requests above the limit are rejected instead of queued
+type genesisService struct {
+	slots       chan struct{}
+	maxBodySize int64
+	timeout     time.Duration
+}
+
+// generateResponse is the body of successful POST /v1/genesis
+type generateResponse struct {
+	Genesis json.RawMessage `json:"genesis"`
+	Report  json.RawMessage `json:"report"`
+}
+
+// handleResult is the outcome of handling submitted config
+type handleResult struct {
+	value interface{}
+	err   error
+}
+
+func newGenesisService(maxConcurrent int, maxBodySize int64, timeout time.Duration) *genesisService {
+	return &genesisService{slots: make(chan struct{}, maxConcurrent), maxBodySize: maxBodySize, timeout: timeout}
+}
+
+func (s *genesisService) handler() http.Handler {
+	mux := http.NewServeMux()
+	mux.HandleFunc("/v1/genesis", s.post(func(configJson []byte) (interface{}, error) {
+		genesis, report, err := generateGenesisWithReport(configJson)
+		if err != nil {
+			return nil, err
+		}
+		return &generateResponse{Genesis: genesis, Report: report}, nil
+	}))
+	mux.HandleFunc("/v1/validate", s.post(func(configJson []byte) (interface{}, error) {
+		config, err := loadConfigJson(configJson)
+		return json.RawMessage(config), err
+	}))
+	mux.HandleFunc("/healthz", func(w http.ResponseWriter, r *http.Request) {
+		w.WriteHeader(http.StatusOK)
+	})
+	return mux
+}
+
+// post wraps handling of submitted config with method, body size, concurrency and deadline checks, generation can't be
+// interrupted, so it keeps its slot until it's finished even if the request has already timed out
+func (s *genesisService) post(handle func(configJson []byte) (interface{}, error)) http.HandlerFunc {
+	return func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			w.Header().Set("Allow", http.MethodPost)
+			writeServiceError(w, http.StatusMethodNotAllowed, fmt.Errorf("method %s isn't allowed", r.Method))
+			return
+		}
+		configJson, err := io.ReadAll(http.MaxBytesReader(w, r.Body, s.maxBodySize))
+		if isBodyTooLarge(err) {
+			writeServiceError(w, http.StatusRequestEntityTooLarge, fmt.Errorf("config exceeds %d bytes", s.maxBodySize))
+			return
+		} else if err != nil {
+			writeServiceError(w, http.StatusBadRequest, fmt.Errorf("failed to read config: %w", err))
+			return
+		}
+		select {
+		case s.slots <- struct{}{}:
+		default:
+			w.Header().Set("Retry-After", "5")
+			writeServiceError(w, http.StatusServiceUnavailable, fmt.Errorf("all %d generation slots are busy", cap(s.slots)))
+			return
+		}
+		ctx, cancel := context.WithTimeout(r.Context(), s.timeout)
+		defer cancel()
+		started := time.Now()
+		done := make(chan handleResult, 1)
+		go func() {
+			defer func() { <-s.slots }()
+			value, err := handle(configJson)
+			done <- handleResult{value: value, err: err}
+		}()
+		var result handleResult
+		select {
+		case result = <-done:
+		case <-ctx.Done():
+			log.Warn("Request abandoned", "path", r.URL.Path, "remote", r.RemoteAddr, "elapsed", time.Since(started), "err", ctx.Err())
+			if errors.Is(ctx.Err(), context.DeadlineExceeded) {
+				writeServiceError(w, http.StatusGatewayTimeout, fmt.Errorf("generation exceeds %s", s.timeout))
+			}
+			return
+		}
+		if result.err != nil {
+			log.Info("Request failed", "path", r.URL.Path, "remote", r.RemoteAddr, "elapsed", time.Since(started), "err", result.err)
+			writeServiceError(w, serviceErrorStatus(result.err), result.err)
+			return
+		}
+		log.Info("Request served", "path", r.URL.Path, "remote", r.RemoteAddr, "elapsed", time.Since(started))
+		writeServiceJson(w, http.StatusOK, result.value)
+	}
+}
+
+// isBodyTooLarge reports whether reading failed on the limit of http.MaxBytesReader, *http.MaxBytesError isn't
+// available before go 1.19, so the error is matched by its message
+func isBodyTooLarge(err error) bool {
+	return err != nil && strings.Contains(err.Error(), "request body too large")
+}
+
+// serviceErrorStatus maps error categories of exit codes to HTTP statuses
+func serviceErrorStatus(err error) int {
+	switch errorExitCode(err) {
+	case exitCodeConfig:
+		return http.StatusBadRequest
+	case exitCodeSimulation:
+		return http.StatusUnprocessableEntity
+	}
+	return http.StatusInternalServerError
+}
+
+// writeServiceError writes the error object of --error-format json
+func writeServiceError(w http.ResponseWriter, status int, err error) {
+	writeServiceJson(w, status, newErrorOutput(err))
+}
+
+func writeServiceJson(w http.ResponseWriter, status int, value interface{}) {
+	data, err := json.Marshal(value)
+	if err != nil {
+		status, data = http.StatusInternalServerError, []byte(`{"message": "failed to encode response"}`)
+	}
+	w.Header().Set("Content-Type", "application/json")
+	w.Header().Set("Content-Length", strconv.Itoa(len(data)))
+	w.WriteHeader(status)
+	_, _ = w.Write(data)
+}
+
+func runGenServeCommand(args []string) error {
+	flags := flag.NewFlagSet("gen serve", flag.ContinueOnError)
+	listen := flags.String("listen", "127.0.0.1:8080", "address to listen on")
+	maxConcurrent := flags.Int("max-concurrent", runtime.NumCPU(), "max number of genesis generated at the same time, requests above are rejected with 503")
+	maxBodySize := flags.Int64("max-body-size", 1<<20, "max size of submitted config in bytes")
+	timeout := flags.Duration("timeout", 5*time.Minute, "max time to generate genesis for a request, slower requests are answered with 504")
+	applyLoggingFlags := registerLoggingFlags(flags)
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if err := applyLoggingFlags(); err != nil {
+		return err
+	}
+	if flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen serve [--listen <host:port>] [--max-concurrent <n>] [--max-body-size <bytes>] [--timeout <duration>]")
+	}
+	if *maxConcurrent < 1 || *maxBodySize < 1 || *timeout <= 0 {
+		return fmt.Errorf("--max-concurrent, --max-body-size and --timeout must be positive")
+	}
+	server := &http.Server{
+		Addr:              *listen,
+		Handler:           newGenesisService(*maxConcurrent, *maxBodySize, *timeout).handler(),
+		ReadHeaderTimeout: 10 * time.Second,
+	}
+	log.Info("Serving genesis generation", "listen", *listen, "maxConcurrent", *maxConcurrent)
+	if err := server.ListenAndServe(); !errors.Is(err, http.ErrServerClosed) {
+		return err
+	}
+	return nil
+}
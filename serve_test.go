@@ -0,0 +1,148 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"testing/iotest"
+	"time"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestGenesisService(t *testing.T) {
+	service := newGenesisService(1, 1<<20, time.Minute)
+	server := httptest.NewServer(service.handler())
+	defer server.Close()
+	configJson, err := json.Marshal(devNetConfig)
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp, err := http.Post(server.URL+"/v1/genesis", "application/json", bytes.NewReader(configJson))
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer resp.Body.Close()
+	result := &generateResponse{}
+	if err := json.NewDecoder(resp.Body).Decode(result); err != nil || resp.StatusCode != http.StatusOK {
+		t.Fatalf("unexpected response %d (%v)", resp.StatusCode, err)
+	}
+	report := &deploymentReport{}
+	if err := json.Unmarshal(result.Report, report); err != nil || report.ChainId != devNetConfig.ChainId || len(result.Genesis) == 0 {
+		t.Fatalf("unexpected report %s (%v)", result.Report, err)
+	}
+	// invalid config is a client error with violations
+	config := devNetConfig
+	config.Safe = &safeConfig{}
+	configJson, _ = json.Marshal(config)
+	resp, err = http.Post(server.URL+"/v1/validate", "application/json", bytes.NewReader(configJson))
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer resp.Body.Close()
+	output := &errorOutput{}
+	if err := json.NewDecoder(resp.Body).Decode(output); err != nil || resp.StatusCode != http.StatusBadRequest || len(output.Violations) == 0 {
+		t.Fatalf("unexpected response %d %+v (%v)", resp.StatusCode, output, err)
+	}
+}
+
+func TestGenesisServiceLimits(t *testing.T) {
+	service := newGenesisService(1, 16, time.Minute)
+	handler := service.handler()
+	recorder := httptest.NewRecorder()
+	handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodGet, "/v1/genesis", nil))
+	if recorder.Code != http.StatusMethodNotAllowed {
+		t.Fatalf("unexpected status %d", recorder.Code)
+	}
+	recorder = httptest.NewRecorder()
+	handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodPost, "/v1/validate", bytes.NewReader(make([]byte, 17))))
+	if recorder.Code != http.StatusRequestEntityTooLarge {
+		t.Fatalf("unexpected status %d", recorder.Code)
+	}
+	// failed read isn't a size problem
+	recorder = httptest.NewRecorder()
+	handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodPost, "/v1/validate", iotest.ErrReader(errors.New("connection reset"))))
+	if recorder.Code != http.StatusBadRequest {
+		t.Fatalf("unexpected status %d", recorder.Code)
+	}
+	// the only slot is taken
+	service.slots <- struct{}{}
+	recorder = httptest.NewRecorder()
+	handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodPost, "/v1/validate", bytes.NewReader([]byte("{}"))))
+	if recorder.Code != http.StatusServiceUnavailable || recorder.Header().Get("Retry-After") == "" {
+		t.Fatalf("unexpected status %d", recorder.Code)
+	}
+}
+
+func TestGenesisServiceRejectsFileReferences(t *testing.T) {
+	handler := newGenesisService(1, 1<<20, time.Minute).handler()
+	for _, test := range []struct {
+		path   string
+		modify func(config *genesisConfig)
+	}{
+		// HOME is set in environment, but it's not the environment of the caller
+		{"config", func(config *genesisConfig) {
+			config.ExtraContracts = []extraContractConfig{{Name: "${HOME}"}}
+		}},
+		{"extraContracts[0].artifact", func(config *genesisConfig) {
+			config.ExtraContracts = []extraContractConfig{{Address: common.HexToAddress("0x0100"), Artifact: "/etc/passwd"}}
+		}},
+		{"extraContracts[0].abi", func(config *genesisConfig) {
+			config.ExtraContracts = []extraContractConfig{{Address: common.HexToAddress("0x0100"), Abi: "/etc/passwd"}}
+		}},
+		{"tokens[0].artifact", func(config *genesisConfig) {
+			config.Tokens = []tokenConfig{{Artifact: "/etc/passwd"}}
+		}},
+		{"validators[0].keystore", func(config *genesisConfig) {
+			config.Validators = append([]validatorConfig{}, config.Validators...)
+			config.Validators[0].Keystore = "/etc/passwd"
+		}},
+	} {
+		config := devNetConfig
+		test.modify(&config)
+		configJson, err := json.Marshal(config)
+		if err != nil {
+			t.Fatal(err)
+		}
+		for _, endpoint := range []string{"/v1/genesis", "/v1/validate"} {
+			recorder := httptest.NewRecorder()
+			handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodPost, endpoint, bytes.NewReader(configJson)))
+			output := &errorOutput{}
+			if err := json.Unmarshal(recorder.Body.Bytes(), output); err != nil || recorder.Code != http.StatusBadRequest || output.Category != "config" {
+				t.Fatalf("%s of %s: unexpected response %d %s (%v)", test.path, endpoint, recorder.Code, recorder.Body, err)
+			}
+			if len(output.Violations) != 1 || output.Violations[0].Path != test.path {
+				t.Fatalf("%s of %s: unexpected violations %+v", test.path, endpoint, output.Violations)
+			}
+		}
+	}
+}
+
+func TestGenesisServiceTimeout(t *testing.T) {
+	service := newGenesisService(1, 1<<20, 10*time.Millisecond)
+	release := make(chan struct{})
+	handler := service.post(func(configJson []byte) (interface{}, error) {
+		<-release
+		return json.RawMessage("{}"), nil
+	})
+	recorder := httptest.NewRecorder()
+	handler.ServeHTTP(recorder, httptest.NewRequest(http.MethodPost, "/v1/genesis", bytes.NewReader([]byte("{}"))))
+	if recorder.Code != http.StatusGatewayTimeout {
+		t.Fatalf("unexpected status %d", recorder.Code)
+	}
+	// abandoned generation holds its slot until it's finished
+	select {
+	case service.slots <- struct{}{}:
+		t.Fatal("slot is released before generation is finished")
+	default:
+	}
+	close(release)
+	select {
+	case service.slots <- struct{}{}:
+	case <-time.After(time.Second):
+		t.Fatal("slot isn't released after generation is finished")
+	}
+}
@@ -0,0 +1,162 @@
+package main
+
+import (
+	"bytes"
+	"compress/gzip"
+	"crypto/ecdsa"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/keystore"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+const genesisSignatureVersion = 1
+
+// genesisSignature is a detached secp256k1 signature of the canonical genesis JSON (sorted keys, compact),
+// so it doesn't depend on indentation or compression of the distributed file
+type genesisSignature struct {
+	Version int            `json:"version"`
+	Signer  common.Address `json:"signer"`
+	// keccak256 of the canonical genesis JSON
+	Hash      common.Hash   `json:"hash"`
+	Signature hexutil.Bytes `json:"signature"`
+}
+
+// loadSigningKey reads hex encoded private key or geth keystore file (password file is required then)
+func loadSigningKey(keyFile, passwordFile string) (*ecdsa.PrivateKey, error) {
+	data, err := os.ReadFile(keyFile)
+	if err != nil {
+		return nil, err
+	}
+	if !json.Valid(data) {
+		return crypto.LoadECDSA(keyFile)
+	}
+	if passwordFile == "" {
+		return nil, fmt.Errorf("%s is a keystore file, pass its password with --sign-password", keyFile)
+	}
+	passwords, err := readPasswords(passwordFile, 1)
+	if err != nil {
+		return nil, err
+	}
+	key, err := keystore.DecryptKey(data, passwords[0])
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt %s: %w", keyFile, err)
+	}
+	return key.PrivateKey, nil
+}
+
+// canonicalGenesisHash returns keccak256 of the canonical JSON of the genesis file (gzip compressed if it has .gz suffix)
+func canonicalGenesisHash(genesisFile string) (common.Hash, error) {
+	data, err := os.ReadFile(genesisFile)
+	if err != nil {
+		return common.Hash{}, err
+	}
+	if strings.HasSuffix(genesisFile, ".gz") {
+		reader, err := gzip.NewReader(bytes.NewReader(data))
+		if err != nil {
+			return common.Hash{}, err
+		}
+		if data, err = io.ReadAll(reader); err != nil {
+			return common.Hash{}, err
+		}
+	}
+	if !json.Valid(data) {
+		return common.Hash{}, fmt.Errorf("%s is not a JSON file, only JSON genesis can be signed", genesisFile)
+	}
+	canonical, err := canonicalJson(json.RawMessage(data))
+	if err != nil {
+		return common.Hash{}, err
+	}
+	return crypto.Keccak256Hash(canonical), nil
+}
+
+// signGenesis writes detached signature of the genesis file to <genesis>.sig
+func signGenesis(genesisFile string, key *ecdsa.PrivateKey) error {
+	hash, err := canonicalGenesisHash(genesisFile)
+	if err != nil {
+		return err
+	}
+	signature, err := crypto.Sign(hash[:], key)
+	if err != nil {
+		return err
+	}
+	result, _ := json.MarshalIndent(&genesisSignature{
+		Version:   genesisSignatureVersion,
+		Signer:    crypto.PubkeyToAddress(key.PublicKey),
+		Hash:      hash,
+		Signature: signature,
+	}, "", "  ")
+	return os.WriteFile(genesisFile+".sig", append(result, '\n'), 0644)
+}
+
+// verifyGenesisSignature checks detached signature of the genesis file, the signer must be the expected one (if set)
+func verifyGenesisSignature(genesisFile, signatureFile string, expectedSigner *common.Address) (common.Address, error) {
+	data, err := os.ReadFile(signatureFile)
+	if err != nil {
+		return common.Address{}, err
+	}
+	signature := &genesisSignature{}
+	if err := json.Unmarshal(data, signature); err != nil {
+		return common.Address{}, fmt.Errorf("failed to parse signature %s: %w", signatureFile, err)
+	}
+	if signature.Version != genesisSignatureVersion {
+		return common.Address{}, fmt.Errorf("unsupported signature version %d in %s", signature.Version, signatureFile)
+	}
+	hash, err := canonicalGenesisHash(genesisFile)
+	if err != nil {
+		return common.Address{}, err
+	}
+	if hash != signature.Hash {
+		return common.Address{}, fmt.Errorf("genesis hash %s doesn't match signed hash %s", hash.Hex(), signature.Hash.Hex())
+	}
+	publicKey, err := crypto.SigToPub(hash[:], signature.Signature)
+	if err != nil {
+		return common.Address{}, fmt.Errorf("invalid signature: %w", err)
+	}
+	signer := crypto.PubkeyToAddress(*publicKey)
+	if signer != signature.Signer {
+		return common.Address{}, fmt.Errorf("signature is made by %s, not by declared signer %s", signer.Hex(), signature.Signer.Hex())
+	}
+	if expectedSigner != nil && signer != *expectedSigner {
+		return common.Address{}, fmt.Errorf("genesis is signed by %s, expected %s", signer.Hex(), expectedSigner.Hex())
+	}
+	return signer, nil
+}
+
+// runGenVerifyCommand checks detached signature of the distributed genesis before launching a node
+func runGenVerifyCommand(args []string) error {
+	flags := flag.NewFlagSet("gen verify", flag.ContinueOnError)
+	signatureFile := flags.String("signature", "", "detached signature file (default is <genesis>.sig)")
+	signer := flags.String("signer", "", "address the genesis must be signed by")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if flags.NArg() != 1 {
+		return fmt.Errorf("usage: gen verify [--signature <file>] [--signer <address>] <genesis.json>")
+	}
+	genesisFile := flags.Arg(0)
+	if *signatureFile == "" {
+		*signatureFile = genesisFile + ".sig"
+	}
+	var expectedSigner *common.Address
+	if *signer != "" {
+		if !common.IsHexAddress(*signer) {
+			return fmt.Errorf("invalid signer address: %s", *signer)
+		}
+		address := common.HexToAddress(*signer)
+		expectedSigner = &address
+	}
+	actualSigner, err := verifyGenesisSignature(genesisFile, *signatureFile, expectedSigner)
+	if err != nil {
+		return err
+	}
+	fmt.Printf("%s is signed by %s\n", genesisFile, actualSigner.Hex())
+	return nil
+}
@@ -0,0 +1,37 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+func TestGenesisSignature(t *testing.T) {
+	key, err := crypto.GenerateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	genesisFile := filepath.Join(t.TempDir(), "genesis.json")
+	if err := os.WriteFile(genesisFile, []byte(`{"config": {"chainId": 14000}, "alloc": {}}`), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := signGenesis(genesisFile, key); err != nil {
+		t.Fatal(err)
+	}
+	// signature covers canonical JSON, so re-indented genesis is still valid
+	if err := os.WriteFile(genesisFile, []byte("{\n  \"alloc\": {},\n  \"config\": {\"chainId\": 14000}\n}"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	signer := crypto.PubkeyToAddress(key.PublicKey)
+	if actual, err := verifyGenesisSignature(genesisFile, genesisFile+".sig", &signer); err != nil || actual != signer {
+		t.Fatalf("valid signature is rejected: %v", err)
+	}
+	if err := os.WriteFile(genesisFile, []byte(`{"config": {"chainId": 14001}, "alloc": {}}`), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := verifyGenesisSignature(genesisFile, genesisFile+".sig", nil); err == nil {
+		t.Fatal("signature of modified genesis is accepted")
+	}
+}
@@ -0,0 +1,198 @@
+package main
+
+import (
+	"crypto/sha256"
+	"encoding/json"
+	"errors"
+	"io/fs"
+	"math/big"
+	"os"
+	"path/filepath"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/log"
+	"github.com/ethereum/go-ethereum/params"
+)
+
+// simulationCacheVersion must be increased when simulation starts producing different results for the same inputs
+const simulationCacheVersion = 6
+
+// simulationCache keeps accounts produced by system contract constructors in a directory, so repeated runs
+// with unchanged artifacts and params skip constructor execution (init functions are always executed, next
+// contracts rely on their state)
+type simulationCache struct {
+	dir string
+}
+
+// simulationCacheKey is every input that affects constructor execution, the hash of it is the cache key
+type simulationCacheKey struct {
+	Version       int                 `json:"version"`
+	Artifact      common.Hash         `json:"artifact"`
+	Constructor   hexutil.Bytes       `json:"constructor"`
+	Balance       *big.Int            `json:"balance"`
+	FollowUpCalls []cachedFollowUp    `json:"followUpCalls"`
+	ChainConfig   *params.ChainConfig `json:"chainConfig"`
+	BlockNumber   *big.Int            `json:"blockNumber"`
+	Time          *big.Int            `json:"time"`
+	GasLimit      uint64              `json:"gasLimit"`
+	Coinbase      common.Address      `json:"coinbase"`
+	Difficulty    *big.Int            `json:"difficulty"`
+	// alloc the constructor is executed against (already deployed contracts)
+	State core.GenesisAlloc `json:"state"`
+	// gas given to the constructor if it's not the default one (metered gas)
+	GasBudget uint64 `json:"gasBudget,omitempty"`
+	// precompile set the constructor is executed with (custom precompile params included)
+	Precompiles *precompileConfig `json:"precompiles,omitempty"`
+}
+
+type cachedFollowUp struct {
+	From  common.Address `json:"from"`
+	Input hexutil.Bytes  `json:"input"`
+	Value *big.Int       `json:"value"`
+}
+
+// cachedDeployment is the result of constructor and follow-up calls: gas used by ctor, modified and
+// self-destructed accounts
+type cachedDeployment struct {
+	GasUsed         uint64            `json:"gasUsed"`
+	Accounts        core.GenesisAlloc `json:"accounts"`
+	Deleted         []common.Address  `json:"deleted,omitempty"`
+	PrecompileCalls []precompileCall  `json:"precompileCalls,omitempty"`
+	Logs            []simulatedLog    `json:"logs,omitempty"`
+	Nonce           uint64            `json:"nonce"`
+}
+
+func newSimulationCache(dir string) *simulationCache {
+	if dir == "" {
+		return nil
+	}
+	return &simulationCache{dir: dir}
+}
+
+func (c *simulationCache) key(simulator *systemContractSimulator, deployment *systemContractDeployment, rawArtifact, ctor []byte) (string, error) {
+	var calls []cachedFollowUp
+	for _, call := range deployment.followUpCalls {
+		calls = append(calls, cachedFollowUp{From: call.from, Input: call.input, Value: call.value})
+	}
+	blockContext := simulator.evm.Context
+	var gasBudget uint64
+	if simulator.gasLimit != defaultSimulationGas {
+		gasBudget = simulator.gasLimit
+	}
+	data, err := json.Marshal(simulationCacheKey{
+		Version:       simulationCacheVersion,
+		Artifact:      sha256.Sum256(rawArtifact),
+		Constructor:   ctor,
+		Balance:       deployment.initialBalance(),
+		FollowUpCalls: calls,
+		ChainConfig:   simulator.genesis.Config,
+		BlockNumber:   blockContext.BlockNumber,
+		Time:          blockContext.Time,
+		GasLimit:      blockContext.GasLimit,
+		Coinbase:      blockContext.Coinbase,
+		Difficulty:    blockContext.Difficulty,
+		State:         simulator.genesis.Alloc,
+		GasBudget:     gasBudget,
+		Precompiles:   simulator.precompiles,
+	})
+	if err != nil {
+		return "", err
+	}
+	hash := sha256.Sum256(data)
+	return common.Bytes2Hex(hash[:]), nil
+}
+
+func (c *simulationCache) path(key string) string {
+	return filepath.Join(c.dir, key+".json")
+}
+
+func (c *simulationCache) load(key string) *cachedDeployment {
+	data, err := os.ReadFile(c.path(key))
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil
+	} else if err != nil {
+		log.Warn("Failed to read simulation cache", "key", key, "err", err)
+		return nil
+	}
+	result := &cachedDeployment{}
+	if err := json.Unmarshal(data, result); err != nil {
+		log.Warn("Ignoring broken simulation cache entry", "key", key, "err", err)
+		return nil
+	}
+	return result
+}
+
+// store writes the entry through a temporary file, so concurrent runs never see partially written entries
+func (c *simulationCache) store(key string, entry *cachedDeployment) error {
+	data, err := json.Marshal(entry)
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(c.dir, 0755); err != nil {
+		return err
+	}
+	file, err := os.CreateTemp(c.dir, key+".*.tmp")
+	if err != nil {
+		return err
+	}
+	defer os.Remove(file.Name())
+	if _, err := file.Write(data); err != nil {
+		file.Close()
+		return err
+	}
+	if err := file.Close(); err != nil {
+		return err
+	}
+	return os.Rename(file.Name(), c.path(key))
+}
+
+// invokeConstructor executes the constructor or applies its cached result, nil cache always executes it
+func (c *simulationCache) invokeConstructor(simulator *systemContractSimulator, deployment *systemContractDeployment, rawArtifact []byte) (*simulationResult, error) {
+	if c == nil {
+		return invokeConstructor(simulator, deployment, rawArtifact)
+	}
+	ctor, err := encodeConstructor(deployment, rawArtifact)
+	if err != nil {
+		return nil, err
+	}
+	key, err := c.key(simulator, deployment, rawArtifact, ctor)
+	if err != nil {
+		return nil, err
+	}
+	if entry := c.load(key); entry != nil {
+		log.Debug("Using cached simulation", "contract", deployment.name, "key", key)
+		simulator.statedb.Finalise(false)
+		simulator.applyAllocChanges(&allocChangeSet{accounts: entry.Accounts, deleted: entry.Deleted})
+		artifact := &artifactData{}
+		if err := json.Unmarshal(rawArtifact, artifact); err != nil {
+			return nil, err
+		}
+		return &simulationResult{
+			GasUsed:         entry.GasUsed,
+			PrecompileCalls: entry.PrecompileCalls,
+			Logs:            entry.Logs,
+			Nonce:           entry.Nonce,
+			contractAbi:     parseArtifactAbi(artifact),
+		}, nil
+	}
+	before := cloneAlloc(simulator.genesis.Alloc)
+	result, err := simulator.deploySystemContract(deployment.address, rawArtifact, ctor, deployment.initialBalance(), deployment.followUpCalls)
+	if err != nil {
+		return nil, err
+	}
+	changes := simulator.allocChanges(before)
+	entry := &cachedDeployment{
+		GasUsed:         result.GasUsed,
+		Accounts:        changes.accounts,
+		Deleted:         changes.deleted,
+		PrecompileCalls: result.PrecompileCalls,
+		Logs:            result.Logs,
+		Nonce:           result.Nonce,
+	}
+	if err := c.store(key, entry); err != nil {
+		log.Warn("Failed to write simulation cache", "key", key, "err", err)
+	}
+	return result, nil
+}
@@ -0,0 +1,64 @@
+package main
+
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestSimulationCacheKeepsGenesis(t *testing.T) {
+	cacheDir := t.TempDir()
+	var outputs [][]byte
+	for _, dir := range []string{"", cacheDir, cacheDir} {
+		options := defaultGenerateOptions()
+		options.CacheDir = dir
+		outputFile := filepath.Join(t.TempDir(), "genesis.json")
+		if err := createGenesisConfig(devNetConfig, outputFile, options); err != nil {
+			t.Fatalf("failed to generate genesis (cache %q): %s", dir, err)
+		}
+		output, err := os.ReadFile(outputFile)
+		if err != nil {
+			t.Fatal(err)
+		}
+		outputs = append(outputs, output)
+	}
+	entries, err := os.ReadDir(cacheDir)
+	if err != nil || len(entries) == 0 {
+		t.Fatalf("cache is not populated: %v", err)
+	}
+	for i := 1; i < len(outputs); i++ {
+		if !bytes.Equal(outputs[0], outputs[i]) {
+			t.Fatalf("cached simulation changes genesis (run %d):\n%s", i, firstDifference(outputs[0], outputs[i]))
+		}
+	}
+}
+
+func TestSimulationCacheKeyIncludesPrecompiles(t *testing.T) {
+	simulator, err := newSystemContractSimulator(defaultGenesisConfig(devNetConfig.ChainId))
+	if err != nil {
+		t.Fatal(err)
+	}
+	cache := newSimulationCache(t.TempDir())
+	deployment := &systemContractDeployment{name: "Test"}
+	var keys []string
+	for _, precompiles := range []*precompileConfig{
+		nil,
+		{Custom: []customPrecompileConfig{{Address: common.HexToAddress("0x0100"), Plugin: "static", Params: []byte(`{"output":"0x01"}`)}}},
+		{Custom: []customPrecompileConfig{{Address: common.HexToAddress("0x0100"), Plugin: "static", Params: []byte(`{"output":"0x02"}`)}}},
+	} {
+		simulator.precompiles = precompiles
+		key, err := cache.key(simulator, deployment, []byte("artifact"), []byte{0x60, 0x00})
+		if err != nil {
+			t.Fatal(err)
+		}
+		for i, previous := range keys {
+			if previous == key {
+				t.Fatalf("precompile config %d has the same cache key as config %d", len(keys), i)
+			}
+		}
+		keys = append(keys, key)
+	}
+}
@@ -0,0 +1,57 @@
+package main
+
+import (
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestRecreatedAccountResetsStorage(t *testing.T) {
+	address := common.HexToAddress("0x0000000000000000000000000000000000abcdef")
+	genesis := defaultGenesisConfig(14000)
+	genesis.Alloc = core.GenesisAlloc{
+		address: {
+			Balance: big.NewInt(1),
+			Code:    []byte{0x60, 0x00},
+			Storage: map[common.Hash]common.Hash{common.HexToHash("0x01"): common.HexToHash("0x01")},
+		},
+	}
+	simulator, err := newSystemContractSimulator(genesis)
+	if err != nil {
+		t.Fatal(err)
+	}
+	simulator.statedb.Finalise(false)
+	before := simulator.snapshotAccounts()
+	// the same as selfdestruct followed by CREATE2 at the address
+	simulator.statedb.CreateAccount(address)
+	simulator.statedb.SetState(address, common.HexToHash("0x02"), common.HexToHash("0x02"))
+	simulator.mergeAccountChanges(address, readStateObjects(simulator.statedb)[address], before[address])
+	account := genesis.Alloc[address]
+	if len(account.Storage) != 1 || account.Storage[common.HexToHash("0x02")] != common.HexToHash("0x02") {
+		t.Fatalf("storage of recreated account isn't reset: %v", account.Storage)
+	}
+	if len(account.Code) != 0 || account.Balance.Int64() != 1 {
+		t.Fatalf("unexpected recreated account: %+v", account)
+	}
+}
+
+func TestSelfDestructedAccountIsRemoved(t *testing.T) {
+	address := common.HexToAddress("0x0000000000000000000000000000000000abcdef")
+	genesis := defaultGenesisConfig(14000)
+	genesis.Alloc = core.GenesisAlloc{
+		address: {Balance: big.NewInt(0), Code: []byte{0x60, 0x00}},
+	}
+	simulator, err := newSystemContractSimulator(genesis)
+	if err != nil {
+		t.Fatal(err)
+	}
+	simulator.statedb.Finalise(false)
+	before := simulator.snapshotAccounts()
+	simulator.statedb.Suicide(address)
+	simulator.mergeAccountChanges(address, readStateObjects(simulator.statedb)[address], before[address])
+	if _, ok := genesis.Alloc[address]; ok {
+		t.Fatal("self-destructed account is kept in genesis")
+	}
+}
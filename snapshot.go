@@ -0,0 +1,162 @@
+//go:build snapshot
+// +build snapshot
+
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"encoding/json"
+	"flag"
+	"fmt"
+	"math/big"
+	"os"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/ethclient"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// snapshotRequestTimeout limits every JSON-RPC request, public endpoints tend to hang instead of failing
+const snapshotRequestTimeout = 30 * time.Second
+
+// readSnapshotAddresses reads one address per line, empty lines and # comments are skipped
+func readSnapshotAddresses(path string) ([]common.Address, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+	var result []common.Address
+	seen := make(map[common.Address]bool)
+	scanner := bufio.NewScanner(file)
+	for line := 1; scanner.Scan(); line++ {
+		text := strings.TrimSpace(scanner.Text())
+		if text == "" || strings.HasPrefix(text, "#") {
+			continue
+		}
+		if !common.IsHexAddress(text) {
+			return nil, fmt.Errorf("%s:%d: invalid address %q", path, line, text)
+		}
+		address := common.HexToAddress(text)
+		if seen[address] {
+			continue
+		}
+		seen[address] = true
+		result = append(result, address)
+	}
+	return result, scanner.Err()
+}
+
+// parseStorageSlots parses comma separated <address>:<slot> pairs
+func parseStorageSlots(value string) (map[common.Address][]common.Hash, error) {
+	result := make(map[common.Address][]common.Hash)
+	if value == "" {
+		return result, nil
+	}
+	for _, item := range strings.Split(value, ",") {
+		parts := strings.SplitN(strings.TrimSpace(item), ":", 2)
+		if len(parts) != 2 || !common.IsHexAddress(parts[0]) {
+			return nil, fmt.Errorf("invalid storage slot %q, expected <address>:<slot>", item)
+		}
+		slot, ok := new(big.Int).SetString(strings.TrimPrefix(parts[1], "0x"), 16)
+		if !ok || slot.BitLen() > 256 {
+			return nil, fmt.Errorf("invalid slot %q of %s", parts[1], parts[0])
+		}
+		address := common.HexToAddress(parts[0])
+		result[address] = append(result[address], common.BigToHash(slot))
+	}
+	return result, nil
+}
+
+// runGenSnapshotCommand pulls balances (and optionally storage slots) of the listed accounts at the block over
+// JSON-RPC, balances are written as airdrop CSV and storage as genesis patch, so they can be passed to gen with
+// --airdrop and --patch
+func runGenSnapshotCommand(args []string) error {
+	flags := flag.NewFlagSet("gen snapshot", flag.ContinueOnError)
+	rpcUrl := flags.String("rpc", "", "JSON-RPC endpoint of the source chain (archive node for old blocks)")
+	blockNumber := flags.Uint64("block", 0, "block number of the snapshot")
+	addressesFile := flags.String("addresses", "", "file with one address per line")
+	storage := flags.String("storage", "", "comma separated <address>:<slot> storage slots to copy")
+	outputFile := flags.String("output", "snapshot-airdrop.csv", "airdrop CSV (address,amount) to pass to gen --airdrop")
+	patchFile := flags.String("storage-output", "snapshot-patch.json", "genesis patch with copied storage to pass to gen --patch")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if *rpcUrl == "" || *addressesFile == "" || flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen snapshot --rpc <url> --block <number> --addresses <file> [--storage <address>:<slot>,...]")
+	}
+	addresses, err := readSnapshotAddresses(*addressesFile)
+	if err != nil {
+		return err
+	}
+	slots, err := parseStorageSlots(*storage)
+	if err != nil {
+		return err
+	}
+	client, err := ethclient.Dial(*rpcUrl)
+	if err != nil {
+		return err
+	}
+	defer client.Close()
+	block := new(big.Int).SetUint64(*blockNumber)
+	phase := startPhase("snapshot")
+	csv := &strings.Builder{}
+	csv.WriteString("address,amount\n")
+	total := big.NewInt(0)
+	for i, address := range addresses {
+		phase.progress(i, len(addresses))
+		ctx, cancel := context.WithTimeout(context.Background(), snapshotRequestTimeout)
+		balance, err := client.BalanceAt(ctx, address, block)
+		cancel()
+		if err != nil {
+			return fmt.Errorf("failed to get balance of %s: %w", address.Hex(), err)
+		}
+		if balance.Sign() == 0 {
+			continue
+		}
+		fmt.Fprintf(csv, "%s,%s\n", address.Hex(), balance)
+		total.Add(total, balance)
+	}
+	if err := os.WriteFile(*outputFile, []byte(csv.String()), 0644); err != nil {
+		return err
+	}
+	if len(slots) > 0 {
+		alloc := orderedObject{}
+		for _, address := range sortedSlotOwners(slots) {
+			values := orderedObject{}
+			for _, slot := range slots[address] {
+				ctx, cancel := context.WithTimeout(context.Background(), snapshotRequestTimeout)
+				value, err := client.StorageAt(ctx, address, slot, block)
+				cancel()
+				if err != nil {
+					return fmt.Errorf("failed to get storage %s of %s: %w", slot.Hex(), address.Hex(), err)
+				}
+				values = append(values, orderedField{Key: slot.Hex(), Value: common.BytesToHash(value).Hex()})
+			}
+			alloc = append(alloc, orderedField{Key: address.Hex(), Value: orderedObject{{Key: "storage", Value: values}}})
+		}
+		patch, _ := json.MarshalIndent(orderedObject{{Key: "alloc", Value: alloc}}, "", "  ")
+		if err := os.WriteFile(*patchFile, append(patch, '\n'), 0644); err != nil {
+			return err
+		}
+	}
+	phase.finish()
+	log.Info("Snapshot is written", "block", *blockNumber, "accounts", len(addresses), "balance", formatAmount(total), "airdrop", *outputFile)
+	return nil
+}
+
+func sortedSlotOwners(slots map[common.Address][]common.Hash) []common.Address {
+	var result []common.Address
+	for address := range slots {
+		result = append(result, address)
+	}
+	sort.Slice(result, func(i, j int) bool {
+		return bytes.Compare(result[i][:], result[j][:]) < 0
+	})
+	return result
+}
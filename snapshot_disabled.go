@@ -0,0 +1,11 @@
+//go:build !snapshot
+// +build !snapshot
+
+package main
+
+import "fmt"
+
+// runGenSnapshotCommand is only available in builds with network access to RPC endpoints (-tags snapshot)
+func runGenSnapshotCommand(args []string) error {
+	return fmt.Errorf("snapshot support is not compiled in, rebuild with: go build -tags snapshot")
+}
@@ -0,0 +1,93 @@
+package main
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"encoding/json"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+const genesisSpecVersion = 1
+
+// genesisSpecSection is a canonical (sorted keys, compact) JSON document of one genesis part with its hash,
+// sections can be verified independently of each other
+type genesisSpecSection struct {
+	Name    string          `json:"name"`
+	Hash    common.Hash     `json:"hash"`
+	Content json.RawMessage `json:"content"`
+}
+
+// genesisSpec is a normalized genesis document, its hash is sha256 over "<name>:<hash>\n" lines of all sections
+type genesisSpec struct {
+	Version  int                  `json:"version"`
+	Hash     common.Hash          `json:"hash"`
+	Sections []genesisSpecSection `json:"sections"`
+}
+
+// canonicalJson re-encodes value with sorted object keys and without whitespaces, numbers are kept as is
+func canonicalJson(value interface{}) ([]byte, error) {
+	raw, err := json.Marshal(value)
+	if err != nil {
+		return nil, err
+	}
+	decoder := json.NewDecoder(bytes.NewReader(raw))
+	decoder.UseNumber()
+	var generic interface{}
+	if err := decoder.Decode(&generic); err != nil {
+		return nil, err
+	}
+	return json.Marshal(generic)
+}
+
+func newGenesisSpecSection(name string, value interface{}) (genesisSpecSection, error) {
+	content, err := canonicalJson(value)
+	if err != nil {
+		return genesisSpecSection{}, err
+	}
+	return genesisSpecSection{Name: name, Hash: sha256.Sum256(content), Content: content}, nil
+}
+
+func createGenesisSpec(genesis *core.Genesis) (*genesisSpec, error) {
+	raw, err := json.Marshal(genesis)
+	if err != nil {
+		return nil, err
+	}
+	header := make(map[string]json.RawMessage)
+	if err := json.Unmarshal(raw, &header); err != nil {
+		return nil, err
+	}
+	delete(header, "config")
+	delete(header, "alloc")
+	spec := &genesisSpec{Version: genesisSpecVersion}
+	headerSection, err := newGenesisSpecSection("header", header)
+	if err != nil {
+		return nil, err
+	}
+	configSection, err := newGenesisSpecSection("config", genesis.Config)
+	if err != nil {
+		return nil, err
+	}
+	spec.Sections = append(spec.Sections, headerSection, configSection)
+	var addresses []string
+	for address := range genesis.Alloc {
+		addresses = append(addresses, strings.ToLower(address.Hex()))
+	}
+	sort.Strings(addresses)
+	for _, address := range addresses {
+		section, err := newGenesisSpecSection("alloc/"+address, genesis.Alloc[common.HexToAddress(address)])
+		if err != nil {
+			return nil, err
+		}
+		spec.Sections = append(spec.Sections, section)
+	}
+	digest := sha256.New()
+	for _, section := range spec.Sections {
+		digest.Write([]byte(section.Name + ":" + section.Hash.Hex() + "\n"))
+	}
+	copy(spec.Hash[:], digest.Sum(nil))
+	return spec, nil
+}
@@ -0,0 +1,69 @@
+package main
+
+import (
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/core"
+	"github.com/ethereum/go-ethereum/core/rawdb"
+	"github.com/ethereum/go-ethereum/core/state"
+	"github.com/ethereum/go-ethereum/core/types"
+	"github.com/ethereum/go-ethereum/core/vm"
+	"github.com/ethereum/go-ethereum/ethdb/memorydb"
+	"github.com/ethereum/go-ethereum/trie"
+)
+
+// system contracts in init order, chain config goes first because staking reads consensus params in its ctor
+var systemContractAddresses = []common.Address{
+	chainConfigAddress,
+	stakingAddress,
+	slashingIndicatorAddress,
+	systemRewardAddress,
+	stakingPoolAddress,
+	governanceAddress,
+	runtimeUpgradeAddress,
+	deployerProxyAddress,
+}
+
+// newStateFromGenesis creates in-memory state database filled with genesis alloc
+func newStateFromGenesis(genesis *core.Genesis) (*state.StateDB, error) {
+	ethdb := rawdb.NewDatabase(memorydb.New())
+	db := state.NewDatabaseWithConfig(ethdb, &trie.Config{})
+	statedb, err := state.New(common.Hash{}, db, nil)
+	if err != nil {
+		return nil, err
+	}
+	for address, account := range genesis.Alloc {
+		if account.Balance != nil {
+			statedb.SetBalance(address, account.Balance)
+		}
+		statedb.SetNonce(address, account.Nonce)
+		statedb.SetCode(address, account.Code)
+		for key, value := range account.Storage {
+			statedb.SetState(address, key, value)
+		}
+	}
+	return statedb, nil
+}
+
+// newGenesisEVM creates EVM on top of genesis state, coinbase is zero address (same as system caller)
+func newGenesisEVM(genesis *core.Genesis, statedb *state.StateDB) *vm.EVM {
+	block := genesis.ToBlock(nil)
+	blockContext := core.NewEVMBlockContext(block.Header(), &dummyChainContext{}, &common.Address{})
+	txContext := core.NewEVMTxContext(
+		types.NewMessage(common.Address{}, nil, 0, big.NewInt(0), 10_000_000, big.NewInt(0), []byte{}, nil, false),
+	)
+	return vm.NewEVM(blockContext, txContext, statedb, genesis.Config, vm.Config{})
+}
+
+// initSystemContracts calls init function of all system contracts, consensus engine does the same in the first block
+func initSystemContracts(evm *vm.EVM) error {
+	for _, systemContract := range systemContractAddresses {
+		if result, _, err := evm.Call(vm.AccountRef(common.Address{}), systemContract, hexutil.MustDecode("0xe1c7392a"), 10_000_000, big.NewInt(0)); err != nil {
+			return fmt.Errorf("failed to init system contract %s: %w (%s)", systemContract.Hex(), err, revertReason(result))
+		}
+	}
+	return nil
+}
@@ -0,0 +1,311 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"math/big"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+// storageLayout is solc's storageLayout output
+type storageLayout struct {
+	Storage []storageLayoutEntry          `json:"storage"`
+	Types   map[string]*storageLayoutType `json:"types"`
+}
+
+type storageLayoutEntry struct {
+	Label  string `json:"label"`
+	Offset int    `json:"offset"`
+	Slot   string `json:"slot"`
+	Type   string `json:"type"`
+}
+
+type storageLayoutType struct {
+	Encoding      string               `json:"encoding"`
+	Label         string               `json:"label"`
+	NumberOfBytes string               `json:"numberOfBytes"`
+	Base          string               `json:"base,omitempty"`
+	Key           string               `json:"key,omitempty"`
+	Value         string               `json:"value,omitempty"`
+	Members       []storageLayoutEntry `json:"members,omitempty"`
+}
+
+// decodedVariable is a non-zero storage value with the variable path it belongs to
+type decodedVariable struct {
+	Label  string      `json:"label"`
+	Type   string      `json:"type"`
+	Slot   common.Hash `json:"slot"`
+	Offset int         `json:"offset"`
+	Value  string      `json:"value"`
+}
+
+// max number of dynamic array elements to decode (protects from corrupted length slots)
+const maxDecodedArrayLength = 10_000
+
+var staticArrayLength = regexp.MustCompile(`\)(\d+)_storage$`)
+
+// storageLayoutDecoder walks over storage layout and decodes variables, mapping entries can't be enumerated
+// so they are decoded only for the known address keys (alloc addresses and addresses found in storage)
+type storageLayoutDecoder struct {
+	layout      *storageLayout
+	read        func(slot common.Hash) common.Hash
+	addressKeys map[common.Address]bool
+	deferred    []func() error
+	result      []*decodedVariable
+}
+
+func decodeStorageLayout(layout *storageLayout, read func(slot common.Hash) common.Hash, knownAddresses []common.Address) ([]*decodedVariable, error) {
+	d := &storageLayoutDecoder{layout: layout, read: read, addressKeys: make(map[common.Address]bool)}
+	for _, address := range knownAddresses {
+		d.addressKeys[address] = true
+	}
+	for _, entry := range layout.Storage {
+		slot, ok := new(big.Int).SetString(entry.Slot, 10)
+		if !ok {
+			return nil, fmt.Errorf("invalid slot (%s) of %s", entry.Slot, entry.Label)
+		}
+		if err := d.decode(entry.Label, entry.Type, slot, entry.Offset); err != nil {
+			return nil, err
+		}
+	}
+	// mappings are decoded last, so all addresses found in storage can be used as keys
+	for len(d.deferred) > 0 {
+		next := d.deferred[0]
+		d.deferred = d.deferred[1:]
+		if err := next(); err != nil {
+			return nil, err
+		}
+	}
+	return d.result, nil
+}
+
+func (d *storageLayoutDecoder) decode(label, typeName string, slot *big.Int, offset int) error {
+	t, ok := d.layout.Types[typeName]
+	if !ok {
+		return fmt.Errorf("type %s of %s is not found in storage layout", typeName, label)
+	}
+	switch {
+	case t.Encoding == "mapping":
+		d.deferred = append(d.deferred, func() error {
+			return d.decodeMapping(label, t, slot)
+		})
+		return nil
+	case t.Encoding == "dynamic_array":
+		return d.decodeArray(label, t, slot, d.read(common.BigToHash(slot)).Big(), crypto.Keccak256Hash(common.BigToHash(slot).Bytes()).Big())
+	case t.Encoding == "bytes":
+		return d.decodeBytes(label, t, slot)
+	case len(t.Members) > 0:
+		for _, member := range t.Members {
+			memberSlot, ok := new(big.Int).SetString(member.Slot, 10)
+			if !ok {
+				return fmt.Errorf("invalid slot (%s) of %s.%s", member.Slot, label, member.Label)
+			}
+			if err := d.decode(label+"."+member.Label, member.Type, new(big.Int).Add(slot, memberSlot), member.Offset); err != nil {
+				return err
+			}
+		}
+		return nil
+	case t.Base != "":
+		match := staticArrayLength.FindStringSubmatch(typeName)
+		if match == nil {
+			return fmt.Errorf("can't determine length of %s", typeName)
+		}
+		length, _ := new(big.Int).SetString(match[1], 10)
+		return d.decodeArray(label, t, slot, length, slot)
+	}
+	d.decodeValue(label, t, slot, offset)
+	return nil
+}
+
+func (d *storageLayoutDecoder) decodeArray(label string, t *storageLayoutType, slot, length, start *big.Int) error {
+	if t.Encoding == "dynamic_array" && length.Sign() > 0 {
+		d.result = append(d.result, &decodedVariable{Label: label + ".length", Type: "uint256", Slot: common.BigToHash(slot), Value: length.String()})
+	}
+	if !length.IsInt64() || length.Int64() > maxDecodedArrayLength {
+		return nil
+	}
+	base := d.layout.Types[t.Base]
+	if base == nil {
+		return fmt.Errorf("type %s of %s is not found in storage layout", t.Base, label)
+	}
+	size, _ := strconv.Atoi(base.NumberOfBytes)
+	for i := int64(0); i < length.Int64(); i++ {
+		elementSlot, elementOffset := new(big.Int).Set(start), 0
+		if size > 0 && size <= 16 && base.Encoding == "inplace" && len(base.Members) == 0 {
+			// small value types are packed into one slot
+			perSlot := int64(32 / size)
+			elementSlot.Add(elementSlot, big.NewInt(i/perSlot))
+			elementOffset = int(i%perSlot) * size
+		} else {
+			slotsPerElement := int64((size + 31) / 32)
+			elementSlot.Add(elementSlot, big.NewInt(i*slotsPerElement))
+		}
+		if err := d.decode(fmt.Sprintf("%s[%d]", label, i), t.Base, elementSlot, elementOffset); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func (d *storageLayoutDecoder) decodeMapping(label string, t *storageLayoutType, slot *big.Int) error {
+	key := d.layout.Types[t.Key]
+	if key == nil || !strings.HasPrefix(key.Label, "address") && !strings.HasPrefix(key.Label, "contract ") {
+		// only address keys can be enumerated
+		return nil
+	}
+	var addresses []common.Address
+	for address := range d.addressKeys {
+		addresses = append(addresses, address)
+	}
+	sort.Slice(addresses, func(i, j int) bool {
+		return bytes.Compare(addresses[i].Bytes(), addresses[j].Bytes()) < 0
+	})
+	for _, address := range addresses {
+		valueSlot := crypto.Keccak256Hash(common.BytesToHash(address.Bytes()).Bytes(), common.BigToHash(slot).Bytes())
+		if err := d.decode(fmt.Sprintf("%s[%s]", label, address.Hex()), t.Value, valueSlot.Big(), 0); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func (d *storageLayoutDecoder) decodeBytes(label string, t *storageLayoutType, slot *big.Int) error {
+	word := d.read(common.BigToHash(slot))
+	var data []byte
+	if word[31]&1 == 0 {
+		// short value is stored inline with length*2 in the lowest byte
+		if word[31]/2 > 31 {
+			return fmt.Errorf("invalid length (%d) of short %s in slot %s", word[31]/2, label, common.BigToHash(slot).Hex())
+		}
+		data = word[:word[31]/2]
+	} else {
+		length := new(big.Int).Rsh(word.Big(), 1)
+		if !length.IsInt64() || length.Int64() > 32*maxDecodedArrayLength {
+			return nil
+		}
+		start := crypto.Keccak256Hash(common.BigToHash(slot).Bytes()).Big()
+		for i := int64(0); int64(len(data)) < length.Int64(); i++ {
+			chunk := d.read(common.BigToHash(new(big.Int).Add(start, big.NewInt(i))))
+			data = append(data, chunk[:]...)
+		}
+		data = data[:length.Int64()]
+	}
+	if len(data) == 0 {
+		return nil
+	}
+	value := fmt.Sprintf("0x%x", data)
+	if t.Label == "string" {
+		value = strconv.Quote(string(data))
+	}
+	d.result = append(d.result, &decodedVariable{Label: label, Type: t.Label, Slot: common.BigToHash(slot), Value: value})
+	return nil
+}
+
+func (d *storageLayoutDecoder) decodeValue(label string, t *storageLayoutType, slot *big.Int, offset int) {
+	size, _ := strconv.Atoi(t.NumberOfBytes)
+	if size <= 0 || size > 32 || offset+size > 32 {
+		return
+	}
+	word := d.read(common.BigToHash(slot))
+	raw := word[32-offset-size : 32-offset]
+	value := new(big.Int).SetBytes(raw)
+	if value.Sign() == 0 {
+		return
+	}
+	var formatted string
+	switch {
+	case t.Label == "bool":
+		formatted = "true"
+	case t.Label == "address" || t.Label == "address payable" || strings.HasPrefix(t.Label, "contract "):
+		address := common.BytesToAddress(raw)
+		d.addressKeys[address] = true
+		formatted = address.Hex()
+	case strings.HasPrefix(t.Label, "uint") || strings.HasPrefix(t.Label, "enum "):
+		formatted = value.String()
+	case strings.HasPrefix(t.Label, "int"):
+		if raw[0]&0x80 != 0 {
+			value.Sub(value, new(big.Int).Lsh(big.NewInt(1), uint(size*8)))
+		}
+		formatted = value.String()
+	default:
+		formatted = fmt.Sprintf("0x%x", raw)
+	}
+	d.result = append(d.result, &decodedVariable{Label: label, Type: t.Label, Slot: common.BigToHash(slot), Offset: offset, Value: formatted})
+}
+
+// storageLayoutSuffix is the suffix of solc storage layout files placed next to artifacts (e.g. Staking.storageLayout.json)
+const storageLayoutSuffix = ".storageLayout.json"
+
+// loadStorageLayouts returns layouts of the artifacts, layout file next to the artifact has priority over the
+// layout embedded into the artifact, contracts without layout are omitted
+func loadStorageLayouts(artifacts artifactSet, dir string) (map[string]*storageLayout, error) {
+	result := make(map[string]*storageLayout)
+	for _, name := range artifacts.names() {
+		if dir != "" {
+			data, err := os.ReadFile(filepath.Join(dir, name+storageLayoutSuffix))
+			if err == nil {
+				layout := &storageLayout{}
+				if err := json.Unmarshal(data, layout); err != nil {
+					return nil, fmt.Errorf("failed to parse storage layout of %s: %w", name, err)
+				}
+				result[name] = layout
+				continue
+			} else if !errors.Is(err, fs.ErrNotExist) {
+				return nil, err
+			}
+		}
+		artifact := &artifactData{}
+		if err := json.Unmarshal(artifacts[name], artifact); err != nil {
+			return nil, err
+		}
+		if artifact.StorageLayout != nil {
+			result[name] = artifact.StorageLayout
+		}
+	}
+	return result, nil
+}
+
+// annotatedSlot is a storage slot of the genesis alloc with variables stored in it
+type annotatedSlot struct {
+	Slot  common.Hash `json:"slot"`
+	Value common.Hash `json:"value"`
+	// labels of the variables as "label (type)", empty if the slot doesn't match the layout
+	Variables []string `json:"variables"`
+}
+
+// annotateStorage labels every slot of the storage using the layout, slots are in ascending order
+func annotateStorage(layout *storageLayout, storage map[common.Hash]common.Hash, knownAddresses []common.Address) ([]*annotatedSlot, error) {
+	variables, err := decodeStorageLayout(layout, func(slot common.Hash) common.Hash {
+		return storage[slot]
+	}, knownAddresses)
+	if err != nil {
+		return nil, err
+	}
+	bySlot := make(map[common.Hash][]string)
+	for _, v := range variables {
+		bySlot[v.Slot] = append(bySlot[v.Slot], fmt.Sprintf("%s (%s)", v.Label, v.Type))
+	}
+	var result []*annotatedSlot
+	for slot, value := range storage {
+		labels := bySlot[slot]
+		if labels == nil {
+			labels = []string{}
+		}
+		result = append(result, &annotatedSlot{Slot: slot, Value: value, Variables: labels})
+	}
+	sort.Slice(result, func(i, j int) bool {
+		return bytes.Compare(result[i].Slot[:], result[j].Slot[:]) < 0
+	})
+	return result, nil
+}
@@ -0,0 +1,63 @@
+package main
+
+import (
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestAnnotateStorage(t *testing.T) {
+	layout := &storageLayout{
+		Storage: []storageLayoutEntry{
+			{Label: "owner", Slot: "0", Offset: 0, Type: "t_address"},
+			{Label: "paused", Slot: "0", Offset: 20, Type: "t_bool"},
+			{Label: "total", Slot: "1", Type: "t_uint256"},
+		},
+		Types: map[string]*storageLayoutType{
+			"t_address": {Encoding: "inplace", Label: "address", NumberOfBytes: "20"},
+			"t_bool":    {Encoding: "inplace", Label: "bool", NumberOfBytes: "1"},
+			"t_uint256": {Encoding: "inplace", Label: "uint256", NumberOfBytes: "32"},
+		},
+	}
+	owner := common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725")
+	packed := common.BytesToHash(append([]byte{1}, owner.Bytes()...))
+	storage := map[common.Hash]common.Hash{
+		common.BigToHash(common.Big0): packed,
+		common.BigToHash(common.Big2): common.BigToHash(common.Big1),
+	}
+	slots, err := annotateStorage(layout, storage, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(slots) != 2 {
+		t.Fatalf("unexpected slots: %v", slots)
+	}
+	if len(slots[0].Variables) != 2 || slots[0].Variables[0] != "owner (address)" || slots[0].Variables[1] != "paused (bool)" {
+		t.Fatalf("unexpected variables of slot 0: %v", slots[0].Variables)
+	}
+	if len(slots[1].Variables) != 0 {
+		t.Fatalf("unknown slot is labeled: %v", slots[1].Variables)
+	}
+}
+
+func TestDecodeInvalidShortBytes(t *testing.T) {
+	layout := &storageLayout{
+		Storage: []storageLayoutEntry{{Label: "name", Slot: "0", Type: "t_string_storage"}},
+		Types: map[string]*storageLayoutType{
+			"t_string_storage": {Encoding: "bytes", Label: "string", NumberOfBytes: "32"},
+		},
+	}
+	// even length byte above 64 would point past the slot
+	word := common.Hash{}
+	word[31] = 0xfe
+	if _, err := decodeStorageLayout(layout, func(common.Hash) common.Hash { return word }, nil); err == nil {
+		t.Fatal("invalid short bytes length is accepted")
+	}
+	word = common.Hash{}
+	copy(word[:], "abc")
+	word[31] = 6
+	variables, err := decodeStorageLayout(layout, func(common.Hash) common.Hash { return word }, nil)
+	if err != nil || len(variables) != 1 || variables[0].Value != `"abc"` {
+		t.Fatalf("unexpected variables %v (%v)", variables, err)
+	}
+}
@@ -0,0 +1,151 @@
+package main
+
+import (
+	"bufio"
+	"bytes"
+	"compress/gzip"
+	"encoding/json"
+	"io"
+	"os"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// streamedAlloc is the alloc object of the formatted genesis, JSON writer encodes it account by account, so
+// encoded alloc (millions of airdrop accounts) is never held in memory at once
+type streamedAlloc struct {
+	genesis *core.Genesis
+	key     func(address common.Address) string
+	account func(account core.GenesisAccount) interface{}
+}
+
+// MarshalJSON encodes the whole alloc, it's used by encoders that can't stream (yaml and toml)
+func (a streamedAlloc) MarshalJSON() ([]byte, error) {
+	result := orderedObject{}
+	for _, address := range sortedAlloc(a.genesis) {
+		result = append(result, orderedField{Key: a.key(address), Value: a.account(a.genesis.Alloc[address])})
+	}
+	return result.MarshalJSON()
+}
+
+// writeJsonStream writes value in the same layout as json.MarshalIndent(value, "", "  "), but objects are
+// encoded field by field
+func writeJsonStream(w io.Writer, value interface{}) error {
+	return writeIndentedValue(w, value, 0)
+}
+
+func writeIndentedValue(w io.Writer, value interface{}, depth int) error {
+	switch v := value.(type) {
+	case orderedObject:
+		return writeIndentedObject(w, len(v), depth, func(i int) (string, interface{}) {
+			return v[i].Key, v[i].Value
+		})
+	case streamedAlloc:
+		addresses := sortedAlloc(v.genesis)
+		return writeIndentedObject(w, len(addresses), depth, func(i int) (string, interface{}) {
+			return v.key(addresses[i]), v.account(v.genesis.Alloc[addresses[i]])
+		})
+	}
+	data, err := json.Marshal(value)
+	if err != nil {
+		return err
+	}
+	buf := &bytes.Buffer{}
+	if err := json.Indent(buf, data, strings.Repeat("  ", depth), "  "); err != nil {
+		return err
+	}
+	_, err = buf.WriteTo(w)
+	return err
+}
+
+func writeIndentedObject(w io.Writer, size, depth int, field func(i int) (string, interface{})) error {
+	if size == 0 {
+		_, err := io.WriteString(w, "{}")
+		return err
+	}
+	if _, err := io.WriteString(w, "{\n"); err != nil {
+		return err
+	}
+	indent := strings.Repeat("  ", depth+1)
+	for i := 0; i < size; i++ {
+		name, value := field(i)
+		key, err := json.Marshal(name)
+		if err != nil {
+			return err
+		}
+		if _, err := io.WriteString(w, indent+string(key)+": "); err != nil {
+			return err
+		}
+		if err := writeIndentedValue(w, value, depth+1); err != nil {
+			return err
+		}
+		separator := ",\n"
+		if i == size-1 {
+			separator = "\n"
+		}
+		if _, err := io.WriteString(w, separator); err != nil {
+			return err
+		}
+	}
+	_, err := io.WriteString(w, strings.Repeat("  ", depth)+"}")
+	return err
+}
+
+// writeFormattedOutput writes formatted genesis into the target file (stdout, stderr or path), JSON is streamed
+// and files with .gz suffix are gzip compressed
+func writeFormattedOutput(targetFile string, value interface{}, format string) error {
+	if format != "" && format != "json" {
+		data, err := encodeOutput(value, format)
+		if err != nil {
+			return err
+		}
+		return writeCompressedOutput(targetFile, func(w io.Writer) error {
+			_, err := w.Write(data)
+			return err
+		})
+	}
+	return writeCompressedOutput(targetFile, func(w io.Writer) error {
+		return writeJsonStream(w, value)
+	})
+}
+
+func writeCompressedOutput(targetFile string, write func(w io.Writer) error) error {
+	var file *os.File
+	switch targetFile {
+	case "stdout":
+		file = os.Stdout
+	case "stderr":
+		file = os.Stderr
+	default:
+		var err error
+		if file, err = os.Create(targetFile); err != nil {
+			return err
+		}
+		defer file.Close()
+	}
+	buffered := bufio.NewWriter(file)
+	var w io.Writer = buffered
+	var compressor *gzip.Writer
+	if strings.HasSuffix(targetFile, ".gz") {
+		// no timestamp and name in the header, so compressed output is reproducible as well
+		compressor = gzip.NewWriter(buffered)
+		w = compressor
+	}
+	if err := write(w); err != nil {
+		return err
+	}
+	if compressor != nil {
+		if err := compressor.Close(); err != nil {
+			return err
+		}
+	}
+	if err := buffered.Flush(); err != nil {
+		return err
+	}
+	if file == os.Stdout || file == os.Stderr {
+		return nil
+	}
+	return file.Close()
+}
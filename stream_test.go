@@ -0,0 +1,68 @@
+package main
+
+import (
+	"bytes"
+	"compress/gzip"
+	"encoding/json"
+	"io"
+	"math/big"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestStreamedGenesisMatchesIndentedJson(t *testing.T) {
+	genesis := defaultGenesisConfig(1337)
+	genesis.Alloc = core.GenesisAlloc{
+		common.HexToAddress("0x00000000000000000000000000000000000000ff"): {Balance: big.NewInt(1)},
+		common.HexToAddress("0x0000000000000000000000000000000000001000"): {
+			Balance: big.NewInt(0),
+			Code:    []byte{0x60, 0x00},
+			Storage: map[common.Hash]common.Hash{{}: common.HexToHash("0x01")},
+		},
+	}
+	for _, format := range genesisFormatNames() {
+		formatted, err := formatGenesis(genesis, format)
+		if err != nil {
+			t.Fatal(err)
+		}
+		expected, err := json.MarshalIndent(formatted, "", "  ")
+		if err != nil {
+			t.Fatal(err)
+		}
+		actual := &bytes.Buffer{}
+		if err := writeJsonStream(actual, formatted); err != nil {
+			t.Fatal(err)
+		}
+		if !bytes.Equal(expected, actual.Bytes()) {
+			t.Fatalf("%s genesis differs:\n%s", format, firstDifference(expected, actual.Bytes()))
+		}
+	}
+}
+
+func TestGzipOutput(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "genesis.json.gz")
+	value := orderedObject{{Key: "alloc", Value: orderedObject{}}}
+	if err := writeFormattedOutput(path, value, "json"); err != nil {
+		t.Fatal(err)
+	}
+	file, err := os.Open(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer file.Close()
+	reader, err := gzip.NewReader(file)
+	if err != nil {
+		t.Fatal(err)
+	}
+	data, err := io.ReadAll(reader)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if string(data) != "{\n  \"alloc\": {}\n}" {
+		t.Fatalf("unexpected content: %s", data)
+	}
+}
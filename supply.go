@@ -0,0 +1,81 @@
+package main
+
+import (
+	"fmt"
+	"io"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/core"
+)
+
+// supplyReport is native supply implied by the genesis, total is the sum of all alloc balances and other is the
+// part of it that isn't explained by known sources (treasury, predeploys, system accounts, patch)
+type supplyReport struct {
+	Total                        *amountValue `json:"total"`
+	InitialStakes                *amountValue `json:"initialStakes"`
+	Vesting                      *amountValue `json:"vesting"`
+	Faucet                       *amountValue `json:"faucet"`
+	Accounts                     *amountValue `json:"accounts"`
+	Airdrop                      *amountValue `json:"airdrop"`
+	ValidatorOperationalBalances *amountValue `json:"validatorOperationalBalances"`
+	Other                        *amountValue `json:"other"`
+}
+
+// genesisSupply sums balances of all genesis accounts
+func genesisSupply(genesis *core.Genesis) *big.Int {
+	total := big.NewInt(0)
+	for _, account := range genesis.Alloc {
+		if account.Balance != nil {
+			total.Add(total, account.Balance)
+		}
+	}
+	return total
+}
+
+func newSupplyReport(genesis *core.Genesis, initialStakes, vesting, faucet, accounts, airdrop, operational *big.Int) *supplyReport {
+	total := genesisSupply(genesis)
+	other := new(big.Int).Set(total)
+	for _, known := range []*big.Int{initialStakes, vesting, faucet, accounts, airdrop, operational} {
+		other.Sub(other, known)
+	}
+	return &supplyReport{
+		Total:                        (*amountValue)(total),
+		InitialStakes:                (*amountValue)(initialStakes),
+		Vesting:                      (*amountValue)(vesting),
+		Faucet:                       (*amountValue)(faucet),
+		Accounts:                     (*amountValue)(accounts),
+		Airdrop:                      (*amountValue)(airdrop),
+		ValidatorOperationalBalances: (*amountValue)(operational),
+		Other:                        (*amountValue)(other),
+	}
+}
+
+// verifySupplyCap fails if the genesis mints more than the configured max total supply
+func verifySupplyCap(supply *supplyReport, maxTotalSupply *amountValue) error {
+	if maxTotalSupply == nil {
+		return nil
+	}
+	if total := (*big.Int)(supply.Total); total.Cmp((*big.Int)(maxTotalSupply)) > 0 {
+		excess := new(big.Int).Sub(total, (*big.Int)(maxTotalSupply))
+		return fmt.Errorf("genesis total supply %s exceeds maxTotalSupply %s by %s", formatAmount(total), formatAmount((*big.Int)(maxTotalSupply)), formatAmount(excess))
+	}
+	return nil
+}
+
+func (s *supplyReport) print(w io.Writer) {
+	fmt.Fprintf(w, "genesis supply: %s\n", formatAmount((*big.Int)(s.Total)))
+	for _, part := range []struct {
+		name  string
+		value *amountValue
+	}{
+		{"initial stakes", s.InitialStakes},
+		{"vesting", s.Vesting},
+		{"faucet", s.Faucet},
+		{"accounts", s.Accounts},
+		{"airdrop", s.Airdrop},
+		{"validator operational balances", s.ValidatorOperationalBalances},
+		{"other (treasury, predeploys, patch)", s.Other},
+	} {
+		fmt.Fprintf(w, " + %s: %s\n", part.name, formatAmount((*big.Int)(part.value)))
+	}
+}
@@ -0,0 +1,22 @@
+package main
+
+import (
+	"math/big"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestMaxTotalSupply(t *testing.T) {
+	config := devNetConfig
+	outputFile := filepath.Join(t.TempDir(), "genesis.json")
+	// devnet has 5000 eth of initial stakes and ~10kk eth of faucet
+	config.MaxTotalSupply = (*amountValue)(new(big.Int).Mul(big.NewInt(20_000_000), big.NewInt(1e18)))
+	if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+	config.MaxTotalSupply = (*amountValue)(new(big.Int).Mul(big.NewInt(5000), big.NewInt(1e18)))
+	if err := createGenesisConfig(config, outputFile, defaultGenerateOptions()); err == nil || !strings.Contains(err.Error(), "exceeds maxTotalSupply") {
+		t.Fatalf("supply above the cap is accepted: %v", err)
+	}
+}
@@ -0,0 +1,70 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"regexp"
+	"sort"
+	"strings"
+)
+
+// configPlaceholder is ${NAME} in the config file, names follow shell variable syntax
+var configPlaceholder = regexp.MustCompile(`\$\{([A-Za-z_][A-Za-z0-9_]*)\}`)
+
+// readConfigVariables reads JSON object with string (or number) values used to fill config placeholders
+func readConfigVariables(path string) (map[string]string, error) {
+	if path == "" {
+		return nil, nil
+	}
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	var raw map[string]interface{}
+	decoder := json.NewDecoder(strings.NewReader(string(data)))
+	decoder.UseNumber()
+	if err := decoder.Decode(&raw); err != nil {
+		return nil, fmt.Errorf("failed to parse variables %s: %w", path, err)
+	}
+	result := make(map[string]string, len(raw))
+	for name, value := range raw {
+		switch v := value.(type) {
+		case string:
+			result[name] = v
+		case json.Number:
+			result[name] = v.String()
+		case bool:
+			result[name] = fmt.Sprintf("%t", v)
+		default:
+			return nil, fmt.Errorf("variable %s in %s must be a string, number or boolean", name, path)
+		}
+	}
+	return result, nil
+}
+
+// expandConfigVariables replaces ${NAME} placeholders with values from vars, names missing there are looked up
+// in environment, unresolved placeholders are reported at once
+func expandConfigVariables(data []byte, vars map[string]string) ([]byte, error) {
+	missing := make(map[string]bool)
+	result := configPlaceholder.ReplaceAllFunc(data, func(placeholder []byte) []byte {
+		name := string(configPlaceholder.FindSubmatch(placeholder)[1])
+		if value, ok := vars[name]; ok {
+			return []byte(value)
+		}
+		if value, ok := os.LookupEnv(name); ok {
+			return []byte(value)
+		}
+		missing[name] = true
+		return placeholder
+	})
+	if len(missing) > 0 {
+		var names []string
+		for name := range missing {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+		return nil, fmt.Errorf("config variables are not set: %s (pass them with --vars or environment)", strings.Join(names, ", "))
+	}
+	return result, nil
+}
@@ -0,0 +1,28 @@
+package main
+
+import (
+	"os"
+	"strings"
+	"testing"
+)
+
+func TestExpandConfigVariables(t *testing.T) {
+	os.Setenv("BAS_TEST_TREASURY", "0x00a601f45688dba8a070722073b015277cf36725")
+	defer os.Unsetenv("BAS_TEST_TREASURY")
+	template := []byte(`{"chainId": ${CHAIN_ID}, "systemTreasury": "${BAS_TEST_TREASURY}", "price": "$5"}`)
+	result, err := expandConfigVariables(template, map[string]string{"CHAIN_ID": "14000", "BAS_TEST_TREASURY": "0x0000000000000000000000000000000000000001"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	// variables from the file have priority over environment
+	if string(result) != `{"chainId": 14000, "systemTreasury": "0x0000000000000000000000000000000000000001", "price": "$5"}` {
+		t.Fatalf("unexpected result: %s", result)
+	}
+	result, err = expandConfigVariables(template, map[string]string{"CHAIN_ID": "1"})
+	if err != nil || !strings.Contains(string(result), "0x00a601f45688dba8a070722073b015277cf36725") {
+		t.Fatalf("environment variable isn't used: %s (%v)", result, err)
+	}
+	if _, err := expandConfigVariables([]byte(`${B} ${A} ${B}`), nil); err == nil || !strings.Contains(err.Error(), "A, B") {
+		t.Fatalf("missing variables aren't reported: %v", err)
+	}
+}
@@ -74,11 +74,12 @@ const newContractUsingTypes = async (owner, params, types = {}) => {
   } = Object.assign({}, DEFAULT_MOCK_PARAMS, params)
   // factory contracts
   const staking = await Staking.new(createConstructorArgs(
-    ['address[]', 'uint256[]', 'uint16'],
-    [genesisValidators, genesisValidators.map(() => '0'), '0'])
+    ['address[]', 'address[]', 'uint256[]', 'uint16[]'],
+    [genesisValidators, genesisValidators, genesisValidators.map(() => '0'), genesisValidators.map(() => '0')])
   );
   const slashingIndicator = await SlashingIndicator.new(createConstructorArgs([], []));
-  const systemReward = await SystemReward.new(createConstructorArgs(['address'], [systemTreasury]));
+  const treasuryShares = typeof systemTreasury === 'string' ? {[systemTreasury]: '10000'} : systemTreasury
+  const systemReward = await SystemReward.new(createConstructorArgs(['address[]', 'uint16[]'], [Object.keys(treasuryShares), Object.values(treasuryShares)]));
   const governance = await Governance.new(createConstructorArgs(['uint256'], ['1']));
   const chainConfig = await ChainConfig.new(createConstructorArgs(
     ["uint32", "uint32", "uint32", "uint32", "uint32", "uint32", "uint256", "uint256"],
@@ -9,7 +9,7 @@ const {newMockContract} = require('./helper')
 const BigNumber = require('bignumber.js');
 
 contract("SystemReward", async (accounts) => {
-  const [owner, treasury] = accounts
+  const [owner, treasury, secondTreasury] = accounts
   it("system fee is well calculated", async () => {
     const {systemReward} = await newMockContract(owner, {systemTreasury: treasury,})
     // send 1 ether
@@ -42,4 +42,14 @@ contract("SystemReward", async (accounts) => {
     assert.equal((await web3.eth.getBalance(treasury)).toString(), initialBalance.plus('51000000000000000000').toString(10))
     assert.equal((await systemReward.getSystemFee()).toString(), '0')
   })
+  it("system fee is distributed by shares", async () => {
+    const {systemReward} = await newMockContract(owner, {systemTreasury: {[treasury]: '7000', [secondTreasury]: '3000'}})
+    const treasuryBalance = new BigNumber((await web3.eth.getBalance(treasury)).toString());
+    const secondTreasuryBalance = new BigNumber((await web3.eth.getBalance(secondTreasury)).toString());
+    // send 50 ether to trigger auto claim
+    await web3.eth.sendTransaction({from: owner, to: systemReward.address, value: '50000000000000000000'}); // 50 ether
+    assert.equal((await web3.eth.getBalance(treasury)).toString(), treasuryBalance.plus('35000000000000000000').toString(10))
+    assert.equal((await web3.eth.getBalance(secondTreasury)).toString(), secondTreasuryBalance.plus('15000000000000000000').toString(10))
+    assert.equal((await systemReward.getSystemFee()).toString(), '0')
+  })
 });
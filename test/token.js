@@ -0,0 +1,40 @@
+/** @var artifacts {Array} */
+/** @var web3 {Web3} */
+/** @function contract */
+/** @function it */
+/** @function before */
+/** @var assert */
+
+const GenesisToken = artifacts.require("GenesisToken");
+
+contract("GenesisToken", async (accounts) => {
+  const [owner, holder1, holder2] = accounts
+  it("only minter can mint", async () => {
+    const token = await GenesisToken.new("Test Token", "TT", '18', {from: owner});
+    await token.mint(holder1, '1000', {from: owner});
+    assert.equal((await token.balanceOf(holder1)).toString(), '1000');
+    assert.equal((await token.totalSupply()).toString(), '1000');
+    try {
+      await token.mint(holder2, '1000', {from: holder1});
+      assert.fail("minted by non-minter");
+    } catch (e) {
+      assert.include(e.message, "GenesisToken: only minter");
+    }
+  })
+  it("balances are stored in the slots used by storage seeding", async () => {
+    const token = await GenesisToken.new("Test Token", "TT", '18', {from: owner});
+    await token.mint(holder1, '1000', {from: owner});
+    const balanceSlot = web3.utils.soliditySha3({t: 'uint256', v: holder1}, {t: 'uint256', v: '0'});
+    assert.equal(web3.utils.toBN(await web3.eth.getStorageAt(token.address, balanceSlot)).toString(), '1000');
+    assert.equal(web3.utils.toBN(await web3.eth.getStorageAt(token.address, 2)).toString(), '1000');
+  })
+  it("holder can transfer tokens", async () => {
+    const token = await GenesisToken.new("Test Token", "TT", '18', {from: owner});
+    await token.mint(holder1, '1000', {from: owner});
+    await token.approve(owner, '300', {from: holder1});
+    await token.transferFrom(holder1, holder2, '300', {from: owner});
+    await token.transfer(holder2, '200', {from: holder1});
+    assert.equal((await token.balanceOf(holder1)).toString(), '500');
+    assert.equal((await token.balanceOf(holder2)).toString(), '500');
+  })
+});
@@ -0,0 +1,41 @@
+/** @var artifacts {Array} */
+/** @var web3 {Web3} */
+/** @function contract */
+/** @function it */
+/** @function before */
+/** @var assert */
+
+const GenesisVesting = artifacts.require("GenesisVesting");
+
+contract("GenesisVesting", async (accounts) => {
+  const [owner, beneficiary1, beneficiary2] = accounts
+  it("only setup account can add schedules", async () => {
+    const vesting = await GenesisVesting.new({from: owner});
+    assert.equal(await vesting.setupAccount(), owner);
+    await vesting.addSchedule(beneficiary1, '0', '0', '100', {from: owner, value: '1000'});
+    try {
+      await vesting.addSchedule(beneficiary2, '0', '0', '100', {from: beneficiary1, value: '1000'});
+      assert.fail("schedule added by non-setup account");
+    } catch (e) {
+      assert.include(e.message, "GenesisVesting: only setup account");
+    }
+  })
+  it("nothing is vested before cliff, everything after duration", async () => {
+    const vesting = await GenesisVesting.new({from: owner});
+    await vesting.addSchedule(beneficiary1, '1000', '100', '400', {from: owner, value: '4000'});
+    assert.equal((await vesting.vestedAmount(beneficiary1, '1099')).toString(), '0');
+    assert.equal((await vesting.vestedAmount(beneficiary1, '1100')).toString(), '1000');
+    assert.equal((await vesting.vestedAmount(beneficiary1, '1200')).toString(), '2000');
+    assert.equal((await vesting.vestedAmount(beneficiary1, '1400')).toString(), '4000');
+    assert.equal((await vesting.vestedAmount(beneficiary1, '9999')).toString(), '4000');
+  })
+  it("beneficiary can release vested funds", async () => {
+    const vesting = await GenesisVesting.new({from: owner});
+    await vesting.addSchedule(beneficiary2, '0', '0', '1', {from: owner, value: '1000'});
+    const res = await vesting.release({from: beneficiary2});
+    assert.equal(res.logs[0].args.beneficiary, beneficiary2);
+    assert.equal(res.logs[0].args.amount.toString(), '1000');
+    assert.equal((await vesting.releasable(beneficiary2)).toString(), '0');
+    assert.equal(await web3.eth.getBalance(vesting.address), '0');
+  })
+});
@@ -0,0 +1,55 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// formatTimestamp renders unix time as RFC3339 in UTC together with the raw value, so every party
+// of the launch ceremony sees the same moment regardless of the local timezone
+func formatTimestamp(unix uint64) string {
+	return fmt.Sprintf("%s (%d)", time.Unix(int64(unix), 0).UTC().Format(time.RFC3339), unix)
+}
+
+// parseTimestamp accepts unix seconds (decimal or 0x-prefixed hex), RFC3339 datetime with explicit
+// timezone offset or "now", datetime in local time is rejected because it's ambiguous
+func parseTimestamp(value string) (uint64, error) {
+	value = strings.TrimSpace(value)
+	if value == "now" {
+		return uint64(time.Now().Unix()), nil
+	}
+	if strings.HasPrefix(value, "0x") {
+		return strconv.ParseUint(value[2:], 16, 64)
+	}
+	if unix, err := strconv.ParseUint(value, 10, 64); err == nil {
+		return unix, nil
+	}
+	// RFC3339 requires either "Z" or "±hh:mm" suffix, so local time strings fail here
+	result, err := time.Parse(time.RFC3339, value)
+	if err != nil {
+		return 0, fmt.Errorf("timestamp must be unix seconds or RFC3339 datetime with explicit timezone (e.g. 2022-04-01T00:00:00Z): %s", value)
+	}
+	if result.Unix() < 0 {
+		return 0, fmt.Errorf("timestamp is before unix epoch: %s", value)
+	}
+	return uint64(result.Unix()), nil
+}
+
+// resolveTimestampNow replaces "now" in the header with current unix seconds and returns them (empty if the
+// timestamp isn't "now"), frozen generation must reproduce locked inputs, so "now" is rejected there
+func (c *genesisConfig) resolveTimestampNow(frozen bool) (string, error) {
+	if c.Header == nil || strings.TrimSpace(c.Header.Timestamp) != "now" {
+		return "", nil
+	}
+	if frozen {
+		return "", fmt.Errorf("timestamp \"now\" can't be reproduced with --frozen, set unix seconds or RFC3339 datetime")
+	}
+	unix, err := parseTimestamp("now")
+	if err != nil {
+		return "", err
+	}
+	c.Header.Timestamp = strconv.FormatUint(unix, 10)
+	return c.Header.Timestamp, nil
+}
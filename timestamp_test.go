@@ -0,0 +1,25 @@
+package main
+
+import (
+	"strconv"
+	"testing"
+)
+
+func TestResolveTimestampNow(t *testing.T) {
+	config := genesisConfig{Header: &headerConfig{Timestamp: "now"}}
+	if _, err := config.resolveTimestampNow(true); err == nil {
+		t.Fatal("now is accepted with --frozen")
+	}
+	resolved, err := config.resolveTimestampNow(false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if unix, err := strconv.ParseUint(resolved, 10, 64); err != nil || unix == 0 || config.Header.Timestamp != resolved {
+		t.Fatalf("now isn't resolved to unix seconds: %q (%v)", config.Header.Timestamp, err)
+	}
+	// pinned timestamp is kept as is
+	config.Header.Timestamp = "2022-04-01T00:00:00Z"
+	if resolved, err := config.resolveTimestampNow(true); err != nil || resolved != "" || config.Header.Timestamp != "2022-04-01T00:00:00Z" {
+		t.Fatalf("pinned timestamp is changed: %q (%v)", config.Header.Timestamp, err)
+	}
+}
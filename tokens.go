@@ -0,0 +1,205 @@
+package main
+
+import (
+	"bytes"
+	_ "embed"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"os"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/common/math"
+	"github.com/ethereum/go-ethereum/core/vm"
+	"github.com/ethereum/go-ethereum/crypto"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+//go:embed build/contracts/GenesisToken.json
+var genesisTokenRawArtifact []byte
+
+// storage slots of GenesisToken variables, they are fixed in the contract source
+var genesisTokenBalanceSlot, genesisTokenTotalSupplySlot = big.NewInt(0), big.NewInt(2)
+
+const (
+	tokenSeedingMint    = "mint"
+	tokenSeedingStorage = "storage"
+)
+
+// tokenConfig is an ERC-20 token deployed in genesis with initial holder balances
+type tokenConfig struct {
+	// artifact is optional, embedded GenesisToken (ctor args are name, symbol and decimals) is used by default
+	extraContractConfig
+	// "mint" (default) simulates mint(address,uint256) calls, "storage" writes balanceOf mapping slots directly
+	Seeding string `json:"seeding,omitempty"`
+	// account calling mint, default is the zero address (deployer and minter of the embedded token)
+	Minter common.Address `json:"minter"`
+	// slots of balanceOf mapping and totalSupply for storage seeding, default is taken from the storage
+	// layout of the artifact (solc's storageLayout output)
+	BalanceSlot     *math.HexOrDecimal256 `json:"balanceSlot,omitempty"`
+	TotalSupplySlot *math.HexOrDecimal256 `json:"totalSupplySlot,omitempty"`
+	// token amounts in the smallest units (decimals are not applied)
+	Balances map[common.Address]string `json:"balances"`
+}
+
+func (c tokenConfig) seeding() string {
+	if c.Seeding == "" {
+		return tokenSeedingMint
+	}
+	return c.Seeding
+}
+
+func (c tokenConfig) rawArtifact() ([]byte, error) {
+	if c.Artifact == "" {
+		return genesisTokenRawArtifact, nil
+	}
+	return os.ReadFile(c.Artifact)
+}
+
+// storageSlots returns slots of balanceOf mapping and totalSupply variable
+func (c tokenConfig) storageSlots(rawArtifact []byte) (balanceSlot, totalSupplySlot *big.Int, err error) {
+	if c.Artifact == "" {
+		balanceSlot, totalSupplySlot = genesisTokenBalanceSlot, genesisTokenTotalSupplySlot
+	}
+	if trimmed := bytes.TrimSpace(rawArtifact); c.Artifact != "" && len(trimmed) > 0 && trimmed[0] == '{' {
+		artifact := &artifactData{}
+		if err := json.Unmarshal(trimmed, artifact); err != nil {
+			return nil, nil, err
+		}
+		if artifact.StorageLayout != nil {
+			balanceSlot = findStorageSlot(artifact.StorageLayout, "balanceOf", "_balances", "balances")
+			totalSupplySlot = findStorageSlot(artifact.StorageLayout, "totalSupply", "_totalSupply")
+		}
+	}
+	if c.BalanceSlot != nil {
+		balanceSlot = (*big.Int)(c.BalanceSlot)
+	}
+	if c.TotalSupplySlot != nil {
+		totalSupplySlot = (*big.Int)(c.TotalSupplySlot)
+	}
+	if balanceSlot == nil || totalSupplySlot == nil {
+		return nil, nil, fmt.Errorf("can't find balanceOf and totalSupply slots in storage layout of %s, set balanceSlot and totalSupplySlot", c.Artifact)
+	}
+	return balanceSlot, totalSupplySlot, nil
+}
+
+// findStorageSlot returns slot of the first top-level variable with one of the labels
+func findStorageSlot(layout *storageLayout, labels ...string) *big.Int {
+	for _, label := range labels {
+		for _, entry := range layout.Storage {
+			if entry.Label != label || entry.Offset != 0 {
+				continue
+			}
+			if slot, ok := new(big.Int).SetString(entry.Slot, 10); ok {
+				return slot
+			}
+		}
+	}
+	return nil
+}
+
+// deployTokens deploys tokens and seeds balances, balanceOf of every holder is checked afterwards, so wrong
+// storage slots can't produce silently broken token
+func deployTokens(simulator *systemContractSimulator, tokens []tokenConfig) deploymentFailures {
+	var failures deploymentFailures
+	for _, token := range tokens {
+		if err := deployToken(simulator, token); err != nil {
+			failures.add(token.name(), token.Address, err)
+			continue
+		}
+		log.Info("Deployed token", "name", token.name(), "address", token.Address, "holders", len(token.Balances), "seeding", token.seeding())
+	}
+	return failures
+}
+
+func deployToken(simulator *systemContractSimulator, token tokenConfig) error {
+	if account, ok := simulator.genesis.Alloc[token.Address]; ok && len(account.Code) > 0 {
+		return fmt.Errorf("address already has code")
+	}
+	rawArtifact, err := token.rawArtifact()
+	if err != nil {
+		return err
+	}
+	initCode, err := token.initCodeFrom(rawArtifact)
+	if err != nil {
+		return err
+	}
+	balance := (*big.Int)(token.Balance)
+	if balance == nil {
+		balance = big.NewInt(0)
+	}
+	if _, err := simulator.deploy(token.Address, initCode, balance); err != nil {
+		return err
+	}
+	holders := sortedAddresses(token.Balances)
+	amounts := make(map[common.Address]*big.Int)
+	for _, holder := range holders {
+		amount, ok := new(big.Int).SetString(token.Balances[holder], 0)
+		if !ok || amount.Sign() < 0 {
+			return fmt.Errorf("invalid balance of %s: %s", holder.Hex(), token.Balances[holder])
+		}
+		amounts[holder] = amount
+	}
+	switch token.seeding() {
+	case tokenSeedingMint:
+		mintSelector := crypto.Keccak256([]byte("mint(address,uint256)"))[:4:4]
+		var calls []followUpCall
+		for _, holder := range holders {
+			args, err := newArguments("address", "uint256").Pack(holder, amounts[holder])
+			if err != nil {
+				return err
+			}
+			calls = append(calls, followUpCall{from: token.Minter, input: append(mintSelector, args...)})
+		}
+		if err := simulator.runFollowUpCalls(token.Address, calls); err != nil {
+			return err
+		}
+	case tokenSeedingStorage:
+		balanceSlot, totalSupplySlot, err := token.storageSlots(rawArtifact)
+		if err != nil {
+			return err
+		}
+		account := simulator.genesis.Alloc[token.Address]
+		if account.Storage == nil {
+			account.Storage = make(map[common.Hash]common.Hash)
+		}
+		totalSupply := simulator.statedb.GetState(token.Address, common.BigToHash(totalSupplySlot)).Big()
+		for _, holder := range holders {
+			slot := crypto.Keccak256Hash(common.BytesToHash(holder.Bytes()).Bytes(), common.BigToHash(balanceSlot).Bytes())
+			value := new(big.Int).Add(simulator.statedb.GetState(token.Address, slot).Big(), amounts[holder])
+			simulator.statedb.SetState(token.Address, slot, common.BigToHash(value))
+			account.Storage[slot] = common.BigToHash(value)
+			totalSupply.Add(totalSupply, amounts[holder])
+		}
+		simulator.statedb.SetState(token.Address, common.BigToHash(totalSupplySlot), common.BigToHash(totalSupply))
+		account.Storage[common.BigToHash(totalSupplySlot)] = common.BigToHash(totalSupply)
+		simulator.genesis.Alloc[token.Address] = account
+	default:
+		return fmt.Errorf("unknown seeding %q, expected %s or %s", token.Seeding, tokenSeedingMint, tokenSeedingStorage)
+	}
+	return verifyTokenBalances(simulator, token.Address, holders, amounts)
+}
+
+// verifyTokenBalances calls balanceOf of every holder, balances are expected to be at least seeded amounts
+// (ctor might mint tokens as well)
+func verifyTokenBalances(simulator *systemContractSimulator, token common.Address, holders []common.Address, amounts map[common.Address]*big.Int) error {
+	simulator.statedb.Finalise(false)
+	balanceOfSelector := crypto.Keccak256([]byte("balanceOf(address)"))[:4:4]
+	var mismatches []string
+	for _, holder := range holders {
+		args, _ := newArguments("address").Pack(holder)
+		result, _, err := simulator.evm.StaticCall(vm.AccountRef(common.Address{}), token, append(balanceOfSelector, args...), 10_000_000)
+		if err != nil {
+			return fmt.Errorf("balanceOf(%s) failed: %w (%s)", holder.Hex(), err, revertReason(result))
+		}
+		if actual := new(big.Int).SetBytes(result); len(result) != 32 || actual.Cmp(amounts[holder]) < 0 {
+			mismatches = append(mismatches, fmt.Sprintf("%s: expected %s, got %s", holder.Hex(), amounts[holder], hexutil.Encode(result)))
+		}
+	}
+	if len(mismatches) > 0 {
+		return fmt.Errorf("seeded balances don't match balanceOf (wrong storage slot?):\n    %s", strings.Join(mismatches, "\n    "))
+	}
+	return nil
+}
@@ -0,0 +1,132 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"io/ioutil"
+	"math/big"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core/vm"
+	"github.com/ethereum/go-ethereum/eth/tracers"
+	"github.com/ethereum/go-ethereum/log"
+	"github.com/ethereum/go-ethereum/params"
+)
+
+// precompileCall is a call to a precompiled contract made by the simulated constructor (or init)
+type precompileCall struct {
+	Address   checksumAddress `json:"address"`
+	InputSize int             `json:"inputSize"`
+	GasUsed   uint64          `json:"gasUsed"`
+}
+
+// simulationTracer forwards all EVM events to the call tracer and records calls to precompiles
+type simulationTracer struct {
+	tracers.Tracer
+	precompiles map[common.Address]vm.PrecompiledContract
+	calls       []precompileCall
+	// index of the recorded call for every entered frame (-1 if it's not a precompile)
+	frames []int
+	// whether results of the call tracer are kept for every top-level call
+	traceCalls bool
+	traces     []json.RawMessage
+}
+
+func newSimulationTracer(rules params.Rules) (*simulationTracer, error) {
+	tracer, err := tracers.New("callTracer", nil)
+	if err != nil {
+		return nil, err
+	}
+	return &simulationTracer{Tracer: tracer, precompiles: activePrecompiles(rules)}, nil
+}
+
+func (t *simulationTracer) CaptureEnter(typ vm.OpCode, from common.Address, to common.Address, input []byte, gas uint64, value *big.Int) {
+	t.Tracer.CaptureEnter(typ, from, to, input, gas, value)
+	if _, ok := t.precompiles[to]; !ok {
+		t.frames = append(t.frames, -1)
+		return
+	}
+	t.frames = append(t.frames, len(t.calls))
+	t.calls = append(t.calls, precompileCall{Address: checksumAddress(to), InputSize: len(input)})
+}
+
+func (t *simulationTracer) CaptureExit(output []byte, gasUsed uint64, err error) {
+	t.Tracer.CaptureExit(output, gasUsed, err)
+	if len(t.frames) == 0 {
+		return
+	}
+	if index := t.frames[len(t.frames)-1]; index >= 0 {
+		t.calls[index].GasUsed = gasUsed
+	}
+	t.frames = t.frames[:len(t.frames)-1]
+}
+
+func (t *simulationTracer) CaptureEnd(output []byte, gasUsed uint64, elapsed time.Duration, err error) {
+	t.Tracer.CaptureEnd(output, gasUsed, elapsed, err)
+	if !t.traceCalls {
+		return
+	}
+	result, err := t.Tracer.GetResult()
+	if err != nil {
+		log.Warn("Failed to collect call trace", "err", err)
+		return
+	}
+	t.traces = append(t.traces, result)
+}
+
+// takeTraces returns call frames of top-level calls executed since the previous take
+func (t *simulationTracer) takeTraces() []json.RawMessage {
+	result := t.traces
+	t.traces = nil
+	return result
+}
+
+// enableTracing makes the simulator keep call traces, they are written by writeTrace
+func (s *systemContractSimulator) enableTracing(dir string) {
+	s.traceDir = dir
+	s.tracer.traceCalls = dir != ""
+}
+
+// writeTrace writes call frames of the contract (constructor, follow-up calls and init) as JSON array, it's
+// written for failed deployments too, so the operator can see where the call reverted
+func (s *systemContractSimulator) writeTrace(name string, frames []json.RawMessage) {
+	frames = append(frames, s.tracer.takeTraces()...)
+	if s.traceDir == "" {
+		return
+	}
+	if frames == nil {
+		frames = []json.RawMessage{}
+	}
+	data, err := json.MarshalIndent(frames, "", "  ")
+	if err == nil {
+		err = os.MkdirAll(s.traceDir, 0755)
+	}
+	if err == nil {
+		err = ioutil.WriteFile(filepath.Join(s.traceDir, name+".json"), data, 0644)
+	}
+	if err != nil {
+		log.Warn("Failed to write call trace", "contract", name, "err", err)
+		return
+	}
+	log.Debug("Written call trace", "contract", name, "calls", len(frames))
+}
+
+// takePrecompileCalls returns calls recorded since the previous take
+func (t *simulationTracer) takePrecompileCalls() []precompileCall {
+	result := t.calls
+	t.calls, t.frames = nil, nil
+	return result
+}
+
+// warnNonstandardPrecompiles logs calls to precompiles outside of Ethereum's 0x01-0x09, production client
+// might not have them
+func warnNonstandardPrecompiles(contract string, calls []precompileCall) {
+	for _, call := range calls {
+		if bytes.Compare(call.Address[:], maxStandardPrecompile[:]) > 0 {
+			log.Warn("Constructor calls nonstandard precompile", "contract", contract, "precompile", common.Address(call.Address), "inputSize", call.InputSize, "gas", call.GasUsed)
+		}
+	}
+}
@@ -0,0 +1,92 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+// treasuryShareDenominator is sum of all treasury shares (SHARE_DENOMINATOR of SystemReward)
+const treasuryShareDenominator = 10_000
+
+// treasuryConfig is system treasury accounts with their shares of the system fee in basis points, in config file
+// it's either a single address (it gets the whole fee) or an object of address to share
+type treasuryConfig map[common.Address]uint16
+
+func singleTreasury(address common.Address) treasuryConfig {
+	return treasuryConfig{address: treasuryShareDenominator}
+}
+
+func (t *treasuryConfig) UnmarshalJSON(data []byte) error {
+	var address common.Address
+	if err := json.Unmarshal(data, &address); err == nil {
+		*t = singleTreasury(address)
+		return nil
+	}
+	shares := make(map[common.Address]uint16)
+	if err := json.Unmarshal(data, &shares); err != nil {
+		return fmt.Errorf("system treasury must be an address or an object of address to share: %w", err)
+	}
+	*t = shares
+	return nil
+}
+
+// parseTreasury parses an address or comma separated <address>:<share> pairs
+func parseTreasury(value string) (treasuryConfig, error) {
+	if common.IsHexAddress(value) {
+		return singleTreasury(common.HexToAddress(value)), nil
+	}
+	result := make(treasuryConfig)
+	for _, pair := range strings.Split(value, ",") {
+		parts := strings.Split(strings.TrimSpace(pair), ":")
+		if len(parts) != 2 || !common.IsHexAddress(parts[0]) {
+			return nil, fmt.Errorf("invalid treasury share %q, expected <address>:<share>", pair)
+		}
+		share, err := strconv.ParseUint(parts[1], 10, 16)
+		if err != nil {
+			return nil, fmt.Errorf("invalid treasury share %q: %w", pair, err)
+		}
+		result[common.HexToAddress(parts[0])] = uint16(share)
+	}
+	return result, nil
+}
+
+// shares returns treasury accounts in address order with their shares, treasury isn't required, by default
+// the whole fee goes to the zero address (as single treasury of older configs did)
+func (t treasuryConfig) shares() ([]common.Address, []uint16) {
+	if len(t) == 0 {
+		return []common.Address{{}}, []uint16{treasuryShareDenominator}
+	}
+	var accounts []common.Address
+	for address := range t {
+		accounts = append(accounts, address)
+	}
+	sort.Slice(accounts, func(i, j int) bool {
+		return bytes.Compare(accounts[i][:], accounts[j][:]) < 0
+	})
+	shares := make([]uint16, len(accounts))
+	for i, address := range accounts {
+		shares[i] = t[address]
+	}
+	return accounts, shares
+}
+
+// validate adds violations of shares that SystemReward's ctor would revert on
+func (t treasuryConfig) validate(violations *configViolations) {
+	total := 0
+	accounts, shares := t.shares()
+	for i, address := range accounts {
+		if shares[i] == 0 {
+			violations.add(fmt.Sprintf("systemTreasury[%s]", address.Hex()), "share must be positive")
+		}
+		total += int(shares[i])
+	}
+	if total != treasuryShareDenominator {
+		violations.add("systemTreasury", "shares must sum up to %d basis points, got %d", treasuryShareDenominator, total)
+	}
+}
@@ -0,0 +1,41 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestTreasuryConfig(t *testing.T) {
+	first, second := common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725"), common.HexToAddress("0x08fae3885e299c24ff9841478eb946f41023ac69")
+	var treasury treasuryConfig
+	if err := json.Unmarshal([]byte(`"0x00a601f45688dBA8a070722073B015277cF36725"`), &treasury); err != nil {
+		t.Fatal(err)
+	}
+	if len(treasury) != 1 || treasury[first] != treasuryShareDenominator {
+		t.Fatalf("single address must get all shares: %v", treasury)
+	}
+	if err := json.Unmarshal([]byte(`{"0x08fae3885e299c24ff9841478eb946f41023ac69": 3000, "0x00a601f45688dba8a070722073b015277cf36725": 7000}`), &treasury); err != nil {
+		t.Fatal(err)
+	}
+	accounts, shares := treasury.shares()
+	if len(accounts) != 2 || accounts[0] != first || shares[0] != 7000 || accounts[1] != second || shares[1] != 3000 {
+		t.Fatalf("shares must be in address order: %v %v", accounts, shares)
+	}
+	parsed, err := parseTreasury(first.Hex() + ":5000," + second.Hex() + ":4000")
+	if err != nil {
+		t.Fatal(err)
+	}
+	var violations configViolations
+	parsed.validate(&violations)
+	if len(violations) != 1 || violations[0].Path != "systemTreasury" {
+		t.Fatalf("expected sum violation, got %v", violations)
+	}
+	config := devNetConfig
+	config.SystemTreasury = parsed
+	if err := config.validate(); !errors.As(err, &violations) {
+		t.Fatalf("config with bad shares is valid")
+	}
+}
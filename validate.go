@@ -0,0 +1,405 @@
+package main
+
+import (
+	"bytes"
+	"fmt"
+	"math/big"
+	"sort"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// configViolation is a problem found in genesis config with path to the field
+type configViolation struct {
+	Path    string
+	Message string
+}
+
+// configViolations reports all problems at once, so config can be fixed in one pass
+type configViolations []configViolation
+
+func (v configViolations) Error() string {
+	lines := []string{fmt.Sprintf("genesis config has %d problem(s):", len(v))}
+	for _, violation := range v {
+		lines = append(lines, fmt.Sprintf("  %s: %s", violation.Path, violation.Message))
+	}
+	return strings.Join(lines, "\n")
+}
+
+func (v *configViolations) add(path, format string, args ...interface{}) {
+	*v = append(*v, configViolation{Path: path, Message: fmt.Sprintf(format, args...)})
+}
+
+// validate checks genesis config and returns configViolations if anything is wrong
+func (c genesisConfig) validate() error {
+	var violations configViolations
+	if c.Version > currentConfigVersion {
+		violations.add("version", "must be at most %d, config is written for a newer generator", currentConfigVersion)
+	}
+	if c.ChainId <= 0 {
+		violations.add("chainId", "must be greater than zero (EIP-155)")
+	}
+	params := c.ConsensusParams
+	if params.EpochBlockInterval == 0 {
+		violations.add("consensusParams.epochBlockInterval", "must be greater than zero")
+	} else if period, err := params.blockPeriod(); err != nil {
+		violations.add("consensusParams.blockPeriodSeconds", "%s", err)
+	} else if uint64(params.EpochBlockInterval)%period != 0 {
+		// epoch boundaries must fall on whole block periods
+		violations.add("consensusParams.epochBlockInterval", "is %d, it must be a multiple of block period (%d)", params.EpochBlockInterval, period)
+	}
+	if params.ActiveValidatorsLength == 0 {
+		violations.add("consensusParams.activeValidatorsLength", "must be greater than zero")
+	} else if int(params.ActiveValidatorsLength) < len(c.Validators) {
+		violations.add("consensusParams.activeValidatorsLength", "is %d, but there are %d genesis validators (some of them won't be active)", params.ActiveValidatorsLength, len(c.Validators))
+	}
+	if params.MinValidatorStakeAmount == nil {
+		violations.add("consensusParams.minValidatorStakeAmount", "is required")
+	}
+	if params.MinStakingAmount == nil {
+		violations.add("consensusParams.minStakingAmount", "is required")
+	}
+	if _, err := c.CommissionRate.basisPoints(); err != nil {
+		violations.add("commissionRate", "%s", err)
+	}
+	if _, err := c.VotingPeriod.blocks(); err != nil {
+		violations.add("votingPeriod", "%s", err)
+	}
+	c.SystemTreasury.validate(&violations)
+	seen, seenBlsKeys, owners := make(map[string]int), make(map[string]int), make(map[common.Address]int)
+	// layout errors are reported below with the extra data
+	extraDataLayout, _ := c.extraDataConfig().layout()
+	for i, entry := range c.Validators {
+		validator, path := entry.Address, fmt.Sprintf("validators[%d]", i)
+		if validator == (common.Address{}) {
+			violations.add(path+".address", "is required (set address or keystore)")
+			continue
+		}
+		if prev, ok := seen[validator.Hex()]; ok {
+			violations.add(path, "duplicates validators[%d] (%s)", prev, validator.Hex())
+			continue
+		}
+		seen[validator.Hex()] = i
+		// staking ctor maps every owner to exactly one validator
+		if owner := entry.owner(); owner == (common.Address{}) {
+			violations.add(path+".owner", "must not be zero address")
+		} else if prev, ok := owners[owner]; ok {
+			violations.add(path+".owner", "%s already owns validators[%d]", owner.Hex(), prev)
+		} else {
+			owners[owner] = i
+		}
+		if entry.CommissionRate != nil {
+			if _, err := entry.CommissionRate.basisPoints(); err != nil {
+				violations.add(path+".commissionRate", "%s", err)
+			}
+		}
+		if len(entry.BlsPublicKey) > 0 {
+			if err := verifyBlsPublicKey(entry.BlsPublicKey); err != nil {
+				violations.add(path+".blsPublicKey", "%s", err)
+			} else if prev, ok := seenBlsKeys[string(entry.BlsPublicKey)]; ok {
+				violations.add(path+".blsPublicKey", "duplicates validators[%d].blsPublicKey", prev)
+			} else {
+				seenBlsKeys[string(entry.BlsPublicKey)] = i
+			}
+		} else if extraDataLayout.ValidatorBlsKeys {
+			violations.add(path+".blsPublicKey", "is required by extra data layout")
+		}
+		initialStake, ok := c.InitialStakes[validator]
+		if !ok {
+			violations.add(path, "initial stake is not found for validator %s", validator.Hex())
+			continue
+		}
+		stakePath := fmt.Sprintf("initialStakes[%s]", validator.Hex())
+		if initialStake == nil {
+			violations.add(stakePath, "amount is required")
+			continue
+		}
+		if minStake := params.MinValidatorStakeAmount; minStake != nil && (*big.Int)(initialStake).Cmp((*big.Int)(minStake)) < 0 {
+			violations.add(stakePath, "%s is below minValidatorStakeAmount (%s)", (*big.Int)(initialStake), (*big.Int)(minStake))
+		}
+	}
+	for _, validator := range sortedAmountAddresses(c.InitialStakes) {
+		if _, ok := seen[validator.Hex()]; !ok {
+			violations.add(fmt.Sprintf("initialStakes[%s]", validator.Hex()), "is not a genesis validator")
+		}
+	}
+	for _, address := range sortedAmountAddresses(c.Faucet) {
+		path := fmt.Sprintf("faucet[%s]", address.Hex())
+		if c.Faucet[address] == nil {
+			violations.add(path, "balance is required")
+		}
+		if reserved := reservedSystemAddress(address); reserved != "" {
+			violations.add(path, "%s is reserved for %s", address.Hex(), reserved)
+		}
+		// validator gets its balance from validatorOperationalBalance, faucet entry is usually a copy-paste mistake
+		if _, ok := seen[address.Hex()]; ok {
+			if !c.AllowValidatorFaucet {
+				violations.add(path, "%s is a genesis validator, use validatorOperationalBalance or set allowValidatorFaucet", address.Hex())
+			} else {
+				log.Warn("Genesis validator is funded by faucet", "address", address)
+			}
+		}
+	}
+	for i, deployer := range c.Deployers {
+		if reserved := reservedSystemAddress(deployer); reserved != "" {
+			violations.add(fmt.Sprintf("deployers[%d]", i), "%s is reserved for %s", deployer.Hex(), reserved)
+		}
+	}
+	contractNames := defaultArtifactSet().names()
+	for i, call := range c.FollowUpCalls {
+		known := false
+		for _, name := range contractNames {
+			known = known || strings.EqualFold(name, call.Contract)
+		}
+		if !known {
+			violations.add(fmt.Sprintf("followUpCalls[%d].contract", i), "unknown system contract %q, expected one of: %s", call.Contract, strings.Join(contractNames, ", "))
+		}
+		if len(call.Data) < 4 {
+			violations.add(fmt.Sprintf("followUpCalls[%d].data", i), "must contain at least function selector")
+		}
+	}
+	for i, assertion := range c.Assertions {
+		known := false
+		for _, name := range contractNames {
+			known = known || strings.EqualFold(name, assertion.Contract)
+		}
+		if !known {
+			violations.add(fmt.Sprintf("assertions[%d].contract", i), "unknown system contract %q, expected one of: %s", assertion.Contract, strings.Join(contractNames, ", "))
+		}
+		if assertion.Call == "" {
+			violations.add(fmt.Sprintf("assertions[%d].call", i), "function signature is required")
+		}
+		if len(assertion.Expect) == 0 {
+			violations.add(fmt.Sprintf("assertions[%d].expect", i), "expected return values are required")
+		}
+	}
+	if _, err := contractNonce(c.SystemContractNonce, 0); err != nil {
+		violations.add("systemContractNonce", "must be one of %s", strings.Join(contractNoncePolicies, ", "))
+	}
+	if c.MaxTotalSupply != nil && (*big.Int)(c.MaxTotalSupply).Sign() <= 0 {
+		violations.add("maxTotalSupply", "must be positive")
+	}
+	occupied := make(map[common.Address]string)
+	for _, address := range systemContractAddresses {
+		occupied[address] = "system contract"
+	}
+	for i, contract := range c.ExtraContracts {
+		path := fmt.Sprintf("extraContracts[%d]", i)
+		if owner, ok := occupied[contract.Address]; ok {
+			violations.add(path+".address", "%s is already used by %s", contract.Address.Hex(), owner)
+			continue
+		}
+		occupied[contract.Address] = path
+		if contract.Artifact == "" {
+			violations.add(path+".artifact", "is required")
+		}
+	}
+	for i, token := range c.Tokens {
+		path := fmt.Sprintf("tokens[%d]", i)
+		if token.Salt != nil || token.Create2Deployer != nil {
+			violations.add(path+".salt", "CREATE2 placement is supported only for extraContracts")
+		}
+		if owner, ok := occupied[token.Address]; ok {
+			violations.add(path+".address", "%s is already used by %s", token.Address.Hex(), owner)
+		} else {
+			occupied[token.Address] = path
+		}
+		if seeding := token.seeding(); seeding != tokenSeedingMint && seeding != tokenSeedingStorage {
+			violations.add(path+".seeding", "must be %s or %s, got %q", tokenSeedingMint, tokenSeedingStorage, seeding)
+		}
+		for _, holder := range sortedAddresses(token.Balances) {
+			if amount, ok := new(big.Int).SetString(token.Balances[holder], 0); !ok || amount.Sign() < 0 {
+				violations.add(fmt.Sprintf("%s.balances[%s]", path, holder.Hex()), "must be non-negative integer in the smallest token units, got %q", token.Balances[holder])
+			}
+		}
+		if _, ok := token.Balances[common.Address{}]; ok {
+			violations.add(path+".balances", "zero address can't hold tokens")
+		}
+	}
+	if vesting := c.Vesting; vesting != nil {
+		address := vesting.address()
+		if owner, ok := occupied[address]; ok {
+			violations.add("vesting.address", "%s is already used by %s", address.Hex(), owner)
+		} else {
+			occupied[address] = "vesting"
+		}
+		if vesting.Start != "" {
+			if _, err := parseTimestamp(vesting.Start); err != nil {
+				violations.add("vesting.start", "%s", err)
+			}
+		}
+		beneficiaries := make(map[common.Address]int)
+		for i, schedule := range vesting.Schedules {
+			path := fmt.Sprintf("vesting.schedules[%d]", i)
+			if schedule.Beneficiary == (common.Address{}) {
+				violations.add(path+".beneficiary", "is required")
+			} else if prev, ok := beneficiaries[schedule.Beneficiary]; ok {
+				violations.add(path+".beneficiary", "duplicates vesting.schedules[%d] (%s)", prev, schedule.Beneficiary.Hex())
+			} else {
+				beneficiaries[schedule.Beneficiary] = i
+			}
+			if schedule.Total == nil || (*big.Int)(schedule.Total).Sign() <= 0 {
+				violations.add(path+".total", "must be greater than zero")
+			}
+			if schedule.Duration == 0 {
+				violations.add(path+".duration", "must be greater than zero")
+			} else if schedule.Cliff > schedule.Duration {
+				violations.add(path+".cliff", "is %d, but must not exceed duration (%d)", schedule.Cliff, schedule.Duration)
+			}
+		}
+	}
+	if c.Safe != nil {
+		c.Safe.validate(occupied, &violations)
+	}
+	systemAccounts := make(map[common.Address]bool)
+	for _, account := range c.systemAccounts() {
+		systemAccounts[account.Address] = true
+	}
+	// faucet entries replace the whole alloc account, so they must not hit contracts or system accounts
+	for _, address := range sortedAmountAddresses(c.Faucet) {
+		if reservedSystemAddress(address) != "" {
+			continue
+		}
+		path := fmt.Sprintf("faucet[%s]", address.Hex())
+		if systemAccounts[address] {
+			violations.add(path, "%s is a system account, set it in systemAccounts", address.Hex())
+		} else if owner, ok := occupied[address]; ok {
+			violations.add(path, "%s is already used by %s", address.Hex(), owner)
+		}
+	}
+	for _, address := range sortedAccountAddresses(c.Accounts) {
+		path := fmt.Sprintf("accounts[%s]", address.Hex())
+		if reserved := reservedSystemAddress(address); reserved != "" {
+			violations.add(path, "%s is reserved for %s", address.Hex(), reserved)
+		} else if systemAccounts[address] {
+			violations.add(path, "%s is a system account, set it in systemAccounts", address.Hex())
+		} else if owner, ok := occupied[address]; ok {
+			violations.add(path, "%s is already used by %s", address.Hex(), owner)
+		}
+		if _, ok := c.Faucet[address]; ok {
+			violations.add(path, "%s is also funded by faucet", address.Hex())
+		}
+	}
+	if precompiles := c.Precompiles; precompiles != nil {
+		if precompiles.Fork != "" {
+			if _, err := precompilesForFork(precompiles.Fork); err != nil {
+				violations.add("precompiles.fork", "%s", err)
+			}
+		}
+		if set := precompiles.set(); set != precompileSetNode && set != precompileSetStandard {
+			violations.add("precompiles.set", "must be %s or %s, got %q", precompileSetNode, precompileSetStandard, set)
+		}
+		targets := make(map[common.Address]common.Address)
+		for _, from := range sortedAddressKeys(precompiles.Remap) {
+			to := precompiles.Remap[from]
+			path := fmt.Sprintf("precompiles.remap[%s]", from.Hex())
+			if owner, ok := occupied[to]; ok {
+				violations.add(path, "%s is already used by %s", to.Hex(), owner)
+			} else if prev, ok := targets[to]; ok {
+				violations.add(path, "%s is also the target of %s", to.Hex(), prev.Hex())
+			} else {
+				targets[to] = from
+			}
+		}
+		for i, custom := range precompiles.Custom {
+			path := fmt.Sprintf("precompiles.custom[%d]", i)
+			if owner, ok := occupied[custom.Address]; ok {
+				violations.add(path+".address", "%s is already used by %s", custom.Address.Hex(), owner)
+			} else if from, ok := targets[custom.Address]; ok {
+				violations.add(path+".address", "%s is also the target of %s", custom.Address.Hex(), from.Hex())
+			} else {
+				targets[custom.Address] = custom.Address
+			}
+			if _, err := custom.create(); err != nil {
+				violations.add(path, "%s", err)
+			}
+		}
+	}
+	if header := c.Header; header != nil {
+		if header.Timestamp != "" {
+			if _, err := parseTimestamp(header.Timestamp); err != nil {
+				violations.add("header.timestamp", "%s", err)
+			}
+		}
+		// parlia and clique expect in-turn difficulty of the genesis block
+		if header.Difficulty != nil && c.Consensus.engine() != consensusEthashDev && (*big.Int)(header.Difficulty).Cmp(big.NewInt(1)) != 0 {
+			violations.add("header.difficulty", "must be 1 for %s, got %s", c.Consensus.engine(), (*big.Int)(header.Difficulty))
+		}
+		if header.GasLimit != nil && uint64(*header.GasLimit) < minGenesisGasLimit {
+			violations.add("header.gasLimit", "must be at least %d", minGenesisGasLimit)
+		}
+	}
+	if _, err := checkForkSchedule(forkSchedule(defaultGenesisConfig(c.ChainId).Config, c.Forks)); err != nil {
+		violations.add("forks", "%s", err)
+	}
+	if len(c.Forks.blocks()) > 0 && c.Consensus.engine() != consensusParlia {
+		violations.add("forks", "BSC forks can be used only with parlia engine")
+	}
+	// validators vote from the first block, so extra data must contain their BLS keys
+	if c.Forks != nil && c.Forks.LubanBlock != nil && *c.Forks.LubanBlock == 0 && !extraDataLayout.ValidatorBlsKeys {
+		violations.add("forks.lubanBlock", "fast finality from genesis requires extra data layout with BLS keys (luban)")
+	}
+	if err := c.Consensus.checkEngine(); err != nil {
+		violations.add("consensus.engine", "%s", err)
+	} else if layout, err := c.extraDataConfig().layout(); err == nil {
+		if err := c.Consensus.checkExtraDataLayout(layout); err != nil {
+			violations.add("extraData.layout", "%s", err)
+		}
+	}
+	if _, err := createExtraData(c.extraDataConfig(), c.Validators); err != nil {
+		violations.add("extraData", "%s", err)
+	}
+	if len(violations) > 0 {
+		return violations
+	}
+	return nil
+}
+
+// reservedSystemAddresses are bounds of the range BAS reserves for system contracts (0x...1000 - 0x...7005)
+var reservedSystemAddresses = [2]common.Address{stakingAddress, deployerProxyAddress}
+
+// reservedSystemAddress returns what the address is reserved for (empty if it's a regular address)
+func reservedSystemAddress(address common.Address) string {
+	if address == intermediarySystemAddress {
+		return "intermediary system account"
+	}
+	if bytes.Compare(address[:], reservedSystemAddresses[0][:]) >= 0 && bytes.Compare(address[:], reservedSystemAddresses[1][:]) <= 0 {
+		return "system contracts"
+	}
+	return ""
+}
+
+func sortedAddresses(values map[common.Address]string) []common.Address {
+	var result []common.Address
+	for address := range values {
+		result = append(result, address)
+	}
+	sort.Slice(result, func(i, j int) bool {
+		return bytes.Compare(result[i].Bytes(), result[j].Bytes()) < 0
+	})
+	return result
+}
+
+func sortedAmountAddresses(values map[common.Address]*amountValue) []common.Address {
+	var result []common.Address
+	for address := range values {
+		result = append(result, address)
+	}
+	sort.Slice(result, func(i, j int) bool {
+		return bytes.Compare(result[i].Bytes(), result[j].Bytes()) < 0
+	})
+	return result
+}
+
+func sortedAddressKeys(values map[common.Address]common.Address) []common.Address {
+	var result []common.Address
+	for address := range values {
+		result = append(result, address)
+	}
+	sort.Slice(result, func(i, j int) bool {
+		return bytes.Compare(result[i].Bytes(), result[j].Bytes()) < 0
+	})
+	return result
+}
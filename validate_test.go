@@ -0,0 +1,78 @@
+package main
+
+import (
+	"errors"
+	"math/big"
+	"strings"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+func TestReservedAndValidatorAddressesInFaucet(t *testing.T) {
+	config := devNetConfig
+	validator := config.Validators[0].Address
+	ether := (*amountValue)(big.NewInt(1e18))
+	config.Faucet = map[common.Address]*amountValue{
+		common.HexToAddress("0x0000000000000000000000000000000000005000"): ether,
+		intermediarySystemAddress: ether,
+		validator:                 ether,
+	}
+	config.Deployers = []common.Address{deployerProxyAddress}
+	var violations configViolations
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 4 {
+		t.Fatalf("expected 4 violations, got %v", err)
+	}
+	config.AllowValidatorFaucet = true
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 3 {
+		t.Fatalf("validator in faucet isn't allowed explicitly: %v", err)
+	}
+	// just above the reserved range
+	config.Faucet = map[common.Address]*amountValue{common.HexToAddress("0x0000000000000000000000000000000000007006"): ether}
+	config.Deployers = nil
+	if err := config.validate(); err != nil {
+		t.Fatal(err)
+	}
+}
+
+func TestFaucetOverlapsContracts(t *testing.T) {
+	config := devNetConfig
+	token, systemAccount := common.HexToAddress("0x0000000000000000000000000000000000009000"), common.HexToAddress("0x0000000000000000000000000000000000009001")
+	config.Tokens = []tokenConfig{{extraContractConfig: extraContractConfig{Address: token}}}
+	config.Vesting = &vestingConfig{}
+	config.SystemAccounts = []systemAccountConfig{{Address: systemAccount}}
+	ether := (*amountValue)(big.NewInt(1e18))
+	config.Faucet = map[common.Address]*amountValue{token: ether, defaultVestingAddress: ether, systemAccount: ether}
+	// regular address is fine
+	config.Faucet[common.HexToAddress("0x0000000000000000000000000000000000009002")] = ether
+	var violations configViolations
+	if err := config.validate(); !errors.As(err, &violations) {
+		t.Fatalf("expected violations, got %v", err)
+	}
+	faucetViolations := 0
+	for _, violation := range violations {
+		if strings.HasPrefix(violation.Path, "faucet[") {
+			faucetViolations++
+		}
+	}
+	if faucetViolations != 3 {
+		t.Fatalf("expected 3 faucet violations, got %v", violations)
+	}
+}
+
+func TestEpochBlockIntervalMultipleOfBlockPeriod(t *testing.T) {
+	config := devNetConfig
+	config.ConsensusParams.BlockPeriodSeconds = 7
+	var violations configViolations
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 1 || violations[0].Path != "consensusParams.epochBlockInterval" {
+		t.Fatalf("epoch that isn't a multiple of block period is accepted: %v", err)
+	}
+	config.ConsensusParams.BlockPeriodSeconds = 1 << 62
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 1 || violations[0].Path != "consensusParams.blockPeriodSeconds" {
+		t.Fatalf("overflowing block period is accepted: %v", err)
+	}
+	config.ConsensusParams.BlockPeriodSeconds = 4
+	if err := config.validate(); err != nil {
+		t.Fatal(err)
+	}
+}
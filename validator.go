@@ -0,0 +1,144 @@
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+)
+
+// validatorConfig is a genesis validator, in config file it's either a bare address or an object
+type validatorConfig struct {
+	Address common.Address `json:"address"`
+	// validator owner (default is validator itself)
+	Owner *common.Address `json:"owner,omitempty"`
+	// commission rate of the validator (default is config's commissionRate)
+	CommissionRate *commissionRate `json:"commissionRate,omitempty"`
+	// human-readable name, it's not stored on chain and only goes to the deployment report
+	Moniker string `json:"moniker,omitempty"`
+	// compressed BLS12-381 public key for fast finality votes, required by extra data layouts with BLS keys
+	BlsPublicKey hexutil.Bytes `json:"blsPublicKey,omitempty"`
+	// geth keystore file of the validator key, address is taken from it (relative to the config file)
+	Keystore string `json:"keystore,omitempty"`
+}
+
+func (v *validatorConfig) UnmarshalJSON(data []byte) error {
+	var address common.Address
+	if err := json.Unmarshal(data, &address); err == nil {
+		*v = validatorConfig{Address: address}
+		return nil
+	}
+	type plainValidatorConfig validatorConfig
+	return json.Unmarshal(data, (*plainValidatorConfig)(v))
+}
+
+// keystoreAddress reads address from geth keystore file, the key isn't decrypted, so no password is needed
+func keystoreAddress(path string) (common.Address, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return common.Address{}, err
+	}
+	var keyJson struct {
+		Address string `json:"address"`
+	}
+	if err := json.Unmarshal(data, &keyJson); err != nil {
+		return common.Address{}, fmt.Errorf("%s is not a keystore file: %w", path, err)
+	}
+	if !common.IsHexAddress(keyJson.Address) {
+		return common.Address{}, fmt.Errorf("keystore file %s has no valid address", path)
+	}
+	return common.HexToAddress(keyJson.Address), nil
+}
+
+// resolveValidatorKeystores sets addresses of validators declared with keystore files, explicit address must
+// match the keystore
+func (c *genesisConfig) resolveValidatorKeystores(baseDir string) error {
+	var violations configViolations
+	for i := range c.Validators {
+		validator, path := &c.Validators[i], fmt.Sprintf("validators[%d].keystore", i)
+		if validator.Keystore == "" {
+			continue
+		}
+		keystoreFile := validator.Keystore
+		if !filepath.IsAbs(keystoreFile) {
+			keystoreFile = filepath.Join(baseDir, keystoreFile)
+		}
+		address, err := keystoreAddress(keystoreFile)
+		if err != nil {
+			violations.add(path, "%s", err)
+			continue
+		}
+		if validator.Address != (common.Address{}) && validator.Address != address {
+			violations.add(path, "contains %s, but validator address is %s", address.Hex(), validator.Address.Hex())
+			continue
+		}
+		validator.Address = address
+	}
+	if len(violations) > 0 {
+		return violations
+	}
+	return nil
+}
+
+func (v validatorConfig) owner() common.Address {
+	if v.Owner != nil {
+		return *v.Owner
+	}
+	return v.Address
+}
+
+func (v validatorConfig) commissionRate(defaultRate commissionRate) commissionRate {
+	if v.CommissionRate != nil {
+		return *v.CommissionRate
+	}
+	return defaultRate
+}
+
+func (c genesisConfig) validatorAddresses() []common.Address {
+	var result []common.Address
+	for _, validator := range c.Validators {
+		result = append(result, validator.Address)
+	}
+	return result
+}
+
+// validatorOperationalAccounts returns consensus and owner addresses of validators without duplicates, both
+// send transactions from the first blocks, so both are topped up to the operational balance
+func (c genesisConfig) validatorOperationalAccounts() []common.Address {
+	var result []common.Address
+	seen := make(map[common.Address]bool)
+	for _, validator := range c.Validators {
+		for _, address := range []common.Address{validator.Address, validator.owner()} {
+			if !seen[address] {
+				seen[address] = true
+				result = append(result, address)
+			}
+		}
+	}
+	return result
+}
+
+// validatorOwners returns owners of validators in the order of validators, the staking ctor registers them
+func (c genesisConfig) validatorOwners() []common.Address {
+	var result []common.Address
+	for _, validator := range c.Validators {
+		result = append(result, validator.owner())
+	}
+	return result
+}
+
+// validatorCommissionRates returns commission rates of validators in basis points for the staking ctor
+func (c genesisConfig) validatorCommissionRates() ([]uint16, error) {
+	var result []uint16
+	for i, validator := range c.Validators {
+		rate, err := validator.commissionRate(c.CommissionRate).basisPoints()
+		if err != nil {
+			return nil, fmt.Errorf("validators[%d].commissionRate %w", i, err)
+		}
+		result = append(result, rate)
+	}
+	return result, nil
+}
@@ -0,0 +1,89 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"math/big"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core"
+)
+
+func TestResolveValidatorKeystores(t *testing.T) {
+	keystoreFile := "keystore/UTC--2021-04-15T12-00-01.000Z--00a601f45688dba8a070722073b015277cf36725"
+	expected := common.HexToAddress("0x00a601f45688dba8a070722073b015277cf36725")
+	config := genesisConfig{}
+	rawConfig := `{"validators": [{"keystore": "` + keystoreFile + `"}, {"address": "0x08fae3885e299c24ff9841478eb946f41023ac69", "keystore": "` + keystoreFile + `"}]}`
+	if err := json.Unmarshal([]byte(rawConfig), &config); err != nil {
+		t.Fatal(err)
+	}
+	err := config.resolveValidatorKeystores(".")
+	if config.Validators[0].Address != expected {
+		t.Fatalf("wrong address from keystore: %s", config.Validators[0].Address.Hex())
+	}
+	violations, ok := err.(configViolations)
+	if !ok || len(violations) != 1 || violations[0].Path != "validators[1].keystore" {
+		t.Fatalf("address mismatch isn't reported: %v", err)
+	}
+}
+
+func TestValidatorOperationalAccounts(t *testing.T) {
+	first, second, owner := common.HexToAddress("0x01"), common.HexToAddress("0x02"), common.HexToAddress("0x03")
+	// the second validator is owned by the first one
+	config := genesisConfig{Validators: []validatorConfig{{Address: first, Owner: &owner}, {Address: second, Owner: &first}}}
+	accounts := config.validatorOperationalAccounts()
+	if len(accounts) != 3 || accounts[0] != first || accounts[1] != owner || accounts[2] != second {
+		t.Fatalf("unexpected accounts %v", accounts)
+	}
+}
+
+func TestValidatorOwnerOperationalBalance(t *testing.T) {
+	config := devNetConfig
+	owner := common.HexToAddress("0x00000000000000000000000000000000000000a1")
+	config.Validators = append([]validatorConfig{}, config.Validators...)
+	config.Validators[0].Owner = &owner
+	config.ValidatorOperationalBalance = (*amountValue)(big.NewInt(1e18))
+	output := &bytes.Buffer{}
+	options := defaultGenerateOptions()
+	options.Output = output
+	if err := createGenesisConfig(config, "", options); err != nil {
+		t.Fatal(err)
+	}
+	genesis := &core.Genesis{}
+	if err := json.Unmarshal(output.Bytes(), genesis); err != nil {
+		t.Fatal(err)
+	}
+	for _, account := range []common.Address{config.Validators[0].Address, owner} {
+		if balance := genesis.Alloc[account].Balance; balance == nil || balance.Cmp(big.NewInt(1e18)) != 0 {
+			t.Fatalf("%s isn't topped up: %v", account.Hex(), balance)
+		}
+	}
+}
+
+func TestValidatorOwnersAndCommissionRates(t *testing.T) {
+	config := devNetConfig
+	owner, rate := common.HexToAddress("0x00000000000000000000000000000000000000a1"), commissionRate(500)
+	config.Validators = append([]validatorConfig{}, config.Validators...)
+	config.Validators[0].Owner, config.Validators[0].CommissionRate = &owner, &rate
+	validator := config.Validators[0].Address.Hex()
+	config.Assertions = []assertionConfig{
+		{
+			Contract: "Staking",
+			Call:     "getValidatorStatus(address)",
+			Args:     json.RawMessage(`["`+validator+`"]`),
+			Expect:   json.RawMessage(`["`+owner.Hex()+`", 1, "1000000000000000000000", 0, 0, 0, 0, 500, 0]`),
+		},
+	}
+	if err := createGenesisConfig(config, filepath.Join(t.TempDir(), "genesis.json"), defaultGenerateOptions()); err != nil {
+		t.Fatal(err)
+	}
+	// staking maps every owner to one validator
+	config.Validators[1].Owner = &owner
+	var violations configViolations
+	if err := config.validate(); !errors.As(err, &violations) || len(violations) != 1 || violations[0].Path != "validators[1].owner" {
+		t.Fatalf("shared owner isn't reported: %v", err)
+	}
+}
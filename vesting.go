@@ -0,0 +1,106 @@
+package main
+
+import (
+	"bytes"
+	_ "embed"
+	"encoding/json"
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/log"
+)
+
+//go:embed build/contracts/GenesisVesting.json
+var genesisVestingRawArtifact []byte
+
+var defaultVestingAddress = common.HexToAddress("0x0000000000000000000000000000000000007100")
+
+// vestingConfig declares allocations locked in the vesting contract from block 0
+type vestingConfig struct {
+	// address of the vesting contract (default is 0x0000000000000000000000000000000000007100)
+	Address *common.Address `json:"address,omitempty"`
+	// vesting start as unix seconds or RFC3339 datetime (default is genesis timestamp)
+	Start     string                  `json:"start,omitempty"`
+	Schedules []vestingScheduleConfig `json:"schedules"`
+}
+
+type vestingScheduleConfig struct {
+	Beneficiary common.Address `json:"beneficiary"`
+	Total       *amountValue   `json:"total"`
+	// cliff and duration are in seconds since the vesting start
+	Cliff    uint64 `json:"cliff"`
+	Duration uint64 `json:"duration"`
+}
+
+func (c *vestingConfig) address() common.Address {
+	if c.Address != nil {
+		return *c.Address
+	}
+	return defaultVestingAddress
+}
+
+func (c *vestingConfig) start(genesisTimestamp uint64) (uint64, error) {
+	if c.Start == "" {
+		return genesisTimestamp, nil
+	}
+	return parseTimestamp(c.Start)
+}
+
+// deployVesting deploys vesting contract and adds all schedules on behalf of the zero address (it's the setup
+// account of the contract, so no schedules can be added after launch), locked funds are minted to the contract
+func deployVesting(simulator *systemContractSimulator, config *vestingConfig) (*big.Int, deploymentFailures) {
+	total := big.NewInt(0)
+	if config == nil {
+		return total, nil
+	}
+	var failures deploymentFailures
+	address := config.address()
+	if account, ok := simulator.genesis.Alloc[address]; ok && len(account.Code) > 0 {
+		failures.add("GenesisVesting", address, fmt.Errorf("address already has code"))
+		return total, failures
+	}
+	artifact := &artifactData{}
+	if err := json.Unmarshal(genesisVestingRawArtifact, artifact); err != nil {
+		failures.add("GenesisVesting", address, err)
+		return total, failures
+	}
+	vestingAbi, err := abi.JSON(bytes.NewReader(artifact.Abi))
+	if err != nil {
+		failures.add("GenesisVesting", address, err)
+		return total, failures
+	}
+	start, err := config.start(simulator.genesis.Timestamp)
+	if err != nil {
+		failures.add("GenesisVesting", address, err)
+		return total, failures
+	}
+	var calls []followUpCall
+	for _, schedule := range config.Schedules {
+		input, err := vestingAbi.Pack("addSchedule", schedule.Beneficiary, start, schedule.Cliff, schedule.Duration)
+		if err != nil {
+			failures.add("GenesisVesting", address, err)
+			return total, failures
+		}
+		value := new(big.Int).Set((*big.Int)(schedule.Total))
+		calls = append(calls, followUpCall{from: common.Address{}, input: input, value: value})
+		total.Add(total, value)
+	}
+	if _, err := simulator.deploy(address, hexutil.MustDecode(artifact.Bytecode), big.NewInt(0)); err != nil {
+		failures.add("GenesisVesting", address, err)
+		return total, failures
+	}
+	// zero address is never persisted into alloc, so locked funds exist only in the contract balance
+	simulator.statedb.AddBalance(common.Address{}, total)
+	if err := simulator.runFollowUpCalls(address, calls); err != nil {
+		failures.add("GenesisVesting", address, err)
+		return total, failures
+	}
+	log.Info("Deployed GenesisVesting", "address", address, "schedules", len(config.Schedules), "start", formatTimestamp(start))
+	for _, schedule := range config.Schedules {
+		log.Debug("Vesting schedule", "beneficiary", schedule.Beneficiary, "total", formatAmount((*big.Int)(schedule.Total)), "cliff", schedule.Cliff, "duration", schedule.Duration)
+	}
+	return total, nil
+}
@@ -0,0 +1,30 @@
+//go:build js && wasm
+// +build js,wasm
+
+package main
+
+import (
+	"syscall/js"
+
+	"github.com/ethereum/go-ethereum/log"
+)
+
+// main of the wasm build exposes generate(configJson) to JavaScript, it returns genesis JSON as a string or an
+// Error if the config is invalid or simulation fails
+func main() {
+	if err := setupLogging(log.LvlWarn, "text"); err != nil {
+		panic(err)
+	}
+	js.Global().Set("generate", js.FuncOf(func(this js.Value, args []js.Value) interface{} {
+		if len(args) != 1 || args[0].Type() != js.TypeString {
+			return js.Global().Get("Error").New("generate expects config JSON string")
+		}
+		genesis, err := generateGenesisJson([]byte(args[0].String()))
+		if err != nil {
+			return js.Global().Get("Error").New(err.Error())
+		}
+		return string(genesis)
+	}))
+	// keep the runtime alive, otherwise the exported function is gone once main returns
+	select {}
+}
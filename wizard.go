@@ -0,0 +1,231 @@
+package main
+
+import (
+	"bufio"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"math/big"
+	"os"
+	"strconv"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/common"
+)
+
+// wizard asks questions on the terminal, wrong answers are asked again
+type wizard struct {
+	in  *bufio.Reader
+	out io.Writer
+}
+
+// ask prints the question with default value and passes the answer (or default if it's empty) to parse
+func (w *wizard) ask(question, defaultValue string, parse func(answer string) error) error {
+	for {
+		if defaultValue != "" {
+			fmt.Fprintf(w.out, "%s [%s]: ", question, defaultValue)
+		} else {
+			fmt.Fprintf(w.out, "%s: ", question)
+		}
+		line, err := w.in.ReadString('\n')
+		if err != nil && (!errors.Is(err, io.EOF) || line == "") {
+			return fmt.Errorf("no answer to %q: %w", question, err)
+		}
+		answer := strings.TrimSpace(line)
+		if answer == "" {
+			answer = defaultValue
+		}
+		if err := parse(answer); err != nil {
+			fmt.Fprintf(w.out, "  %s\n", err)
+			continue
+		}
+		return nil
+	}
+}
+
+func parseWizardAddress(answer string) (common.Address, error) {
+	if !common.IsHexAddress(answer) {
+		return common.Address{}, fmt.Errorf("%q is not an address", answer)
+	}
+	address := common.HexToAddress(answer)
+	if hasMixedCase(answer) && address.Hex() != answer {
+		return common.Address{}, fmt.Errorf("%s has wrong EIP-55 checksum", answer)
+	}
+	return address, nil
+}
+
+func hasMixedCase(value string) bool {
+	value = strings.TrimPrefix(value, "0x")
+	return strings.ToLower(value) != value && strings.ToUpper(value) != value
+}
+
+// runWizard asks for the basic chain settings and returns config built on top of the skeleton of `gen keys`
+func runWizard(in io.Reader, out io.Writer) (orderedObject, error) {
+	w := &wizard{in: bufio.NewReader(in), out: out}
+	var chainId int64
+	err := w.ask("Chain id (must be unique, see chainlist.org)", "", func(answer string) error {
+		value, err := strconv.ParseInt(answer, 0, 64)
+		if err != nil || value <= 0 {
+			return fmt.Errorf("chain id must be positive integer")
+		}
+		if warnings := chainIdWarnings(value); len(warnings) > 0 {
+			fmt.Fprintf(out, "  warning: %s\n", warnings[0])
+		}
+		chainId = value
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	var validators []*generatedValidator
+	err = w.ask("Validator addresses (comma separated, `gen keys` generates them)", "", func(answer string) error {
+		validators = nil
+		seen := make(map[common.Address]bool)
+		for _, value := range strings.Split(answer, ",") {
+			if value = strings.TrimSpace(value); value == "" {
+				continue
+			}
+			address, err := parseWizardAddress(value)
+			if err != nil {
+				return err
+			}
+			if seen[address] {
+				return fmt.Errorf("%s is listed twice", address.Hex())
+			}
+			seen[address] = true
+			validators = append(validators, &generatedValidator{Address: address})
+		}
+		if len(validators) == 0 {
+			return fmt.Errorf("at least one validator is required")
+		}
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	config := genesisConfigSkeleton(chainId, validators)
+	params := localNetConfig.ConsensusParams
+	params.ActiveValidatorsLength = uint32(len(validators))
+	err = w.ask("Initial stake of every validator", "1000 ether", func(answer string) error {
+		amount, err := parseAmount(answer)
+		if err != nil {
+			return err
+		}
+		if amount.Cmp((*big.Int)(params.MinValidatorStakeAmount)) < 0 {
+			return fmt.Errorf("initial stake must be at least %s (min validator stake)", formatAmount((*big.Int)(params.MinValidatorStakeAmount)))
+		}
+		stakes := orderedObject{}
+		for _, validator := range validators {
+			stakes = append(stakes, orderedField{Key: validator.Address.Hex(), Value: formatAmount(amount)})
+		}
+		setConfigField(config, "initialStakes", stakes)
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	err = w.ask("Block period in seconds", strconv.FormatUint(params.BlockPeriodSeconds, 10), func(answer string) error {
+		value, err := strconv.ParseUint(answer, 10, 64)
+		if err != nil || value == 0 {
+			return fmt.Errorf("block period must be positive integer")
+		}
+		params.BlockPeriodSeconds = value
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	err = w.ask("Epoch length in blocks (validator set is updated every epoch)", "1200", func(answer string) error {
+		value, err := strconv.ParseUint(answer, 10, 32)
+		if err != nil || value == 0 {
+			return fmt.Errorf("epoch length must be positive integer")
+		}
+		if _, err := (consensusParams{EpochBlockInterval: uint32(value), BlockPeriodSeconds: params.BlockPeriodSeconds}).blockPeriod(); err != nil {
+			return err
+		}
+		params.EpochBlockInterval = uint32(value)
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	setConfigField(config, "consensusParams", params)
+	err = w.ask("System treasury (receives system fees)", validators[0].Address.Hex(), func(answer string) error {
+		address, err := parseWizardAddress(answer)
+		if err != nil {
+			return err
+		}
+		setConfigField(config, "systemTreasury", address.Hex())
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	var faucet common.Address
+	err = w.ask("Faucet address funded with the token supply (empty for none)", "", func(answer string) error {
+		if answer == "" {
+			return nil
+		}
+		address, err := parseWizardAddress(answer)
+		faucet = address
+		return err
+	})
+	if err != nil || faucet == (common.Address{}) {
+		return config, err
+	}
+	err = w.ask("Token supply of the faucet", "10000000 ether", func(answer string) error {
+		amount, err := parseAmount(answer)
+		if err != nil {
+			return err
+		}
+		setConfigField(config, "faucet", orderedObject{{Key: faucet.Hex(), Value: formatAmount(amount)}})
+		return nil
+	})
+	return config, err
+}
+
+// setConfigField replaces value of the existing top level field
+func setConfigField(config orderedObject, key string, value interface{}) {
+	for i := range config {
+		if config[i].Key == key {
+			config[i].Value = value
+			return
+		}
+	}
+	panic(fmt.Sprintf("there is no %s field in config", key))
+}
+
+// runGenInitCommand writes config of a new chain from answers to a few questions
+func runGenInitCommand(args []string) error {
+	flags := flag.NewFlagSet("gen init", flag.ContinueOnError)
+	outputFile := flags.String("output", "genesis-config.json", "config file to write")
+	force := flags.Bool("force", false, "overwrite existing config file")
+	if err := flags.Parse(args); err != nil {
+		return err
+	}
+	if flags.NArg() != 0 {
+		return fmt.Errorf("usage: gen init [--output <file>] [--force]")
+	}
+	if _, err := os.Stat(*outputFile); err == nil && !*force {
+		return fmt.Errorf("%s already exists, use --force to overwrite it", *outputFile)
+	}
+	config, err := runWizard(os.Stdin, os.Stdout)
+	if err != nil {
+		return err
+	}
+	data, _ := encodeJson(config)
+	if err := writeOutput(*outputFile, append(data, '\n')); err != nil {
+		return err
+	}
+	// answers are checked one by one, the whole config must pass validation of the generator as well
+	parsed, err := readGenesisConfig(*outputFile, false, nil)
+	if err != nil {
+		return err
+	}
+	if err := parsed.validate(); err != nil {
+		return err
+	}
+	fmt.Printf("config is written to %s, generate genesis with: gen --config %s --output genesis.json\n", *outputFile, *outputFile)
+	return nil
+}
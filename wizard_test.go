@@ -0,0 +1,40 @@
+package main
+
+import (
+	"encoding/json"
+	"io"
+	"strings"
+	"testing"
+)
+
+func TestWizard(t *testing.T) {
+	answers := strings.Join([]string{
+		"abc", // not a number, asked again
+		"14000",
+		"0x08fae3885e299c24ff9841478eb946f41023ac69, 0x751aaca849b09a3e347bbfe125cf18423cc24b40",
+		"", // default stake
+		"", // default block period
+		"600",
+		"", // treasury is the first validator
+		"0xb891fe7b38f857f53a7b5529204c58d5c487280b",
+		"1000000 ether",
+	}, "\n") + "\n"
+	result, err := runWizard(strings.NewReader(answers), io.Discard)
+	if err != nil {
+		t.Fatal(err)
+	}
+	data, _ := encodeJson(result)
+	config := &genesisConfig{}
+	if err := json.Unmarshal(data, config); err != nil {
+		t.Fatal(err)
+	}
+	if err := config.validate(); err != nil {
+		t.Fatalf("config of the wizard is invalid: %s\n%s", err, data)
+	}
+	if config.ChainId != 14000 || len(config.Validators) != 2 || config.ConsensusParams.EpochBlockInterval != 600 || len(config.Faucet) != 1 {
+		t.Fatalf("answers are not applied: %s", data)
+	}
+	if _, err := runWizard(strings.NewReader("14000\n"), io.Discard); err == nil {
+		t.Fatalf("missing answers are not reported")
+	}
+}